@@ -17,5 +17,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .file_descriptor_set_path(out_dir.join("news_descriptor.bin"))
         .compile_protos(&[&news], &[&parent])?;
 
+    let streaming = Path::new(gqlforge_fixtures::protobuf::STREAMING);
+
+    tonic_prost_build::configure()
+        .file_descriptor_set_path(out_dir.join("streaming_descriptor.bin"))
+        .compile_protos(&[&streaming], &[&parent])?;
+
     Ok(())
 }
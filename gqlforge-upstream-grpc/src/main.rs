@@ -1,5 +1,6 @@
 mod error;
 
+use std::pin::Pin;
 use std::sync::{Arc, Mutex};
 
 use error::Error;
@@ -13,9 +14,12 @@ use opentelemetry_otlp::{SpanExporter, WithExportConfig, WithTonicConfig};
 use opentelemetry_sdk::Resource;
 use opentelemetry_sdk::propagation::TraceContextPropagator;
 use opentelemetry_sdk::trace::SdkTracerProvider;
+use streaming::event_service_server::{EventService, EventServiceServer};
+use streaming::{Event, EventRequest, EventSummary};
+use tokio_stream::{Stream, StreamExt};
 use tonic::metadata::MetadataMap;
 use tonic::service::interceptor::InterceptedService;
-use tonic::{Request, Response, Status};
+use tonic::{Request, Response, Status, Streaming};
 use tonic_tracing_opentelemetry::middleware::server;
 use tracing_subscriber::layer::SubscriberExt;
 
@@ -25,6 +29,12 @@ pub mod news {
         tonic::include_file_descriptor_set!("news_descriptor");
 }
 
+pub mod streaming {
+    tonic::include_proto!("streaming");
+    pub(crate) const FILE_DESCRIPTOR_SET: &[u8] =
+        tonic::include_file_descriptor_set!("streaming_descriptor");
+}
+
 #[derive(Debug, Default)]
 pub struct MyNewsService {
     news: Arc<Mutex<Vec<News>>>, // Using a simple vector to store news items in memory
@@ -162,6 +172,62 @@ impl NewsService for MyNewsService {
     }
 }
 
+type EventStream = Pin<Box<dyn Stream<Item = Result<Event, Status>> + Send>>;
+
+/// Covers every kind of streaming call: unary, server, client and
+/// bidirectional streaming.
+#[derive(Debug, Default)]
+pub struct MyEventService;
+
+#[tonic::async_trait]
+impl EventService for MyEventService {
+    async fn get_event(
+        &self,
+        request: tonic::Request<EventRequest>,
+    ) -> std::result::Result<Response<Event>, Status> {
+        let topic = request.into_inner().topic;
+        Ok(Response::new(Event { id: 1, data: topic }))
+    }
+
+    type WatchEventsStream = EventStream;
+
+    async fn watch_events(
+        &self,
+        request: tonic::Request<EventRequest>,
+    ) -> std::result::Result<Response<Self::WatchEventsStream>, Status> {
+        let topic = request.into_inner().topic;
+        let events = (1..=3).map(move |id| Ok(Event { id, data: format!("{topic} {id}") }));
+        Ok(Response::new(Box::pin(tokio_stream::iter(events))))
+    }
+
+    async fn record_events(
+        &self,
+        request: tonic::Request<Streaming<EventRequest>>,
+    ) -> std::result::Result<Response<EventSummary>, Status> {
+        let mut requests = request.into_inner();
+        let mut count = 0;
+        while requests.message().await?.is_some() {
+            count += 1;
+        }
+        Ok(Response::new(EventSummary { count }))
+    }
+
+    type ChatStream = EventStream;
+
+    async fn chat(
+        &self,
+        request: tonic::Request<Streaming<EventRequest>>,
+    ) -> std::result::Result<Response<Self::ChatStream>, Status> {
+        // answers every message as soon as it's received
+        let mut id = 0;
+        let events = request.into_inner().map(move |request| {
+            id += 1;
+            request.map(|request| Event { id, data: request.topic })
+        });
+        Ok(Response::new(Box::pin(events)))
+    }
+}
+
 static RESOURCE: Lazy<Resource> = Lazy::new(|| {
     Resource::builder_empty()
         .with_attributes(vec![
@@ -232,6 +298,7 @@ async fn main() -> Result<(), Error> {
     let news_service = MyNewsService::new();
     let reflection = tonic_reflection::server::Builder::configure()
         .register_encoded_file_descriptor_set(news::FILE_DESCRIPTOR_SET)
+        .register_encoded_file_descriptor_set(streaming::FILE_DESCRIPTOR_SET)
         .build_v1()
         .unwrap();
 
@@ -240,6 +307,7 @@ async fn main() -> Result<(), Error> {
     tonic::transport::Server::builder()
         .layer(server::OtelGrpcLayer::default())
         .add_service(NewsServiceServer::new(news_service))
+        .add_service(EventServiceServer::new(MyEventService))
         .add_service(InterceptedService::new(reflection, intercept))
         .serve(addr)
         .await?;
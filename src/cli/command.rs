@@ -1,4 +1,4 @@
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use gqlforge_version::VERSION;
 use strum_macros::Display;
 
@@ -16,6 +16,15 @@ pub struct Cli {
     pub command: Command,
 }
 
+/// Output format of the lint diagnostics reported by `check`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum LintFormat {
+    #[default]
+    Human,
+    Json,
+    Sarif,
+}
+
 #[derive(Subcommand, Display)]
 pub enum Command {
     /// Starts the GraphQL server on the configured port
@@ -43,10 +52,44 @@ pub enum Command {
         #[arg(short, long)]
         n_plus_one_queries: bool,
 
+        /// Directory of `.graphql` operations. When set, only the N plus one
+        /// queries these operations hit are reported, with suggested fixes.
+        #[arg(long, requires = "n_plus_one_queries")]
+        operations: Option<String>,
+
         /// Display schema
         #[arg(short, long)]
         schema: bool,
 
+        /// Output format of lint diagnostics
+        #[arg(long, value_enum, default_value_t = LintFormat::Human)]
+        format: LintFormat,
+
+        /// Controls SSL/TLS certificate verification for remote config files
+        /// Set to false to skip certificate verification (not recommended for
+        /// production)
+        #[arg(short, long, action = clap::ArgAction::Set, default_value_t = true)]
+        verify_ssl: bool,
+    },
+
+    /// Compares two configurations and reports breaking, dangerous and safe
+    /// schema changes
+    Diff {
+        /// Path or http(s) link of the current configuration
+        old: String,
+
+        /// Path or http(s) link of the proposed configuration
+        new: String,
+
+        /// Recorded operations to check against the new configuration. Only
+        /// operations that stop validating are reported as breaking.
+        #[arg(short, long)]
+        operations: Vec<String>,
+
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+
         /// Controls SSL/TLS certificate verification for remote config files
         /// Set to false to skip certificate verification (not recommended for
         /// production)
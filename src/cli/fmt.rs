@@ -1,6 +1,6 @@
 use colored::Colorize;
 
-use crate::core::config::{Config, QueryPath};
+use crate::core::config::{Config, OperationNPlusOne, QueryPath};
 
 pub struct Fmt {}
 
@@ -32,4 +32,15 @@ impl Fmt {
 
         tracing::info!("{}", message);
     }
+
+    pub fn log_n_plus_one_in_operations(found: &[OperationNPlusOne]) {
+        let mut message = format!("N + 1 detected in operations: {}", found.len());
+
+        for n_plus_one in found {
+            message.push('\n');
+            message.push_str(&Fmt::meta(&n_plus_one.to_string()));
+        }
+
+        tracing::info!("{}", message);
+    }
 }
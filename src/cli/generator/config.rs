@@ -11,6 +11,7 @@ use serde::{Deserialize, Serialize};
 use url::Url;
 
 use crate::core::config::transformer::Preset;
+use crate::core::generator::{CrudOperation, DatabaseOptions, NamingConvention};
 use crate::core::http::Method;
 
 #[derive(Deserialize, Serialize, Debug, Default, Setters)]
@@ -83,6 +84,52 @@ pub enum Source<Status = UnResolved> {
     Config {
         src: Location<Status>,
     },
+    /// Describes an HTTP endpoint with JSON Schema documents for its response
+    /// (`src`) and, optionally, its request body (`input`).
+    #[serde(rename_all = "camelCase")]
+    JsonSchema {
+        src: Location<Status>,
+        url: String,
+        field_name: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        input: Option<Location<Status>>,
+        headers: Headers,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        method: Option<Method>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        is_mutation: Option<bool>,
+    },
+    /// Introspects a live `PostgreSQL` database.
+    #[serde(rename_all = "camelCase")]
+    Postgres {
+        src: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        tables: Option<Vec<String>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        exclude: Option<Vec<String>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        naming: Option<NamingConvention>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        operations: Option<Vec<CrudOperation>>,
+    },
+    /// Parses `.sql` migration files. `src` may be a glob such as
+    /// `migrations/*.sql`; matches are applied in lexical order.
+    #[serde(rename_all = "camelCase")]
+    Sql {
+        src: Location<Status>,
+        /// Connection URL used at runtime. When omitted only the `Sql` links
+        /// are generated.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        url: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        tables: Option<Vec<String>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        exclude: Option<Vec<String>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        naming: Option<NamingConvention>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        operations: Option<Vec<CrudOperation>>,
+    },
 }
 
 #[derive(Deserialize, Serialize, Debug, Default)]
@@ -246,10 +293,46 @@ impl Source<UnResolved> {
                 let resolved_path = src.into_resolved(parent_dir);
                 Ok(Source::Config { src: resolved_path })
             }
+            Source::JsonSchema { src, url, field_name, input, headers, method, is_mutation } => {
+                Ok(Source::JsonSchema {
+                    src: src.into_resolved(parent_dir),
+                    url,
+                    field_name,
+                    input: input.map(|input| input.into_resolved(parent_dir)),
+                    headers,
+                    method,
+                    is_mutation,
+                })
+            }
+            Source::Postgres { src, tables, exclude, naming, operations } => {
+                Ok(Source::Postgres { src, tables, exclude, naming, operations })
+            }
+            Source::Sql { src, url, tables, exclude, naming, operations } => {
+                let resolved_path = src.into_resolved(parent_dir);
+                Ok(Source::Sql { src: resolved_path, url, tables, exclude, naming, operations })
+            }
         }
     }
 }
 
+/// Builds the `from_database` options from the filters of a `postgres` or
+/// `sql` source, falling back to the defaults for anything left unset.
+#[must_use]
+pub fn database_options(
+    tables: Option<Vec<String>>,
+    exclude: Option<Vec<String>>,
+    naming: Option<NamingConvention>,
+    operations: Option<Vec<CrudOperation>>,
+) -> DatabaseOptions {
+    let default = DatabaseOptions::default();
+    DatabaseOptions {
+        tables: tables.unwrap_or(default.tables),
+        exclude: exclude.unwrap_or(default.exclude),
+        naming: naming.unwrap_or(default.naming),
+        operations: operations.unwrap_or(default.operations),
+    }
+}
+
 impl Input<UnResolved> {
     ///
     /// # Errors
@@ -436,6 +519,76 @@ mod tests {
         assert_deserialization_error(json, expected_error);
     }
 
+    #[test]
+    fn test_database_sources() {
+        let json = r#"
+            {"inputs": [
+                {"postgres": {
+                    "src": "postgres://localhost/app",
+                    "tables": ["users", "public.post*"],
+                    "exclude": ["schema_migrations"],
+                    "naming": "snakeCase",
+                    "operations": ["read", "create"]
+                }},
+                {"sql": {"src": "migrations/*.sql"}}
+            ]}
+        "#;
+        let config: Config<UnResolved> = serde_json::from_str(json).unwrap();
+
+        let Source::Postgres { src, tables, exclude, naming, operations } =
+            &config.inputs[0].source
+        else {
+            panic!("expected a postgres source");
+        };
+        assert_eq!(src, "postgres://localhost/app");
+        let options =
+            database_options(tables.clone(), exclude.clone(), *naming, operations.clone());
+        assert_eq!(options.tables, vec!["users", "public.post*"]);
+        assert_eq!(options.exclude, vec!["schema_migrations"]);
+        assert_eq!(options.naming, NamingConvention::SnakeCase);
+        assert_eq!(
+            options.operations,
+            vec![CrudOperation::Read, CrudOperation::Create]
+        );
+
+        let Source::Sql { src, url, .. } = &config.inputs[1].source else {
+            panic!("expected a sql source");
+        };
+        assert_eq!(src.0, "migrations/*.sql");
+        assert_eq!(url, &None);
+    }
+
+    #[test]
+    fn test_json_schema_source() {
+        let json = r#"
+            {"inputs": [
+                {"jsonSchema": {
+                    "src": "schemas/user.json",
+                    "url": "https://example.com/users",
+                    "fieldName": "createUser",
+                    "input": "schemas/new-user.json",
+                    "isMutation": true
+                }}
+            ]}
+        "#;
+        let config: Config<UnResolved> = serde_json::from_str(json).unwrap();
+        let input = config.inputs.into_iter().next().unwrap();
+        let input = input.resolve(Some(Path::new("/tmp"))).unwrap();
+
+        let Source::JsonSchema { src, field_name, input, method, is_mutation, .. } = &input.source
+        else {
+            panic!("expected a jsonSchema source");
+        };
+        assert_eq!(src.0, "/tmp/schemas/user.json");
+        assert_eq!(field_name, "createUser");
+        assert_eq!(
+            input.as_ref().map(|input| input.0.as_str()),
+            Some("/tmp/schemas/new-user.json")
+        );
+        assert_eq!(method, &None);
+        assert_eq!(is_mutation, &Some(true));
+    }
+
     #[test]
     fn test_raise_error_unknown_field_in_preset() {
         let json = r#"
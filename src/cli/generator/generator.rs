@@ -7,14 +7,16 @@ use http::header::{HeaderMap, HeaderName, HeaderValue};
 use inquire::Confirm;
 use pathdiff::diff_paths;
 
-use super::config::{Config, Resolved, Source};
+use super::config::{Config, Resolved, Source, database_options};
 use super::source::ConfigSource;
 use crate::core::Mustache;
 use crate::core::config::transformer::Preset;
 use crate::core::config::{self, ConfigModule, ConfigReaderContext};
 use crate::core::generator::{Generator as ConfigGenerator, Input};
+use crate::core::http::Method;
+use crate::core::postgres::{introspector, sql_parser};
 use crate::core::proto_reader::ProtoReader;
-use crate::core::resource_reader::{Resource, ResourceReader};
+use crate::core::resource_reader::{Cached, Resource, ResourceReader};
 use crate::core::runtime::TargetRuntime;
 
 /// CLI that reads the the config file and generates the required gqlforge
@@ -174,6 +176,59 @@ impl Generator {
                     let schema = reader.read_file(path).await?.content;
                     input_samples.push(Input::Config { schema, source });
                 }
+                Source::JsonSchema {
+                    src,
+                    url,
+                    field_name,
+                    input,
+                    headers,
+                    method,
+                    is_mutation,
+                } => {
+                    let is_mutation = is_mutation.unwrap_or_default();
+                    let method = method.unwrap_or(if is_mutation {
+                        Method::POST
+                    } else {
+                        Method::GET
+                    });
+                    let output = read_json_schema(&reader, src.0).await?;
+                    let input = match input {
+                        Some(input) => Some(read_json_schema(&reader, input.0).await?),
+                        None => None,
+                    };
+                    input_samples.push(Input::JsonSchema {
+                        url: url.parse()?,
+                        method,
+                        field_name,
+                        is_mutation,
+                        headers: headers.into_btree_map(),
+                        output,
+                        input,
+                    });
+                }
+                Source::Postgres { src, tables, exclude, naming, operations } => {
+                    let database_schema = introspector::introspect(&src).await?;
+                    input_samples.push(Input::Postgres {
+                        database_schema,
+                        connection_url: src,
+                        options: database_options(tables, exclude, naming, operations),
+                    });
+                }
+                Source::Sql { src, url, tables, exclude, naming, operations } => {
+                    let mut contents = vec![];
+                    let mut migrations = vec![];
+                    for path in expand_glob(&src.0)? {
+                        contents.push(reader.read_file(path.clone()).await?.content);
+                        migrations.push(to_relative_path(output_dir, &path).unwrap_or(path));
+                    }
+                    let database_schema = sql_parser::parse_migrations(&contents)?;
+                    input_samples.push(Input::Sql {
+                        database_schema,
+                        migrations,
+                        connection_url: url,
+                        options: database_options(tables, exclude, naming, operations),
+                    });
+                }
             }
         }
 
@@ -218,6 +273,31 @@ fn is_exists(path: &str) -> bool {
     fs::metadata(path).is_ok()
 }
 
+/// Reads a JSON Schema document, written either as JSON or YAML.
+async fn read_json_schema(
+    reader: &ResourceReader<Cached>,
+    path: String,
+) -> anyhow::Result<serde_json::Value> {
+    let source = config::Source::detect(&path);
+    let content = reader.read_file(path).await?.content;
+    match source {
+        Ok(config::Source::Yml) => Ok(serde_yaml_ng::from_str(&content)?),
+        _ => Ok(serde_json::from_str(&content)?),
+    }
+}
+
+/// Expands a glob pattern into the matching file paths in lexical order.
+fn expand_glob(pattern: &str) -> anyhow::Result<Vec<String>> {
+    let mut paths = glob::glob(pattern)?
+        .map(|path| Ok(path?.to_string_lossy().to_string()))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    if paths.is_empty() {
+        return Err(anyhow!("No files found matching '{pattern}'"));
+    }
+    paths.sort();
+    Ok(paths)
+}
+
 /// Expects both paths to be absolute and returns a relative path from `from` to
 /// `to`. expects `from` to be directory.
 fn to_relative_path(from: &Path, to: &str) -> Option<String> {
@@ -7,6 +7,7 @@ pub mod metrics;
 pub mod postgres;
 pub mod runtime;
 pub mod s3;
+mod sampler;
 pub mod server;
 mod tc;
 pub mod telemetry;
@@ -8,6 +8,7 @@ use http_cache_reqwest::{Cache, CacheMode, HttpCache, HttpCacheOptions};
 use hyper_util::client::legacy::Client as H2Client;
 use hyper_util::rt::TokioExecutor;
 use opentelemetry::KeyValue;
+use opentelemetry::baggage::BaggageExt;
 use opentelemetry::metrics::Counter;
 use opentelemetry::trace::SpanKind;
 use opentelemetry_http::HeaderInjector;
@@ -81,6 +82,8 @@ pub struct NativeHttp {
     h2_client: Option<GrpcH2Client>,
     http2_only: bool,
     enable_telemetry: bool,
+    /// Keys of the baggage entries sent along with requests.
+    baggage: Vec<String>,
 }
 
 impl Default for NativeHttp {
@@ -90,6 +93,7 @@ impl Default for NativeHttp {
             h2_client: None,
             http2_only: false,
             enable_telemetry: false,
+            baggage: Vec::new(),
         }
     }
 }
@@ -155,8 +159,25 @@ impl NativeHttp {
             h2_client: Some(h2_client),
             http2_only: upstream.http2_only,
             enable_telemetry: telemetry.export.is_some(),
+            baggage: telemetry.baggage.clone(),
         }
     }
+
+    /// Context of the current span, with only the baggage entries that are
+    /// configured to be sent to upstreams.
+    fn upstream_context(&self) -> opentelemetry::Context {
+        let context = tracing::Span::current().context();
+        let baggage = self
+            .baggage
+            .iter()
+            .filter_map(|key| {
+                let value = context.baggage().get(key.as_str())?;
+                Some(KeyValue::new(key.clone(), value.clone()))
+            })
+            .collect::<Vec<_>>();
+
+        context.with_cleared_baggage().with_baggage(baggage)
+    }
 }
 
 #[async_trait::async_trait]
@@ -181,11 +202,9 @@ impl HttpIO for NativeHttp {
         let mut req_counter = RequestCounter::new(self.enable_telemetry, &request);
 
         if self.enable_telemetry {
+            let context = self.upstream_context();
             opentelemetry::global::get_text_map_propagator(|propagator| {
-                propagator.inject_context(
-                    &tracing::Span::current().context(),
-                    &mut HeaderInjector(request.headers_mut()),
-                );
+                propagator.inject_context(&context, &mut HeaderInjector(request.headers_mut()));
             });
         }
 
@@ -0,0 +1,228 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use opentelemetry::trace::{
+    Link, SamplingDecision, SamplingResult, SpanContext, SpanKind, Status, TraceContextExt, TraceId,
+};
+use opentelemetry::{Context, KeyValue};
+use opentelemetry_sdk::Resource;
+use opentelemetry_sdk::error::OTelSdkResult;
+use opentelemetry_sdk::trace::{Sampler, ShouldSample, Span, SpanData, SpanProcessor};
+
+use crate::core::config::{TelemetrySampler, TelemetrySampling};
+use crate::core::jit::GRAPHQL_OPERATION_NAME;
+
+fn to_sampler(sampler: &TelemetrySampler) -> Sampler {
+    match sampler {
+        TelemetrySampler::Always => Sampler::AlwaysOn,
+        TelemetrySampler::Never => Sampler::AlwaysOff,
+        TelemetrySampler::Ratio(ratio) => Sampler::TraceIdRatioBased(*ratio),
+        TelemetrySampler::ParentBased(root) => Sampler::ParentBased(Box::new(to_sampler(root))),
+    }
+}
+
+/// Samples traces with `sampling.sampler`, and the spans of the operations
+/// listed in `sampling.operations` with their own sampler.
+#[derive(Clone, Debug)]
+pub struct TraceSampler {
+    sampler: Sampler,
+    operations: HashMap<String, Sampler>,
+    /// Whether spans that aren't sampled are still recorded, for
+    /// [`ErrorSpans`] to export the failed ones.
+    record_errors: bool,
+}
+
+impl TraceSampler {
+    #[must_use]
+    pub fn new(sampling: &TelemetrySampling) -> Self {
+        let sampler = sampling.sampler.as_ref().map_or_else(
+            || Sampler::ParentBased(Box::new(Sampler::AlwaysOn)),
+            to_sampler,
+        );
+        let operations = sampling
+            .operations
+            .iter()
+            .map(|operation| (operation.operation.clone(), to_sampler(&operation.sampler)))
+            .collect();
+
+        Self { sampler, operations, record_errors: sampling.errors() }
+    }
+
+    fn operation_sampler(&self, attributes: &[KeyValue]) -> Option<&Sampler> {
+        let name = attributes
+            .iter()
+            .find(|attribute| attribute.key.as_str() == GRAPHQL_OPERATION_NAME)?
+            .value
+            .as_str();
+
+        self.operations.get(name.as_ref())
+    }
+}
+
+impl ShouldSample for TraceSampler {
+    fn should_sample(
+        &self,
+        parent_context: Option<&Context>,
+        trace_id: TraceId,
+        name: &str,
+        span_kind: &SpanKind,
+        attributes: &[KeyValue],
+        links: &[Link],
+    ) -> SamplingResult {
+        let parent = parent_context
+            .filter(|cx| cx.has_active_span())
+            .map(|cx| cx.span().span_context().clone());
+
+        let mut result = if let Some(sampler) = self.operation_sampler(attributes) {
+            sampler.should_sample(parent_context, trace_id, name, span_kind, attributes, links)
+        } else if let Some(parent) =
+            parent.filter(|parent| !parent.is_remote() && parent.is_sampled())
+        {
+            // Keeps the spans of operations sampled by their own sampler
+            SamplingResult {
+                decision: SamplingDecision::RecordAndSample,
+                attributes: Vec::new(),
+                trace_state: parent.trace_state().clone(),
+            }
+        } else {
+            self.sampler
+                .should_sample(parent_context, trace_id, name, span_kind, attributes, links)
+        };
+
+        if self.record_errors && matches!(result.decision, SamplingDecision::Drop) {
+            result.decision = SamplingDecision::RecordOnly;
+        }
+
+        result
+    }
+}
+
+/// Exports the spans that failed in traces that aren't sampled. Only spans
+/// recorded by [`TraceSampler`] reach it when they aren't sampled.
+#[derive(Debug)]
+pub struct ErrorSpans<P>(pub P);
+
+impl<P: SpanProcessor> SpanProcessor for ErrorSpans<P> {
+    fn on_start(&self, span: &mut Span, cx: &Context) {
+        self.0.on_start(span, cx);
+    }
+
+    fn on_end(&self, mut span: SpanData) {
+        if !span.span_context.is_sampled() {
+            if !matches!(span.status, Status::Error { .. }) {
+                return;
+            }
+
+            let cx = &span.span_context;
+            span.span_context = SpanContext::new(
+                cx.trace_id(),
+                cx.span_id(),
+                cx.trace_flags().with_sampled(true),
+                cx.is_remote(),
+                cx.trace_state().clone(),
+            );
+        }
+
+        self.0.on_end(span);
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        self.0.force_flush()
+    }
+
+    fn shutdown_with_timeout(&self, timeout: Duration) -> OTelSdkResult {
+        self.0.shutdown_with_timeout(timeout)
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        self.0.set_resource(resource);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use opentelemetry::trace::{SpanId, TraceFlags, TraceState};
+
+    use super::*;
+    use crate::core::config::OperationSampler;
+
+    fn sample(
+        sampler: &TraceSampler,
+        parent: Option<&Context>,
+        operation: Option<&str>,
+    ) -> SamplingDecision {
+        let attributes = operation
+            .map(|name| vec![KeyValue::new(GRAPHQL_OPERATION_NAME, name.to_string())])
+            .unwrap_or_default();
+
+        sampler
+            .should_sample(
+                parent,
+                TraceId::from_bytes(1u128.to_be_bytes()),
+                "graphql_operation",
+                &SpanKind::Internal,
+                &attributes,
+                &[],
+            )
+            .decision
+    }
+
+    fn parent(is_sampled: bool) -> Context {
+        let flags = if is_sampled {
+            TraceFlags::SAMPLED
+        } else {
+            TraceFlags::default()
+        };
+        Context::new().with_remote_span_context(SpanContext::new(
+            TraceId::from_bytes(1u128.to_be_bytes()),
+            SpanId::from_bytes(1u64.to_be_bytes()),
+            flags,
+            false,
+            TraceState::default(),
+        ))
+    }
+
+    #[test]
+    fn samples_operations_with_their_sampler() {
+        let sampler = TraceSampler::new(&TelemetrySampling {
+            sampler: Some(TelemetrySampler::Never),
+            operations: vec![OperationSampler {
+                operation: "Checkout".to_string(),
+                sampler: TelemetrySampler::Always,
+            }],
+            errors: None,
+        });
+
+        assert!(matches!(
+            sample(&sampler, None, Some("Checkout")),
+            SamplingDecision::RecordAndSample
+        ));
+        assert!(matches!(
+            sample(&sampler, None, Some("Users")),
+            SamplingDecision::Drop
+        ));
+        // Spans of a sampled operation are sampled too
+        assert!(matches!(
+            sample(&sampler, Some(&parent(true)), None),
+            SamplingDecision::RecordAndSample
+        ));
+        assert!(matches!(
+            sample(&sampler, Some(&parent(false)), None),
+            SamplingDecision::Drop
+        ));
+    }
+
+    #[test]
+    fn records_spans_for_errors() {
+        let sampler = TraceSampler::new(&TelemetrySampling {
+            sampler: Some(TelemetrySampler::Ratio(0.0)),
+            errors: Some(true),
+            ..Default::default()
+        });
+
+        assert!(matches!(
+            sample(&sampler, None, None),
+            SamplingDecision::RecordOnly
+        ));
+    }
+}
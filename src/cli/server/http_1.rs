@@ -44,24 +44,23 @@ pub async fn start_http_1(
             .or(Err(anyhow::anyhow!("Failed to send message")))?;
     }
 
-    let graphql_endpoint = sc.blueprint.server.routes.graphql().clone();
-
     loop {
         let (stream, _addr) = listener.accept().await?;
         let io = TokioIo::new(stream);
         let sc = sc.clone();
-        let graphql_endpoint = graphql_endpoint.clone();
 
         tokio::spawn(async move {
             let svc = service_fn(move |req: http::Request<Incoming>| {
                 let sc = sc.clone();
-                let graphql_endpoint = graphql_endpoint.clone();
                 async move {
                     let (parts, body) = req.into_parts();
                     let bytes = body.collect().await?.to_bytes();
 
+                    // Subscriptions, like queries, are executed with the contract
+                    // of the route they are sent to
+                    let schema_ctx = sc.app_ctx.schema_context(parts.uri.path());
                     let is_sse = parts.method == Method::POST
-                        && parts.uri.path() == graphql_endpoint
+                        && sc.app_ctx.is_graphql_route(parts.uri.path())
                         && serde_json::from_slice::<GraphQLRequest>(&bytes)
                             .map(|mut r| r.is_subscription())
                             .unwrap_or(false);
@@ -69,7 +68,7 @@ pub async fn start_http_1(
                     let req = http::Request::from_parts(parts, Full::new(bytes));
 
                     if is_sse {
-                        match handle_sse_request(req, sc.app_ctx.clone()).await {
+                        match handle_sse_request(req, schema_ctx).await {
                             Ok(resp) => Ok(resp.map(Either::Right)),
                             Err(e) => {
                                 tracing::error!("SSE handler error: {}", e);
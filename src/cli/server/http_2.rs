@@ -7,17 +7,22 @@ use hyper::body::Incoming;
 use hyper::service::service_fn;
 use hyper_util::rt::{TokioExecutor, TokioIo};
 use hyper_util::server::conn::auto::Builder;
+use rustls::RootCertStore;
+use rustls::server::WebPkiClientVerifier;
 use rustls_pki_types::CertificateDer;
-use tokio::net::TcpListener;
+use tokio::net::{TcpListener, TcpStream};
 use tokio::sync::oneshot;
 use tokio_rustls::TlsAcceptor;
+use tokio_rustls::server::TlsStream;
 
 use super::server_config::ServerConfig;
 use crate::core::Errata;
 use crate::core::async_graphql_hyper::{GraphQLBatchRequest, GraphQLRequest, GraphQLRequestLike};
-use crate::core::config::PrivateKey;
-use crate::core::http::handle_request;
+use crate::core::blueprint::ClientAuth;
+use crate::core::config::{ClientAuthMode, PrivateKey};
+use crate::core::http::rpc::{handle_rpc_request, is_rpc_request};
 use crate::core::http::sse::{SseBody, handle_sse_request};
+use crate::core::http::{PeerCertificate, handle_request};
 
 ///
 /// # Errors
@@ -35,16 +40,30 @@ pub async fn start_http_2(
     sc: Arc<ServerConfig>,
     cert: Vec<CertificateDer<'static>>,
     key: PrivateKey,
+    client_auth: Option<ClientAuth>,
     server_up_sender: Option<oneshot::Sender<()>>,
 ) -> anyhow::Result<()> {
     let addr = sc.addr();
 
-    let mut server_config = rustls::ServerConfig::builder_with_provider(Arc::new(
-        rustls::crypto::aws_lc_rs::default_provider(),
-    ))
-    .with_safe_default_protocol_versions()?
-    .with_no_client_auth()
-    .with_single_cert(cert, key.into_inner())?;
+    let provider = Arc::new(rustls::crypto::aws_lc_rs::default_provider());
+    let builder = rustls::ServerConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()?;
+    let builder = match client_auth {
+        Some(client_auth) => {
+            let mut roots = RootCertStore::empty();
+            for ca in client_auth.ca {
+                roots.add(ca)?;
+            }
+            let verifier = WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider);
+            let verifier = match client_auth.mode {
+                ClientAuthMode::Require => verifier,
+                ClientAuthMode::Optional => verifier.allow_unauthenticated(),
+            };
+            builder.with_client_cert_verifier(verifier.build()?)
+        }
+        None => builder.with_no_client_auth(),
+    };
+    let mut server_config = builder.with_single_cert(cert, key.into_inner())?;
     server_config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
     let tls_acceptor = TlsAcceptor::from(Arc::new(server_config));
 
@@ -59,13 +78,10 @@ pub async fn start_http_2(
             .or(Err(anyhow::anyhow!("Failed to send message")))?;
     }
 
-    let graphql_endpoint = sc.blueprint.server.routes.graphql().clone();
-
     loop {
         let (stream, _addr) = listener.accept().await?;
         let tls_acceptor = tls_acceptor.clone();
         let sc = sc.clone();
-        let graphql_endpoint = graphql_endpoint.clone();
 
         tokio::spawn(async move {
             let tls_stream = match tls_acceptor.accept(stream).await {
@@ -75,25 +91,40 @@ pub async fn start_http_2(
                     return;
                 }
             };
+            let peer_certificate = peer_certificate(&tls_stream);
             let io = TokioIo::new(tls_stream);
 
             let svc = service_fn(move |req: http::Request<Incoming>| {
                 let sc = sc.clone();
-                let graphql_endpoint = graphql_endpoint.clone();
+                let peer_certificate = peer_certificate.clone();
                 async move {
-                    let (parts, body) = req.into_parts();
+                    let (mut parts, body) = req.into_parts();
+                    if let Some(peer_certificate) = peer_certificate {
+                        parts.extensions.insert(peer_certificate);
+                    }
                     let bytes = body.collect().await?.to_bytes();
 
+                    // Subscriptions, like queries, are executed with the contract
+                    // of the route they are sent to
+                    let schema_ctx = sc.app_ctx.schema_context(parts.uri.path());
                     let is_sse = parts.method == Method::POST
-                        && parts.uri.path() == graphql_endpoint
+                        && sc.app_ctx.is_graphql_route(parts.uri.path())
                         && serde_json::from_slice::<GraphQLRequest>(&bytes)
                             .map(|mut r| r.is_subscription())
                             .unwrap_or(false);
 
+                    let is_rpc = parts.method == Method::POST && is_rpc_request(&parts.headers);
+
                     let req = http::Request::from_parts(parts, Full::new(bytes));
 
-                    if is_sse {
-                        match handle_sse_request(req, sc.app_ctx.clone()).await {
+                    if is_rpc {
+                        // gRPC calls are answered with their own body type, which shares
+                        // the stream body of SSE responses.
+                        handle_rpc_request(req, &sc.app_ctx.rpc_methods, schema_ctx)
+                            .await
+                            .map(|resp| resp.map(Either::Right))
+                    } else if is_sse {
+                        match handle_sse_request(req, schema_ctx).await {
                             Ok(resp) => Ok(resp.map(Either::Right)),
                             Err(e) => {
                                 tracing::error!("SSE handler error: {}", e);
@@ -125,3 +156,15 @@ pub async fn start_http_2(
         });
     }
 }
+
+/// Parses the leaf certificate the client presented during the handshake.
+fn peer_certificate(tls_stream: &TlsStream<TcpStream>) -> Option<Arc<PeerCertificate>> {
+    let cert = tls_stream.get_ref().1.peer_certificates()?.first()?;
+    match PeerCertificate::from_der(cert) {
+        Ok(cert) => Some(Arc::new(cert)),
+        Err(e) => {
+            tracing::warn!("Failed to parse client certificate: {}", e);
+            None
+        }
+    }
+}
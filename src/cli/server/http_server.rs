@@ -11,6 +11,8 @@ use crate::cli::telemetry::init_opentelemetry;
 use crate::core::Errata;
 use crate::core::blueprint::{Blueprint, Http};
 use crate::core::config::ConfigModule;
+use crate::core::proto_reader::ProtoReader;
+use crate::core::resource_reader::ResourceReader;
 
 pub struct Server {
     config_module: ConfigModule,
@@ -39,15 +41,24 @@ impl Server {
     pub async fn start(self) -> Result<()> {
         let blueprint = Blueprint::try_from(&self.config_module).map_err(Errata::from)?;
         let endpoints = self.config_module.extensions().endpoint_set.clone();
+        let methods = self.config_module.extensions().method_set.clone();
         let s3_configs = self.config_module.extensions().s3_configs.clone();
-        let server_config =
-            Arc::new(ServerConfig::new(blueprint.clone(), endpoints, &s3_configs).await?);
+        let server_config = Arc::new(
+            ServerConfig::new(blueprint.clone(), endpoints, methods, &s3_configs).await?,
+        );
 
         init_opentelemetry(blueprint.telemetry.clone(), &server_config.app_ctx.runtime).await?;
 
+        let runtime = &server_config.app_ctx.runtime;
+        let proto_reader =
+            ProtoReader::init(ResourceReader::cached(runtime.clone()), runtime.clone());
+        for refresh in self.config_module.extensions().grpc_refresh.clone() {
+            refresh.spawn(proto_reader.clone());
+        }
+
         match blueprint.server.http.clone() {
-            Http::HTTP2 { cert, key } => {
-                start_http_2(server_config, cert, key, self.server_up_sender).await
+            Http::HTTP2 { cert, key, client_auth } => {
+                start_http_2(server_config, cert, key, client_auth, self.server_up_sender).await
             }
             Http::HTTP1 => start_http_1(server_config, self.server_up_sender).await,
         }
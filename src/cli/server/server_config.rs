@@ -6,6 +6,7 @@ use crate::core::app_context::AppContext;
 use crate::core::blueprint::{Blueprint, Http};
 use crate::core::config::S3LinkConfig;
 use crate::core::rest::{EndpointSet, Unchecked};
+use crate::core::rpc::MethodSet;
 
 pub struct ServerConfig {
     pub blueprint: Blueprint,
@@ -20,6 +21,7 @@ impl ServerConfig {
     pub async fn new(
         blueprint: Blueprint,
         endpoints: EndpointSet<Unchecked>,
+        methods: MethodSet<Unchecked>,
         s3_configs: &[S3LinkConfig],
     ) -> anyhow::Result<Self> {
         let mut rt = init(&blueprint)?;
@@ -40,7 +42,10 @@ impl ServerConfig {
         }
 
         let endpoints = endpoints.into_checked(&blueprint, rt.clone()).await?;
-        let app_context = Arc::new(AppContext::new(blueprint.clone(), rt, endpoints));
+        let rpc_methods = methods.into_checked(&blueprint)?;
+        let mut app_context = AppContext::new(blueprint.clone(), rt, endpoints);
+        app_context.rpc_methods = rpc_methods;
+        let app_context = Arc::new(app_context);
 
         Ok(Self { app_ctx: app_context, blueprint })
     }
@@ -51,7 +56,7 @@ impl ServerConfig {
 
     pub fn http_version(&self) -> String {
         match self.blueprint.server.http {
-            Http::HTTP2 { cert: _, key: _ } => "HTTP/2".to_string(),
+            Http::HTTP2 { .. } => "HTTP/2".to_string(),
             Http::HTTP1 => "HTTP/1.1".to_string(),
         }
     }
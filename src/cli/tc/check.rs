@@ -1,21 +1,34 @@
-use anyhow::Result;
+use anyhow::{Result, anyhow};
+use async_graphql::parser::types::ExecutableDocument;
 
-use super::helpers::{display_schema, log_endpoint_set};
+use super::helpers::{display_proto, display_schema, log_endpoint_set};
+use super::lint::{display_diagnostics, read_lint_config};
+use crate::cli::command::LintFormat;
 use crate::cli::fmt::Fmt;
 use crate::core::Errata;
 use crate::core::blueprint::Blueprint;
+use crate::core::config::LintSeverity;
 use crate::core::config::reader::ConfigReader;
 use crate::core::runtime::TargetRuntime;
 
 pub(super) struct CheckParams {
     pub(super) file_paths: Vec<String>,
     pub(super) n_plus_one_queries: bool,
+    pub(super) operations: Option<String>,
     pub(super) schema: bool,
+    pub(super) format: LintFormat,
     pub(super) runtime: TargetRuntime,
 }
 
 pub(super) async fn check_command(params: CheckParams, config_reader: &ConfigReader) -> Result<()> {
-    let CheckParams { file_paths, n_plus_one_queries, schema, runtime } = params;
+    let CheckParams {
+        file_paths,
+        n_plus_one_queries,
+        operations,
+        schema,
+        format,
+        runtime,
+    } = params;
 
     let config_module = (config_reader.read_all(&file_paths)).await?;
     log_endpoint_set(&config_module.extensions().endpoint_set);
@@ -24,16 +37,42 @@ pub(super) async fn check_command(params: CheckParams, config_reader: &ConfigRea
     match blueprint {
         Ok(blueprint) => {
             tracing::info!("Config {} ... ok", file_paths.join(", "));
-            Fmt::log_n_plus_one(n_plus_one_queries, config_module.config());
+            match operations {
+                Some(dir) => {
+                    let found = read_operations(&runtime, &dir)
+                        .await?
+                        .iter()
+                        .flat_map(|document| config_module.config().n_plus_one_in(document))
+                        .collect::<Vec<_>>();
+                    Fmt::log_n_plus_one_in_operations(&found);
+                }
+                None => Fmt::log_n_plus_one(n_plus_one_queries, config_module.config()),
+            }
             // Check the endpoints' schema
             let _ = config_module
                 .extensions()
                 .endpoint_set
                 .clone()
-                .into_checked(&blueprint, runtime)
+                .into_checked(&blueprint, runtime.clone())
                 .await?;
+            // Check the gRPC methods served from `@rpc` operations
+            let method_set = &config_module.extensions().method_set;
+            let _ = method_set.clone().into_checked(&blueprint)?;
             if schema {
                 display_schema(&blueprint);
+                display_proto(method_set, &blueprint)?;
+            }
+
+            let lint_config = read_lint_config(&runtime, &file_paths).await?;
+            let diagnostics = config_module.config().lint(&lint_config)?;
+            display_diagnostics(&diagnostics, format, &file_paths)?;
+
+            let errors = diagnostics
+                .iter()
+                .filter(|diagnostic| diagnostic.severity == LintSeverity::Error)
+                .count();
+            if errors > 0 {
+                return Err(anyhow!("Lint failed with {errors} error(s)"));
             }
 
             Ok(())
@@ -41,3 +80,23 @@ pub(super) async fn check_command(params: CheckParams, config_reader: &ConfigRea
         Err(e) => Err(e.into()),
     }
 }
+
+/// Parses every `.graphql` / `.gql` file in `dir`, in file name order.
+async fn read_operations(runtime: &TargetRuntime, dir: &str) -> Result<Vec<ExecutableDocument>> {
+    let dir = glob::Pattern::escape(dir);
+    let mut paths = vec![];
+    for ext in ["graphql", "gql"] {
+        for path in glob::glob(&format!("{dir}/*.{ext}"))? {
+            paths.push(path?.to_string_lossy().to_string());
+        }
+    }
+    paths.sort();
+
+    let mut documents = vec![];
+    for path in paths {
+        let content = runtime.file.read(&path).await?;
+        documents
+            .push(async_graphql::parser::parse_query(content).map_err(|e| anyhow!("{path}: {e}"))?);
+    }
+    Ok(documents)
+}
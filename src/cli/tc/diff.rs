@@ -0,0 +1,216 @@
+use anyhow::{Result, anyhow};
+use serde::Serialize;
+
+use crate::cli::fmt::Fmt;
+use crate::core::Errata;
+use crate::core::blueprint::{Blueprint, ChangeSeverity, SchemaDiff};
+use crate::core::config::reader::ConfigReader;
+use crate::core::rest::EndpointSet;
+use crate::core::runtime::TargetRuntime;
+
+pub(super) struct DiffParams {
+    pub(super) old: String,
+    pub(super) new: String,
+    pub(super) operations: Vec<String>,
+    pub(super) json: bool,
+    pub(super) runtime: TargetRuntime,
+}
+
+/// A recorded operation that validates against the old schema but not
+/// against the new one.
+#[derive(Serialize)]
+struct BrokenOperation {
+    operation: String,
+    errors: Vec<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DiffReport<'a> {
+    breaking: bool,
+    #[serde(flatten)]
+    diff: &'a SchemaDiff,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    broken_operations: Option<Vec<BrokenOperation>>,
+}
+
+pub(super) async fn diff_command(params: DiffParams, config_reader: &ConfigReader) -> Result<()> {
+    let DiffParams { old, new, operations, json, runtime } = params;
+
+    let old_blueprint = read_blueprint(config_reader, &old).await?;
+    let new_blueprint = read_blueprint(config_reader, &new).await?;
+    let diff = SchemaDiff::new(&old_blueprint, &new_blueprint);
+
+    let broken_operations = if operations.is_empty() {
+        None
+    } else {
+        Some(broken_operations(&operations, &old_blueprint, &new_blueprint, runtime).await?)
+    };
+
+    // With recorded operations only the ones that actually stop validating
+    // count as breakage; otherwise any breaking change does.
+    let breaking = match &broken_operations {
+        Some(broken) => !broken.is_empty(),
+        None => diff.is_breaking(),
+    };
+
+    let report = DiffReport { breaking, diff: &diff, broken_operations };
+    if json {
+        Fmt::display(&serde_json::to_string_pretty(&report)?);
+    } else {
+        display_report(&report);
+    }
+
+    if breaking {
+        Err(anyhow!("Breaking changes detected between {old} and {new}"))
+    } else {
+        Ok(())
+    }
+}
+
+async fn read_blueprint(config_reader: &ConfigReader, path: &str) -> Result<Blueprint> {
+    let config_module = config_reader.read(path).await?;
+    Ok(Blueprint::try_from(&config_module).map_err(Errata::from)?)
+}
+
+async fn broken_operations(
+    paths: &[String],
+    old: &Blueprint,
+    new: &Blueprint,
+    runtime: TargetRuntime,
+) -> Result<Vec<BrokenOperation>> {
+    let mut endpoints = EndpointSet::default();
+    let mut sources = vec![];
+    for path in paths {
+        let content = runtime.file.read(path).await?;
+        let set = EndpointSet::try_new(&content)?;
+        sources.extend(std::iter::repeat_n(path, set.get_endpoints().len()));
+        endpoints.extend(set);
+    }
+
+    // Operations that were already invalid aren't caused by this change. The
+    // same endpoint is checked against both blueprints, so its index
+    // identifies it even when several share a method and path.
+    let already_invalid = endpoints.invalid_endpoints(old, runtime.clone()).await;
+    let broken = endpoints
+        .invalid_endpoints(new, runtime)
+        .await
+        .into_iter()
+        .filter(|(index, _)| !already_invalid.iter().any(|(i, _)| i == index))
+        .map(|(index, errors)| {
+            let endpoint = &endpoints.get_endpoints()[index];
+            let operation = format!(
+                "{} {} ({})",
+                endpoint.get_method(),
+                endpoint.get_path().as_str(),
+                sources[index]
+            );
+            BrokenOperation { operation, errors }
+        })
+        .collect();
+
+    Ok(broken)
+}
+
+fn display_report(report: &DiffReport) {
+    if report.diff.changes.is_empty() {
+        Fmt::display("No changes detected");
+    }
+
+    for (severity, heading) in [
+        (ChangeSeverity::Breaking, "Breaking changes"),
+        (ChangeSeverity::Dangerous, "Dangerous changes"),
+        (ChangeSeverity::Safe, "Safe changes"),
+    ] {
+        let changes = report.diff.filter(severity).collect::<Vec<_>>();
+        if changes.is_empty() {
+            continue;
+        }
+
+        Fmt::display(&Fmt::heading(&format!("{heading} ({}):", changes.len())));
+        for change in changes {
+            Fmt::display(&format!(
+                "  {}  {}",
+                Fmt::meta(&change.path),
+                change.message
+            ));
+        }
+    }
+
+    if let Some(broken) = &report.broken_operations {
+        Fmt::display(&Fmt::heading(&format!(
+            "Broken operations ({}):",
+            broken.len()
+        )));
+        for operation in broken {
+            Fmt::display(&format!("  {}", Fmt::meta(&operation.operation)));
+            for error in &operation.errors {
+                Fmt::display(&format!("    {error}"));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![expect(clippy::unwrap_used, reason = "test code")]
+
+    use super::*;
+    use crate::core::config::{Config, ConfigModule};
+
+    fn blueprint(sdl: &str) -> Blueprint {
+        let config = Config::from_sdl(sdl).to_result().unwrap();
+        Blueprint::try_from(&ConfigModule::from(config)).unwrap()
+    }
+
+    fn schema(field: &str) -> String {
+        format!(
+            r#"
+            schema @server @upstream {{
+              query: Query
+            }}
+
+            type Query {{
+              user: User @http(url: "http://localhost/user")
+            }}
+
+            type User {{
+              {field}: String
+            }}
+            "#
+        )
+    }
+
+    #[tokio::test]
+    async fn test_broken_operations_share_a_route() {
+        let dir = tempfile::tempdir().unwrap();
+        let name = dir.path().join("name.graphql");
+        let email = dir.path().join("email.graphql");
+        std::fs::write(
+            &name,
+            r#"query @rest(method: GET, path: "/user") { user { name } }"#,
+        )
+        .unwrap();
+        std::fs::write(
+            &email,
+            r#"query @rest(method: GET, path: "/user") { user { email } }"#,
+        )
+        .unwrap();
+        let paths = [name, email].map(|p| p.to_string_lossy().to_string());
+
+        let runtime = crate::core::runtime::test::init(&None);
+        let old = blueprint(&schema("email"));
+        let new = blueprint(&schema("name"));
+        let broken = broken_operations(&paths, &old, &new, runtime)
+            .await
+            .unwrap();
+
+        // The `name` operation was already broken; the `email` one is still
+        // reported although it shares its route.
+        let operations = broken
+            .iter()
+            .map(|b| b.operation.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(operations, vec![format!("GET /user ({})", paths[1])]);
+    }
+}
@@ -5,10 +5,12 @@ use crate::core::blueprint::Blueprint;
 use crate::core::http::API_URL_PREFIX;
 use crate::core::print_schema;
 use crate::core::rest::{EndpointSet, Unchecked};
+use crate::core::rpc::{MethodSet, proto};
 
 pub const GQLFORGE_RC: &str = ".gqlforgerc.graphql";
 pub const GRAPHQL_RC: &str = ".graphqlrc.yml";
 pub const GQLFORGE_RC_SCHEMA: &str = ".gqlforgerc.schema.json";
+pub const GQLFORGE_RC_LINT: &str = ".gqlforgerc.json";
 
 pub static TRACKER: LazyLock<gqlforge_tracker::Tracker> =
     LazyLock::new(gqlforge_tracker::Tracker::default);
@@ -39,3 +41,15 @@ pub(super) fn display_schema(blueprint: &Blueprint) {
     let sdl = blueprint.to_schema();
     Fmt::display(&format!("{}\n", print_schema::print_schema(&sdl)));
 }
+
+/// Displays the `.proto` files of the gRPC methods served from `@rpc`
+/// operations.
+pub(super) fn display_proto(
+    method_set: &MethodSet<Unchecked>,
+    blueprint: &Blueprint,
+) -> anyhow::Result<()> {
+    for file in method_set.to_proto(blueprint)? {
+        Fmt::display(&format!("{}\n", proto::print(&file)));
+    }
+    Ok(())
+}
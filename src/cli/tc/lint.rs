@@ -0,0 +1,120 @@
+use std::path::Path;
+
+use anyhow::Result;
+use serde::Deserialize;
+use serde_json::json;
+
+use super::helpers::GQLFORGE_RC_LINT;
+use crate::cli::command::LintFormat;
+use crate::cli::fmt::Fmt;
+use crate::core::config::{Diagnostic, LintConfig, LintSeverity, lint_rules};
+use crate::core::runtime::TargetRuntime;
+
+/// Project settings read from `.gqlforgerc.json`.
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RcConfig {
+    #[serde(default)]
+    lint: LintConfig,
+}
+
+/// Reads the lint settings from the `.gqlforgerc.json` next to the first
+/// config file, falling back to the defaults when there is none.
+pub(super) async fn read_lint_config(
+    runtime: &TargetRuntime,
+    file_paths: &[String],
+) -> Result<LintConfig> {
+    let Some(parent_dir) = file_paths.first().and_then(|path| Path::new(path).parent()) else {
+        return Ok(LintConfig::default());
+    };
+    let rc_path = parent_dir.join(GQLFORGE_RC_LINT);
+    // A file that can't be read is treated as missing
+    let Ok(content) = runtime.file.read(&rc_path.to_string_lossy()).await else {
+        return Ok(LintConfig::default());
+    };
+
+    let rc: RcConfig = serde_json::from_str(&content)?;
+    Ok(rc.lint)
+}
+
+pub(super) fn display_diagnostics(
+    diagnostics: &[Diagnostic],
+    format: LintFormat,
+    file_paths: &[String],
+) -> Result<()> {
+    match format {
+        LintFormat::Human => {
+            for diagnostic in diagnostics {
+                let message = format!(
+                    "[{}] {}: {}",
+                    diagnostic.rule,
+                    Fmt::meta(&diagnostic.path),
+                    diagnostic.message
+                );
+                match diagnostic.severity {
+                    LintSeverity::Error => tracing::error!("{message}"),
+                    LintSeverity::Warning => tracing::warn!("{message}"),
+                    LintSeverity::Info | LintSeverity::Off => tracing::info!("{message}"),
+                }
+            }
+        }
+        LintFormat::Json => Fmt::display(&serde_json::to_string_pretty(diagnostics)?),
+        LintFormat::Sarif => Fmt::display(&serde_json::to_string_pretty(&to_sarif(
+            diagnostics,
+            file_paths.first().map(String::as_str),
+        ))?),
+    }
+    Ok(())
+}
+
+/// Renders the diagnostics as a SARIF 2.1.0 log, so they can be uploaded to
+/// code scanning tools.
+fn to_sarif(diagnostics: &[Diagnostic], file_path: Option<&str>) -> serde_json::Value {
+    let rules = lint_rules()
+        .iter()
+        .map(|rule| {
+            json!({
+                "id": rule.id(),
+                "shortDescription": { "text": rule.description() },
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let results = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let level = match diagnostic.severity {
+                LintSeverity::Error => "error",
+                LintSeverity::Warning => "warning",
+                LintSeverity::Info | LintSeverity::Off => "note",
+            };
+            let mut location = json!({
+                "logicalLocations": [{ "fullyQualifiedName": diagnostic.path }],
+            });
+            if let Some(file_path) = file_path {
+                location["physicalLocation"] = json!({ "artifactLocation": { "uri": file_path } });
+            }
+            json!({
+                "ruleId": diagnostic.rule,
+                "level": level,
+                "message": { "text": diagnostic.message },
+                "locations": [location],
+            })
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "gqlforge",
+                    "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }],
+    })
+}
@@ -1,7 +1,9 @@
 mod check;
+mod diff;
 mod r#gen;
 mod helpers;
 mod init;
+mod lint;
 pub mod run;
 mod start;
 mod validate_rc;
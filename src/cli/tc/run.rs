@@ -4,7 +4,7 @@ use dotenvy::dotenv;
 
 use super::helpers::TRACKER;
 use super::validate_rc::validate_rc_config_files;
-use super::{check, r#gen, init, start};
+use super::{check, diff, r#gen, init, start};
 use crate::cli::command::{Cli, Command};
 use crate::cli::{self, update_checker};
 use crate::core::blueprint::Blueprint;
@@ -48,11 +48,33 @@ async fn run_command(cli: Cli) -> Result<()> {
             validate_rc_config_files(runtime, &file_paths).await;
             start::start_command(file_paths, &config_reader).await?;
         }
-        Command::Check { file_paths, n_plus_one_queries, schema, verify_ssl } => {
+        Command::Check {
+            file_paths,
+            n_plus_one_queries,
+            operations,
+            schema,
+            format,
+            verify_ssl,
+        } => {
             let (runtime, config_reader) = get_runtime_and_config_reader(verify_ssl)?;
             validate_rc_config_files(runtime.clone(), &file_paths).await;
             check::check_command(
-                check::CheckParams { file_paths, n_plus_one_queries, schema, runtime },
+                check::CheckParams {
+                    file_paths,
+                    n_plus_one_queries,
+                    operations,
+                    schema,
+                    format,
+                    runtime,
+                },
+                &config_reader,
+            )
+            .await?;
+        }
+        Command::Diff { old, new, operations, json, verify_ssl } => {
+            let (runtime, config_reader) = get_runtime_and_config_reader(verify_ssl)?;
+            diff::diff_command(
+                diff::DiffParams { old, new, operations, json, runtime },
                 &config_reader,
             )
             .await?;
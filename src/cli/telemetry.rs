@@ -1,4 +1,5 @@
 use anyhow::anyhow;
+use opentelemetry::propagation::TextMapCompositePropagator;
 use opentelemetry::trace::TracerProvider as _;
 use opentelemetry::{KeyValue, global};
 use opentelemetry_appender_tracing::layer::OpenTelemetryTracingBridge;
@@ -8,8 +9,10 @@ use opentelemetry_otlp::{
 use opentelemetry_sdk::Resource;
 use opentelemetry_sdk::logs::{SdkLogger, SdkLoggerProvider};
 use opentelemetry_sdk::metrics::{PeriodicReader, SdkMeterProvider};
-use opentelemetry_sdk::propagation::TraceContextPropagator;
-use opentelemetry_sdk::trace::{SdkTracerProvider, Tracer};
+use opentelemetry_sdk::propagation::{BaggagePropagator, TraceContextPropagator};
+use opentelemetry_sdk::trace::{
+    BatchSpanProcessor, SdkTracerProvider, SimpleSpanProcessor, Tracer,
+};
 use tonic::metadata::MetadataMap;
 use tracing::Subscriber;
 use tracing::level_filters::LevelFilter;
@@ -19,7 +22,9 @@ use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::{Layer, Registry};
 
 use super::metrics::init_metrics;
+use super::sampler::{ErrorSpans, TraceSampler};
 use crate::core::blueprint::telemetry::{OtlpExporter, Telemetry, TelemetryExporter};
+use crate::core::config::TelemetrySampling;
 use crate::core::runtime::TargetRuntime;
 use crate::core::tracing::{
     default_tracing, default_tracing_gqlforge, get_log_level, gqlforge_filter_target,
@@ -37,19 +42,22 @@ static RESOURCE: std::sync::LazyLock<Resource> = std::sync::LazyLock::new(|| {
 
 fn set_trace_provider(
     exporter: &TelemetryExporter,
+    sampling: &TelemetrySampling,
 ) -> anyhow::Result<Option<OpenTelemetryLayer<Registry, Tracer>>> {
     let provider = match exporter {
         TelemetryExporter::Stdout(_config) => {
             let exporter = opentelemetry_stdout::SpanExporter::default();
             SdkTracerProvider::builder()
-                .with_simple_exporter(exporter)
+                .with_span_processor(ErrorSpans(SimpleSpanProcessor::new(exporter)))
+                .with_sampler(TraceSampler::new(sampling))
                 .with_resource(RESOURCE.clone())
                 .build()
         }
         TelemetryExporter::Otlp(config) => {
             let exporter = build_otlp_span_exporter(config)?;
             SdkTracerProvider::builder()
-                .with_batch_exporter(exporter)
+                .with_span_processor(ErrorSpans(BatchSpanProcessor::builder(exporter).build()))
+                .with_sampler(TraceSampler::new(sampling))
                 .with_resource(RESOURCE.clone())
                 .build()
         }
@@ -174,11 +182,14 @@ fn set_tracing_subscriber(subscriber: impl Subscriber + Send + Sync) {
 /// Returns an error if the operation fails.
 pub async fn init_opentelemetry(config: Telemetry, runtime: &TargetRuntime) -> anyhow::Result<()> {
     if let Some(export) = &config.export {
-        let trace_layer = set_trace_provider(export)?;
+        let trace_layer = set_trace_provider(export, &config.sampling)?;
         let log_layer = set_logger_provider(export)?;
         set_meter_provider(export)?;
 
-        global::set_text_map_propagator(TraceContextPropagator::new());
+        global::set_text_map_propagator(TextMapCompositePropagator::new(vec![
+            Box::new(TraceContextPropagator::new()),
+            Box::new(BaggagePropagator::new()),
+        ]));
 
         let subscriber = tracing_subscriber::registry()
             .with(trace_layer)
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use async_graphql::dynamic::{self, DynamicRequest};
@@ -11,11 +12,12 @@ use crate::core::data_loader::{DataLoader, DedupeResult};
 use crate::core::graphql::GraphqlDataLoader;
 use crate::core::grpc;
 use crate::core::grpc::data_loader::GrpcDataLoader;
-use crate::core::http::{DataLoaderRequest, HttpDataLoader};
-use crate::core::ir::Error;
+use crate::core::http::{DataLoaderRequest, HttpDataLoader, OAuth2Http};
 use crate::core::ir::model::{DataLoaderId, IO, IR, IoId};
+use crate::core::ir::{Error, IoMetrics};
 use crate::core::jit::{OPHash, OperationPlan};
 use crate::core::rest::{Checked, EndpointSet};
+use crate::core::rpc::MethodSet;
 use crate::core::runtime::TargetRuntime;
 
 pub struct AppContext {
@@ -26,10 +28,15 @@ pub struct AppContext {
     pub gql_data_loaders: Arc<Vec<DataLoader<DataLoaderRequest, GraphqlDataLoader>>>,
     pub grpc_data_loaders: Arc<Vec<DataLoader<grpc::DataLoaderRequest, GrpcDataLoader>>>,
     pub endpoints: EndpointSet<Checked>,
+    /// gRPC methods served from `@rpc` operations.
+    pub rpc_methods: MethodSet<Checked>,
     pub dedupe_handler: Arc<DedupeResult<IoId, ConstValue, Error>>,
     pub dedupe_operation_handler: DedupeResult<OperationId, AnyResponse<Vec<u8>>, Error>,
     pub operation_plans: DashMap<OPHash, OperationPlan<async_graphql_value::Value>>,
     pub const_execution_cache: DashMap<OPHash, AnyResponse<Vec<u8>>>,
+    /// Contexts of the `@server(contracts:)` schema views, by route.
+    pub contracts: HashMap<String, Arc<AppContext>>,
+    pub io_metrics: Option<Arc<IoMetrics>>,
 }
 
 impl AppContext {
@@ -39,6 +46,21 @@ impl AppContext {
         runtime: TargetRuntime,
         endpoints: EndpointSet<Checked>,
     ) -> Self {
+        let contracts = blueprint
+            .server
+            .contracts
+            .iter()
+            .map(|contract| {
+                let app_ctx = AppContext::new(
+                    blueprint.contract(contract),
+                    runtime.clone(),
+                    EndpointSet::default(),
+                );
+                (contract.route.clone(), Arc::new(app_ctx))
+            })
+            .collect();
+
+        let runtime = OAuth2Http::wrap(runtime, &blueprint.upstream.oauth2);
         let mut http_data_loaders = vec![];
         let mut gql_data_loaders = vec![];
         let mut grpc_data_loaders = vec![];
@@ -165,6 +187,12 @@ impl AppContext {
             }
         }
 
+        let io_metrics = blueprint
+            .telemetry
+            .export
+            .as_ref()
+            .map(|_| Arc::new(IoMetrics::new(&blueprint.telemetry.metrics)));
+
         let schema = blueprint
             .to_schema_with(&SchemaModifiers::default().extensions(runtime.extensions.clone()));
 
@@ -176,14 +204,36 @@ impl AppContext {
             gql_data_loaders: Arc::new(gql_data_loaders),
             grpc_data_loaders: Arc::new(grpc_data_loaders),
             endpoints,
+            rpc_methods: MethodSet::default(),
 
-            dedupe_handler: Arc::new(DedupeResult::new(false)),
-            dedupe_operation_handler: DedupeResult::new(false),
+            dedupe_handler: Arc::new(DedupeResult::new(false).name("upstream")),
+            dedupe_operation_handler: DedupeResult::new(false).name("operation"),
             operation_plans: DashMap::new(),
             const_execution_cache: DashMap::default(),
+            contracts,
+            io_metrics,
         }
     }
 
+    /// The context requests to `path` are executed with: the contract served
+    /// on that route, or for the other routes, such as `@rest` endpoints and
+    /// `@rpc` methods, the contract on the GraphQL route. Without a contract
+    /// it's the context itself.
+    #[must_use]
+    pub fn schema_context(self: &Arc<Self>, path: &str) -> Arc<AppContext> {
+        self.contracts
+            .get(path)
+            .or_else(|| self.contracts.get(self.blueprint.server.routes.graphql()))
+            .cloned()
+            .unwrap_or_else(|| self.clone())
+    }
+
+    /// Whether `path` serves a GraphQL schema, the full one or a contract.
+    #[must_use]
+    pub fn is_graphql_route(&self, path: &str) -> bool {
+        path == self.blueprint.server.routes.graphql() || self.contracts.contains_key(path)
+    }
+
     pub async fn execute(&self, request: impl Into<DynamicRequest>) -> async_graphql::Response {
         self.schema.execute(request).await
     }
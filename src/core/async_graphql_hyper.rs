@@ -3,7 +3,7 @@ use std::hash::{Hash, Hasher};
 
 use anyhow::Result;
 use async_graphql::parser::types::{ExecutableDocument, OperationType};
-use async_graphql::{BatchResponse, Executor, Value};
+use async_graphql::{BatchResponse, Executor, ServerError, Value};
 use bytes::Bytes;
 use gqlforge_hasher::GqlforgeHasher;
 use http::header::{CACHE_CONTROL, CONTENT_TYPE, HeaderMap, HeaderValue};
@@ -11,6 +11,7 @@ use http::{Response, StatusCode};
 use http_body_util::Full;
 use serde::{Deserialize, Serialize};
 
+use super::grpc::status::GrpcStatusMap;
 use super::jit::{BatchResponse as JITBatchResponse, JITExecutor};
 
 #[derive(PartialEq, Eq, Clone, Hash, Debug)]
@@ -240,6 +241,21 @@ impl GraphQLResponse {
         }
     }
 
+    /// The gRPC status code of the first error coming from a gRPC upstream.
+    fn grpc_code(&self) -> Option<i32> {
+        let errors: Vec<&ServerError> = match &self.0 {
+            BatchResponse::Single(res) => res.errors.iter().collect(),
+            BatchResponse::Batch(list) => list.iter().flat_map(|res| &res.errors).collect(),
+        };
+
+        errors
+            .into_iter()
+            .find_map(|error| match error.extensions.as_ref()?.get("grpcCode")? {
+                Value::Number(code) => code.as_i64().and_then(|code| i32::try_from(code).ok()),
+                _ => None,
+            })
+    }
+
     /// Transforms a plain `GraphQLResponse` into a `Response<Body>`.
     /// Differs as `to_response` by flattening the response's data
     /// `{"data": {"user": {"name": "John"}}}` becomes `{"name": "John"}`.
@@ -247,9 +263,17 @@ impl GraphQLResponse {
     /// # Errors
     ///
     /// Returns an error if the operation fails.
-    pub fn into_rest_response(self) -> Result<Response<Full<Bytes>>> {
+    pub fn into_rest_response(
+        self,
+        grpc_status_map: &GrpcStatusMap,
+    ) -> Result<Response<Full<Bytes>>> {
         if !self.0.is_ok() {
-            return self.build_response(StatusCode::INTERNAL_SERVER_ERROR, self.default_body()?);
+            let status = self
+                .grpc_code()
+                .map_or(StatusCode::INTERNAL_SERVER_ERROR, |code| {
+                    grpc_status_map.http_status(code)
+                });
+            return self.build_response(status, self.default_body()?);
         }
 
         match self.0 {
@@ -459,7 +483,9 @@ mod tests {
         let data = IndexMap::from([(Name::new("user"), Value::Object(user))]);
 
         let response = GraphQLResponse(BatchResponse::Single(Response::new(Value::Object(data))));
-        let rest_response = response.into_rest_response().unwrap();
+        let rest_response = response
+            .into_rest_response(&GrpcStatusMap::default())
+            .unwrap();
 
         assert_eq!(rest_response.status(), StatusCode::OK);
         assert_eq!(rest_response.headers()["content-type"], "application/json");
@@ -487,7 +513,9 @@ mod tests {
             .collect();
 
         let response = GraphQLResponse(BatchResponse::Batch(list));
-        let rest_response = response.into_rest_response().unwrap();
+        let rest_response = response
+            .into_rest_response(&GrpcStatusMap::default())
+            .unwrap();
 
         assert_eq!(rest_response.status(), StatusCode::OK);
         assert_eq!(rest_response.headers()["content-type"], "application/json");
@@ -517,7 +545,9 @@ mod tests {
             .map(|error| ServerError::new(error.to_string(), None))
             .collect();
         let response = GraphQLResponse(BatchResponse::Single(response));
-        let rest_response = response.into_rest_response().unwrap();
+        let rest_response = response
+            .into_rest_response(&GrpcStatusMap::default())
+            .unwrap();
 
         assert_eq!(rest_response.status(), StatusCode::INTERNAL_SERVER_ERROR);
         assert_eq!(rest_response.headers()["content-type"], "application/json");
@@ -541,6 +571,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_to_rest_response_with_grpc_error() {
+        let response = || {
+            let mut error = ServerError::new("gRPC Error", None);
+            error
+                .extensions
+                .get_or_insert_with(Default::default)
+                .set("grpcCode", 5);
+            GraphQLResponse(BatchResponse::Single(
+                Response::default().server_error(error),
+            ))
+        };
+
+        let rest_response = response()
+            .into_rest_response(&GrpcStatusMap::default())
+            .unwrap();
+        assert_eq!(rest_response.status(), StatusCode::NOT_FOUND);
+
+        let grpc_status_map =
+            GrpcStatusMap::new(std::collections::BTreeMap::from([(5, StatusCode::GONE)]));
+        let rest_response = response().into_rest_response(&grpc_status_map).unwrap();
+        assert_eq!(rest_response.status(), StatusCode::GONE);
+    }
+
     #[test]
     fn to_value() {
         assert_eq!(CacheControl { public: true, max_age: 0 }.value(), None);
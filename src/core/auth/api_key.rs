@@ -0,0 +1,135 @@
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+
+use super::error::Error;
+use super::verification::Verification;
+use super::verify::Verify;
+use crate::core::blueprint;
+use crate::core::config::{ApiKeyEntry, ApiKeySource};
+use crate::core::http::RequestContext;
+
+/// Verifies API keys against a list of SHA-256 hashes.
+pub struct ApiKeyVerifier {
+    options: blueprint::ApiKey,
+}
+
+impl ApiKeyVerifier {
+    pub fn new(options: blueprint::ApiKey) -> Self {
+        Self { options }
+    }
+
+    fn resolve_key(&self, request: &RequestContext) -> Option<String> {
+        match &self.options.source {
+            ApiKeySource::Header(name) => request
+                .request_headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(ToOwned::to_owned),
+            ApiKeySource::Query(name) => request.query_param(name),
+        }
+    }
+
+    /// Finds the entry whose hash matches `key`. Every hash is compared in
+    /// constant time, and all of them are compared, so the response time
+    /// doesn't reveal how much of a hash matched.
+    fn find(&self, key: &str) -> Option<&ApiKeyEntry> {
+        let digest = Sha256::digest(key.as_bytes())
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<String>();
+
+        self.options.keys.iter().fold(None, |found, entry| {
+            if bool::from(digest.as_bytes().ct_eq(entry.hash.as_bytes())) {
+                Some(entry)
+            } else {
+                found
+            }
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Verify for ApiKeyVerifier {
+    async fn verify(&self, request: &RequestContext) -> Verification {
+        let Some(key) = self.resolve_key(request) else {
+            return Verification::fail(Error::Missing);
+        };
+
+        match self.find(&key) {
+            Some(entry) => {
+                Verification::succeed_with_claims(serde_json::Value::Object(entry.claims.clone()))
+            }
+            None => Verification::fail(Error::Invalid),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::header::HeaderValue;
+    use serde_json::json;
+
+    use super::*;
+
+    // sha256("secret-key")
+    const SECRET_KEY_HASH: &str =
+        "85dbe15d75ef9308c7ae0f33c7a324cc6f4bf519a2ed2f3027bd33c140a4f9aa";
+
+    impl blueprint::ApiKey {
+        pub fn test_value() -> Self {
+            Self {
+                source: ApiKeySource::default(),
+                keys: vec![ApiKeyEntry {
+                    hash: SECRET_KEY_HASH.to_owned(),
+                    claims: json!({ "tenant": "acme", "scopes": ["orders:read"] })
+                        .as_object()
+                        .cloned()
+                        .unwrap_or_default(),
+                }],
+            }
+        }
+    }
+
+    fn request_with_header(key: &'static str) -> RequestContext {
+        let mut request = RequestContext::default();
+        request
+            .request_headers
+            .insert("x-api-key", HeaderValue::from_static(key));
+        request
+    }
+
+    #[tokio::test]
+    async fn verifies_header_keys() {
+        let verifier = ApiKeyVerifier::new(blueprint::ApiKey::test_value());
+
+        assert_eq!(
+            verifier.verify(&request_with_header("secret-key")).await,
+            Verification::succeed_with_claims(
+                json!({ "tenant": "acme", "scopes": ["orders:read"] })
+            )
+        );
+        assert_eq!(
+            verifier.verify(&request_with_header("wrong-key")).await,
+            Verification::fail(Error::Invalid)
+        );
+        assert_eq!(
+            verifier.verify(&RequestContext::default()).await,
+            Verification::fail(Error::Missing)
+        );
+    }
+
+    #[tokio::test]
+    async fn verifies_query_keys() {
+        let verifier = ApiKeyVerifier::new(blueprint::ApiKey {
+            source: ApiKeySource::Query("api_key".to_owned()),
+            ..blueprint::ApiKey::test_value()
+        });
+        let request = RequestContext::default()
+            .request_query(Some("query=%7Bme%7D&api_key=secret-key".to_owned()));
+
+        assert!(matches!(
+            verifier.verify(&request).await,
+            Verification::Succeed(Some(_))
+        ));
+    }
+}
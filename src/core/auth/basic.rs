@@ -17,7 +17,7 @@ impl Verify for BasicVerifier {
     /// Verify the request context against the basic auth provider.
     async fn verify(&self, req_ctx: &RequestContext) -> Verification {
         let header = req_ctx
-            .allowed_headers
+            .request_headers
             .typed_try_get::<Authorization<Basic>>();
 
         let Ok(header) = header else {
@@ -67,7 +67,7 @@ testuser3:{SHA}Y2fEjdGT1W6nsLqtJbGUVeUp9e4=
         let mut req_context = RequestContext::default();
 
         req_context
-            .allowed_headers
+            .request_headers
             .typed_insert(Authorization::basic(username, password));
 
         req_context
@@ -120,7 +120,7 @@ testuser3:{SHA}Y2fEjdGT1W6nsLqtJbGUVeUp9e4=
     async fn verify_auth_failure() {
         let provider = setup_provider();
         let mut req_ctx = RequestContext::default();
-        req_ctx.allowed_headers.insert(
+        req_ctx.request_headers.insert(
             "Authorization",
             HeaderValue::from_static("Basic dGVzdHVzZXIyOm15cGFzc3dvcmQ"),
         );
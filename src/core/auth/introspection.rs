@@ -0,0 +1,258 @@
+use std::fmt::Debug;
+use std::num::NonZeroU64;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::bail;
+use headers::authorization::Bearer;
+use headers::{Authorization, HeaderMapExt};
+use http::header::{CONTENT_TYPE, HeaderValue};
+use sha2::{Digest, Sha256};
+
+use super::error::Error;
+use super::verification::Verification;
+use super::verify::Verify;
+use crate::core::cache::InMemoryCache;
+use crate::core::http::RequestContext;
+use crate::core::runtime::TargetRuntime;
+use crate::core::{Cache, blueprint};
+
+/// Introspection responses of active tokens, kept until the token expires.
+/// Entries are keyed by the SHA-256 hash of the token, so the tokens
+/// themselves aren't kept in memory.
+#[derive(Clone, Default)]
+pub struct IntrospectionCache(Arc<InMemoryCache<String, serde_json::Value>>);
+
+// Needed for `blueprint::Introspection` to derive `PartialEq`, which
+// blueprints are compared with. The cache is runtime state rather than
+// part of the provider's settings: it doesn't change which tokens are
+// accepted, so any two caches compare equal.
+impl PartialEq for IntrospectionCache {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for IntrospectionCache {}
+
+impl Debug for IntrospectionCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("IntrospectionCache")
+    }
+}
+
+/// Verifies opaque bearer tokens against an OAuth2 token introspection
+/// endpoint (RFC 7662).
+pub struct IntrospectionVerifier {
+    options: blueprint::Introspection,
+}
+
+impl IntrospectionVerifier {
+    pub fn new(options: blueprint::Introspection) -> Self {
+        Self { options }
+    }
+
+    fn resolve_token(request: &RequestContext) -> anyhow::Result<Option<String>> {
+        let value = request
+            .request_headers
+            .typed_try_get::<Authorization<Bearer>>()?;
+
+        Ok(value.map(|token| token.token().to_owned()))
+    }
+
+    async fn introspect(
+        &self,
+        token: &str,
+        runtime: &TargetRuntime,
+    ) -> anyhow::Result<serde_json::Value> {
+        let body = url::form_urlencoded::Serializer::new(String::new())
+            .append_pair("token", token)
+            .append_pair("token_type_hint", "access_token")
+            .finish();
+
+        let mut request = reqwest::Request::new(reqwest::Method::POST, self.options.url.parse()?);
+        request.headers_mut().insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static("application/x-www-form-urlencoded"),
+        );
+        if let Some(client_id) = &self.options.client_id {
+            request.headers_mut().typed_insert(Authorization::basic(
+                client_id,
+                self.options.client_secret.as_deref().unwrap_or_default(),
+            ));
+        }
+        *request.body_mut() = Some(body.into());
+
+        let response = runtime.http.execute(request).await?;
+        if !response.status.is_success() {
+            bail!("Introspection endpoint responded with {}", response.status);
+        }
+
+        Ok(serde_json::from_slice(&response.body)?)
+    }
+
+    /// Checks that the token is active, unexpired and issued for one of the
+    /// configured audiences with all the required scopes.
+    fn validate_claims(&self, claims: &serde_json::Value, now: u64) -> bool {
+        let active = claims["active"].as_bool().unwrap_or(false);
+        let expired = claims["exp"].as_u64().is_some_and(|exp| exp <= now);
+
+        let audiences = &self.options.audiences;
+        let aud = match &claims["aud"] {
+            serde_json::Value::String(aud) => audiences.contains(aud),
+            serde_json::Value::Array(auds) => auds
+                .iter()
+                .filter_map(serde_json::Value::as_str)
+                .any(|aud| audiences.contains(aud)),
+            _ => false,
+        };
+
+        let granted = claims["scope"]
+            .as_str()
+            .unwrap_or_default()
+            .split_whitespace()
+            .collect::<Vec<_>>();
+        let scopes = self
+            .options
+            .scopes
+            .iter()
+            .all(|scope| granted.contains(&scope.as_str()));
+
+        active && !expired && (audiences.is_empty() || aud) && scopes
+    }
+}
+
+/// Time until the token expires, `None` if the response has no `exp`.
+fn ttl(claims: &serde_json::Value, now: u64) -> Option<NonZeroU64> {
+    let exp = claims["exp"].as_u64()?;
+    NonZeroU64::new(exp.checked_sub(now)?.saturating_mul(1000))
+}
+
+/// Hex encoded SHA-256 hash of `token`.
+fn cache_key(token: &str) -> String {
+    Sha256::digest(token.as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+#[async_trait::async_trait]
+impl Verify for IntrospectionVerifier {
+    async fn verify(&self, request: &RequestContext) -> Verification {
+        let token = Self::resolve_token(request);
+        let Ok(token) = token else {
+            return Verification::fail(Error::Invalid);
+        };
+        let Some(token) = token else {
+            return Verification::fail(Error::Missing);
+        };
+
+        let cache = &self.options.cache.0;
+        let key = cache_key(&token);
+        if let Ok(Some(claims)) = cache.get(&key).await {
+            return Verification::succeed_with_claims(claims);
+        }
+
+        let claims = match self.introspect(&token, &request.runtime).await {
+            Ok(claims) => claims,
+            Err(err) => {
+                tracing::warn!("Token introspection failed: {err}");
+                return Verification::fail(Error::Parse(format!(
+                    "Token introspection failed: {err}"
+                )));
+            }
+        };
+
+        let now = now();
+        if !self.validate_claims(&claims, now) {
+            return Verification::fail(Error::Invalid);
+        }
+
+        // Only positive results are cached, and never past the token's expiry.
+        if let Some(ttl) = ttl(&claims, now)
+            && let Err(err) = cache.set(key, claims.clone(), ttl).await
+        {
+            tracing::warn!("Failed to cache introspection result: {err}");
+        }
+
+        Verification::succeed_with_claims(claims)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use serde_json::json;
+
+    use super::*;
+
+    const NOW: u64 = 1_700_000_000;
+
+    impl blueprint::Introspection {
+        pub fn test_value() -> Self {
+            Self {
+                url: "https://auth.example.com/oauth2/introspect".to_owned(),
+                client_id: Some("gateway".to_owned()),
+                client_secret: Some("secret".to_owned()),
+                audiences: HashSet::new(),
+                scopes: vec![],
+                cache: IntrospectionCache::default(),
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_inactive_and_expired_tokens() {
+        let verifier = IntrospectionVerifier::new(blueprint::Introspection::test_value());
+
+        assert!(verifier.validate_claims(&json!({ "active": true }), NOW));
+        assert!(!verifier.validate_claims(&json!({ "active": false }), NOW));
+        assert!(!verifier.validate_claims(&json!({}), NOW));
+        assert!(!verifier.validate_claims(&json!({ "active": true, "exp": NOW }), NOW));
+    }
+
+    #[test]
+    fn checks_audiences_and_scopes() {
+        let verifier = IntrospectionVerifier::new(blueprint::Introspection {
+            audiences: HashSet::from(["api".to_owned()]),
+            scopes: vec!["read".to_owned(), "write".to_owned()],
+            ..blueprint::Introspection::test_value()
+        });
+
+        let claims = json!({ "active": true, "aud": ["web", "api"], "scope": "read write admin" });
+        assert!(verifier.validate_claims(&claims, NOW));
+
+        let claims = json!({ "active": true, "aud": "web", "scope": "read write" });
+        assert!(!verifier.validate_claims(&claims, NOW));
+
+        let claims = json!({ "active": true, "aud": "api", "scope": "read" });
+        assert!(!verifier.validate_claims(&claims, NOW));
+    }
+
+    #[test]
+    fn caches_until_expiry() {
+        assert_eq!(
+            ttl(&json!({ "exp": NOW + 60 }), NOW),
+            NonZeroU64::new(60_000)
+        );
+        assert_eq!(ttl(&json!({ "exp": NOW - 1 }), NOW), None);
+        assert_eq!(ttl(&json!({}), NOW), None);
+    }
+
+    #[test]
+    fn cache_key_is_token_hash() {
+        let key = cache_key("opaque-token");
+
+        assert_eq!(key.len(), 64);
+        assert!(!key.contains("opaque-token"));
+        assert_eq!(key, cache_key("opaque-token"));
+        assert_ne!(key, cache_key("other-token"));
+    }
+}
@@ -1,7 +1,6 @@
 use std::collections::HashMap;
 
-use headers::authorization::Bearer;
-use headers::{Authorization, HeaderMapExt};
+use jsonwebtoken::decode_header;
 use serde::Deserialize;
 
 use super::jwks::Jwks;
@@ -9,6 +8,7 @@ use crate::core::auth::error::Error;
 use crate::core::auth::verification::Verification;
 use crate::core::auth::verify::Verify;
 use crate::core::blueprint;
+use crate::core::config::TokenSource;
 use crate::core::http::RequestContext;
 
 #[derive(Debug, Deserialize, serde::Serialize)]
@@ -35,31 +35,56 @@ impl JwtClaim {
 
 pub struct JwtVerifier {
     options: blueprint::Jwt,
-    decoder: Jwks,
 }
 
 impl JwtVerifier {
     pub fn new(options: blueprint::Jwt) -> Self {
-        Self {
-            decoder: Jwks {
-                set: options.jwks.clone(),
-                optional_kid: options.optional_kid,
-            },
-            options,
-        }
+        Self { options }
     }
 
-    fn resolve_token(request: &RequestContext) -> anyhow::Result<Option<String>> {
-        let value = request
-            .allowed_headers
-            .typed_try_get::<Authorization<Bearer>>()?;
+    fn decoder(&self) -> Jwks {
+        Jwks {
+            set: self.options.jwks.keys(),
+            optional_kid: self.options.optional_kid,
+        }
+    }
 
-        Ok(value.map(|token| token.token().to_owned()))
+    /// Reads the token from the first of the configured sources the request
+    /// carries it in.
+    fn resolve_token(&self, request: &RequestContext) -> Option<String> {
+        self.options
+            .token_sources
+            .iter()
+            .find_map(|source| match source {
+                TokenSource::Header { name, scheme } => {
+                    let value = request.request_headers.get(name)?.to_str().ok()?;
+                    match scheme {
+                        Some(scheme) => strip_scheme(value, scheme),
+                        None => Some(value.trim()),
+                    }
+                    .map(ToOwned::to_owned)
+                }
+                TokenSource::Cookie(name) => request.cookie(name),
+                TokenSource::Query(name) => request.query_param(name),
+            })
+            .filter(|token| !token.is_empty())
     }
 
-    fn validate_token(&self, token: &str) -> Verification {
+    async fn validate_token(&self, token: &str, request: &RequestContext) -> Verification {
+        let mut decoder = self.decoder();
+
+        // An unknown `kid` usually means the identity provider rotated its
+        // keys, so fetch them again before rejecting the token.
+        if let Ok(header) = decode_header(token)
+            && let Some(kid) = &header.kid
+            && decoder.set.find(kid).is_none()
+            && self.options.jwks.refetch(&request.runtime).await
+        {
+            decoder = self.decoder();
+        }
+
         Verification::from_result(
-            self.decoder.decode(token),
+            decoder.decode(token),
             |claims| self.validate_claims(&claims),
             |err| Verification::fail(Error::Parse(err.to_string())),
         )
@@ -77,18 +102,24 @@ impl JwtVerifier {
 #[async_trait::async_trait]
 impl Verify for JwtVerifier {
     async fn verify(&self, request: &RequestContext) -> Verification {
-        let token = Self::resolve_token(request);
-        let Ok(token) = token else {
-            return Verification::fail(Error::Invalid);
-        };
-        let Some(token) = token else {
+        let Some(token) = self.resolve_token(request) else {
             return Verification::fail(Error::Missing);
         };
 
-        self.validate_token(&token)
+        self.options.jwks.spawn_refresh(&request.runtime);
+        self.validate_token(&token, request).await
     }
 }
 
+/// The credentials of `value` if it starts with `scheme`, compared
+/// case-insensitively as in `Authorization: bearer <token>`.
+fn strip_scheme<'a>(value: &'a str, scheme: &str) -> Option<&'a str> {
+    let (prefix, credentials) = value.trim().split_once(' ')?;
+    prefix
+        .eq_ignore_ascii_case(scheme)
+        .then(|| credentials.trim())
+}
+
 pub fn validate_iss(options: &blueprint::Jwt, claims: &JwtClaim) -> bool {
     options
         .issuer
@@ -120,9 +151,13 @@ pub mod tests {
     #![expect(clippy::unwrap_used, reason = "test code")]
     use std::collections::HashSet;
 
+    use headers::{Authorization, HeaderMapExt};
+    use http::header::HeaderValue;
     use jsonwebtoken::jwk::JwkSet;
 
     use super::*;
+    use crate::core::auth::jwt::store::JwksStore;
+    use crate::core::config::JwksRefresh;
 
     // tokens are valid for 10 years. If it is expired, update it =)
     // to parse the token and see its content use https://jwt.io
@@ -162,7 +197,8 @@ pub mod tests {
                 issuer: None,
                 audiences: HashSet::new(),
                 optional_kid: false,
-                jwks: JWK_SET.clone(),
+                jwks: JWK_SET.clone().into(),
+                token_sources: vec![TokenSource::default()],
             }
         }
     }
@@ -171,7 +207,7 @@ pub mod tests {
         let mut req_context = RequestContext::default();
 
         req_context
-            .allowed_headers
+            .request_headers
             .typed_insert(Authorization::bearer(token).unwrap());
 
         req_context
@@ -260,6 +296,100 @@ pub mod tests {
         assert_eq!(error, Verification::fail(Error::Invalid));
     }
 
+    #[tokio::test]
+    async fn resolve_token_from_sources() {
+        let jwt_options = blueprint::Jwt {
+            token_sources: vec![
+                TokenSource::Header { name: "X-Auth".to_owned(), scheme: Some("Token".to_owned()) },
+                TokenSource::Cookie("access_token".to_owned()),
+                TokenSource::Query("access_token".to_owned()),
+            ],
+            ..blueprint::Jwt::test_value()
+        };
+        let jwt_provider = JwtVerifier::new(jwt_options);
+
+        let mut header_request = RequestContext::default();
+        header_request.request_headers.insert(
+            "x-auth",
+            HeaderValue::from_str(&format!("token {JWT_VALID_TOKEN_WITH_KID}")).unwrap(),
+        );
+        assert_succeed_with_claims(&jwt_provider.verify(&header_request).await);
+
+        let cookie_request = RequestContext::default().request_cookies(Some(format!(
+            "theme=dark; access_token={JWT_VALID_TOKEN_WITH_KID}"
+        )));
+        assert_succeed_with_claims(&jwt_provider.verify(&cookie_request).await);
+
+        let query_request = RequestContext::default().request_query(Some(format!(
+            "query=%7Bme%7D&access_token={JWT_VALID_TOKEN_WITH_KID}"
+        )));
+        assert_succeed_with_claims(&jwt_provider.verify(&query_request).await);
+
+        // The default `Authorization: Bearer` source isn't configured
+        assert_eq!(
+            jwt_provider
+                .verify(&create_jwt_auth_request(JWT_VALID_TOKEN_WITH_KID))
+                .await,
+            Verification::fail(Error::Missing)
+        );
+    }
+
+    #[tokio::test]
+    async fn resolve_token_earlier_sources_first() {
+        let jwt_options = blueprint::Jwt {
+            token_sources: vec![
+                TokenSource::Cookie("access_token".to_owned()),
+                TokenSource::default(),
+            ],
+            ..blueprint::Jwt::test_value()
+        };
+        let jwt_provider = JwtVerifier::new(jwt_options);
+
+        let request = create_jwt_auth_request(JWT_VALID_TOKEN_WITH_KID)
+            .request_cookies(Some("access_token=invalid".to_owned()));
+
+        assert!(matches!(
+            jwt_provider.verify(&request).await,
+            Verification::Fail(Error::Parse(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn refetches_keys_on_unknown_kid() {
+        let server = httpmock::MockServer::start();
+        // The provider rotated in the key the token is signed with
+        let jwks_mock = server.mock(|when, then| {
+            when.method(httpmock::Method::GET).path("/jwks.json");
+            then.status(200)
+                .json_body(serde_json::to_value(&*JWK_SET).unwrap());
+        });
+
+        let refresh = JwksRefresh::from_meta(server.url("/jwks.json"), None);
+        let jwt_options = blueprint::Jwt {
+            jwks: JwksStore::new(
+                JwkSet { keys: vec![JWK_SET.keys[1].clone()] },
+                Some(refresh),
+            ),
+            ..blueprint::Jwt::test_value()
+        };
+        let jwt_provider = JwtVerifier::new(jwt_options);
+
+        let request = create_jwt_auth_request(JWT_VALID_TOKEN_WITH_KID);
+        assert_succeed_with_claims(&jwt_provider.verify(&request).await);
+        // The key is known now, so the second token doesn't fetch again
+        assert_succeed_with_claims(&jwt_provider.verify(&request).await);
+
+        jwks_mock.assert();
+    }
+
+    #[test]
+    fn strip_scheme_is_case_insensitive() {
+        assert_eq!(strip_scheme("Bearer abc", "Bearer"), Some("abc"));
+        assert_eq!(strip_scheme("bearer  abc ", "Bearer"), Some("abc"));
+        assert_eq!(strip_scheme("Basic abc", "Bearer"), None);
+        assert_eq!(strip_scheme("abc", "Bearer"), None);
+    }
+
     mod iss {
         use super::*;
         use crate::core::blueprint::Jwt;
@@ -0,0 +1,299 @@
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, PoisonError, RwLock, Weak};
+use std::time::{Duration, Instant};
+
+use anyhow::{Result, bail};
+use cache_control::CacheControl;
+use jsonwebtoken::jwk::{Jwk, JwkSet};
+
+use crate::core::config::JwksRefresh;
+use crate::core::runtime::TargetRuntime;
+
+/// Signing keys of a JWT provider. Clones share the same keys, so every
+/// verifier built from a blueprint sees the result of a refresh.
+#[derive(Clone)]
+pub struct JwksStore {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    refresh: Option<JwksRefresh>,
+    keys: RwLock<Keys>,
+    /// Held while fetching so concurrent requests with an unknown `kid`
+    /// share a single fetch.
+    fetching: tokio::sync::Mutex<()>,
+    refreshing: AtomicBool,
+}
+
+struct Keys {
+    current: JwkSet,
+    /// Keys removed by a refresh, with the instant they stop being accepted.
+    retired: Vec<(Jwk, Instant)>,
+    last_fetch: Option<Instant>,
+}
+
+impl JwksStore {
+    #[must_use]
+    pub fn new(set: JwkSet, refresh: Option<JwksRefresh>) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                refresh,
+                keys: RwLock::new(Keys { current: set, retired: vec![], last_fetch: None }),
+                fetching: tokio::sync::Mutex::new(()),
+                refreshing: AtomicBool::new(false),
+            }),
+        }
+    }
+
+    /// Keys a token can currently be verified with: the latest set and the
+    /// keys still within their grace period.
+    #[must_use]
+    pub fn keys(&self) -> JwkSet {
+        self.inner.keys_at(Instant::now())
+    }
+
+    /// Fetches the keys again after a token referenced an unknown `kid`.
+    /// Returns `true` if the keys were fetched since the call started, and
+    /// `false` if the store isn't remote, the last fetch is more recent than
+    /// `minRefetchInterval` or the fetch failed.
+    pub async fn refetch(&self, runtime: &TargetRuntime) -> bool {
+        let Some(refresh) = &self.inner.refresh else {
+            return false;
+        };
+
+        let requested = Instant::now();
+        let _guard = self.inner.fetching.lock().await;
+
+        match self.inner.last_fetch() {
+            // Another request fetched the keys while this one was waiting.
+            Some(last_fetch) if last_fetch >= requested => return true,
+            Some(last_fetch) if last_fetch.elapsed() < refresh.min_refetch_interval => {
+                return false;
+            }
+            _ => {}
+        }
+
+        match self.inner.fetch(runtime).await {
+            Ok(_) => true,
+            Err(err) => {
+                tracing::warn!("Failed to refetch JWKS from {}: {err}", refresh.url);
+                false
+            }
+        }
+    }
+
+    /// Starts refreshing remote keys in the background. Does nothing for keys
+    /// read from a file or once the refresh is running. The task stops when
+    /// the last clone of the store is dropped.
+    pub fn spawn_refresh(&self, runtime: &TargetRuntime) {
+        let Some(refresh) = self.inner.refresh.clone() else {
+            return;
+        };
+        if self.inner.refreshing.swap(true, Ordering::AcqRel) {
+            return;
+        }
+
+        let store = Arc::downgrade(&self.inner);
+        let runtime = runtime.clone();
+        tokio::spawn(async move {
+            let mut delay = refresh_delay(&refresh, None);
+            loop {
+                tokio::time::sleep(delay).await;
+                let Some(inner) = Weak::upgrade(&store) else {
+                    break;
+                };
+
+                let _guard = inner.fetching.lock().await;
+                delay = match inner.fetch(&runtime).await {
+                    Ok(max_age) => refresh_delay(&refresh, max_age),
+                    Err(err) => {
+                        tracing::warn!("Failed to refresh JWKS from {}: {err}", refresh.url);
+                        refresh.min_refetch_interval
+                    }
+                };
+            }
+        });
+    }
+}
+
+/// Time until the next background refresh: `refreshInterval`, else the
+/// `max-age` of the last response. Never shorter than `minRefetchInterval`, so
+/// a `max-age=0` doesn't refetch in a loop.
+fn refresh_delay(refresh: &JwksRefresh, max_age: Option<Duration>) -> Duration {
+    refresh
+        .interval
+        .or(max_age)
+        .unwrap_or(JwksRefresh::DEFAULT_INTERVAL)
+        .max(refresh.min_refetch_interval)
+}
+
+impl Inner {
+    fn last_fetch(&self) -> Option<Instant> {
+        self.keys
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .last_fetch
+    }
+
+    fn keys_at(&self, now: Instant) -> JwkSet {
+        let keys = self.keys.read().unwrap_or_else(PoisonError::into_inner);
+        let mut set = keys.current.clone();
+        set.keys.extend(
+            keys.retired
+                .iter()
+                .filter(|(_, until)| *until > now)
+                .map(|(jwk, _)| jwk.clone()),
+        );
+        set
+    }
+
+    /// Fetches the keys from the remote endpoint and returns the `max-age` of
+    /// the response.
+    async fn fetch(&self, runtime: &TargetRuntime) -> Result<Option<Duration>> {
+        let Some(refresh) = &self.refresh else {
+            return Ok(None);
+        };
+
+        let request = reqwest::Request::new(reqwest::Method::GET, refresh.url.parse()?);
+        let response = runtime.http.execute(request).await?;
+        if !response.status.is_success() {
+            bail!("Unexpected status {}", response.status);
+        }
+
+        let set: JwkSet = serde_json::from_slice(&response.body)?;
+        let max_age = response
+            .headers
+            .get(http::header::CACHE_CONTROL)
+            .and_then(|value| value.to_str().ok())
+            .and_then(CacheControl::from_value)
+            .and_then(|cache_control| cache_control.max_age);
+
+        self.replace(set, Instant::now());
+        Ok(max_age)
+    }
+
+    /// Makes `set` the current keys. Keys that are no longer served are
+    /// retired and stay valid for the grace period.
+    fn replace(&self, set: JwkSet, now: Instant) {
+        let grace_period = self
+            .refresh
+            .as_ref()
+            .map_or(Duration::ZERO, |refresh| refresh.grace_period);
+
+        let mut keys = self.keys.write().unwrap_or_else(PoisonError::into_inner);
+        let removed = keys
+            .current
+            .keys
+            .iter()
+            .filter(|jwk| !set.keys.contains(jwk))
+            .map(|jwk| (jwk.clone(), now + grace_period))
+            .collect::<Vec<_>>();
+
+        keys.retired
+            .retain(|(jwk, until)| *until > now && !set.keys.contains(jwk));
+        keys.retired.extend(removed);
+        keys.current = set;
+        keys.last_fetch = Some(now);
+    }
+}
+
+impl PartialEq for JwksStore {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+            || (self.inner.refresh == other.inner.refresh && self.keys() == other.keys())
+    }
+}
+
+impl Eq for JwksStore {}
+
+impl Debug for JwksStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JwksStore")
+            .field("keys", &self.keys())
+            .field("refresh", &self.inner.refresh)
+            .finish()
+    }
+}
+
+impl From<JwkSet> for JwksStore {
+    fn from(set: JwkSet) -> Self {
+        Self::new(set, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![expect(clippy::unwrap_used, reason = "test code")]
+
+    use super::*;
+    use crate::core::auth::jwt::jwt_verify::tests::JWK_SET;
+
+    fn remote(set: JwkSet) -> JwksStore {
+        let refresh = JwksRefresh::from_meta(
+            "https://example.com/.well-known/jwks.json".to_owned(),
+            Some(&serde_json::json!({ "gracePeriod": 60 })),
+        );
+        JwksStore::new(set, Some(refresh))
+    }
+
+    #[test]
+    fn retires_removed_keys_for_the_grace_period() {
+        let store = remote(JWK_SET.clone());
+        let rotated = JwkSet { keys: vec![JWK_SET.keys[1].clone()] };
+        let now = Instant::now();
+
+        store.inner.replace(rotated.clone(), now);
+
+        assert_eq!(store.inner.keys_at(now).keys.len(), 2);
+        assert_eq!(store.inner.keys_at(now + Duration::from_secs(61)), rotated);
+    }
+
+    #[test]
+    fn restored_keys_are_no_longer_retired() {
+        let store = remote(JWK_SET.clone());
+        let now = Instant::now();
+
+        store
+            .inner
+            .replace(JwkSet { keys: vec![JWK_SET.keys[1].clone()] }, now);
+        store.inner.replace(JWK_SET.clone(), now);
+
+        assert!(
+            store
+                .inner
+                .keys
+                .read()
+                .unwrap_or_else(PoisonError::into_inner)
+                .retired
+                .is_empty()
+        );
+        assert_eq!(store.inner.keys_at(now), *JWK_SET);
+    }
+
+    #[test]
+    fn refresh_delay_is_clamped_to_min_refetch_interval() {
+        let refresh = remote(JWK_SET.clone()).inner.refresh.clone().unwrap();
+
+        assert_eq!(
+            refresh_delay(&refresh, Some(Duration::ZERO)),
+            refresh.min_refetch_interval
+        );
+        assert_eq!(
+            refresh_delay(&refresh, Some(Duration::from_secs(120))),
+            Duration::from_secs(120)
+        );
+        assert_eq!(refresh_delay(&refresh, None), JwksRefresh::DEFAULT_INTERVAL);
+    }
+
+    #[test]
+    fn clones_share_keys() {
+        let store = remote(JWK_SET.clone());
+        let clone = store.clone();
+
+        store.inner.replace(JwkSet { keys: vec![] }, Instant::now());
+
+        assert!(clone.inner.last_fetch().is_some());
+        assert_eq!(clone, store);
+    }
+}
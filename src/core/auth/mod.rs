@@ -1,5 +1,7 @@
+pub mod api_key;
 pub mod basic;
 pub mod error;
+pub mod introspection;
 pub mod jwt;
 mod verification;
 pub mod verify;
@@ -1,9 +1,12 @@
 use std::collections::HashSet;
 use std::fmt::Debug;
 
-use jsonwebtoken::jwk::JwkSet;
-
-use crate::core::config::{ConfigModule, Content};
+use crate::core::auth::introspection::IntrospectionCache;
+use crate::core::auth::jwt::store::JwksStore;
+use crate::core::config::{
+    ApiKeyEntry, ApiKeyLink, ApiKeySource, ConfigModule, Content, IntrospectionLink, JwksLink,
+    TokenSource,
+};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Basic {
@@ -15,13 +18,32 @@ pub struct Jwt {
     pub issuer: Option<String>,
     pub audiences: HashSet<String>,
     pub optional_kid: bool,
-    pub jwks: JwkSet,
+    pub jwks: JwksStore,
+    pub token_sources: Vec<TokenSource>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Introspection {
+    pub url: String,
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+    pub audiences: HashSet<String>,
+    pub scopes: Vec<String>,
+    pub cache: IntrospectionCache,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ApiKey {
+    pub source: ApiKeySource,
+    pub keys: Vec<ApiKeyEntry>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Provider {
     Basic(Basic),
     Jwt(Jwt),
+    Introspection(Introspection),
+    ApiKey(ApiKey),
 }
 
 impl From<Content<String>> for Content<Provider> {
@@ -33,15 +55,45 @@ impl From<Content<String>> for Content<Provider> {
     }
 }
 
-impl From<Content<JwkSet>> for Content<Provider> {
-    fn from(content: Content<JwkSet>) -> Self {
+impl From<Content<JwksLink>> for Content<Provider> {
+    fn from(content: Content<JwksLink>) -> Self {
         Content {
             id: content.id,
             content: Provider::Jwt(Jwt {
-                jwks: content.content,
+                jwks: JwksStore::new(content.content.keys, content.content.refresh),
                 issuer: None,
                 audiences: HashSet::new(),
                 optional_kid: false,
+                token_sources: content.content.token_sources,
+            }),
+        }
+    }
+}
+
+impl From<Content<IntrospectionLink>> for Content<Provider> {
+    fn from(content: Content<IntrospectionLink>) -> Self {
+        let link = content.content;
+        Content {
+            id: content.id,
+            content: Provider::Introspection(Introspection {
+                url: link.url,
+                client_id: link.client_id,
+                client_secret: link.client_secret,
+                audiences: link.audiences.into_iter().collect(),
+                scopes: link.scopes,
+                cache: IntrospectionCache::default(),
+            }),
+        }
+    }
+}
+
+impl From<Content<ApiKeyLink>> for Content<Provider> {
+    fn from(content: Content<ApiKeyLink>) -> Self {
+        Content {
+            id: content.id,
+            content: Provider::ApiKey(ApiKey {
+                source: content.content.source,
+                keys: content.content.keys,
             }),
         }
     }
@@ -63,6 +115,20 @@ impl Provider {
                     .iter()
                     .map(|jwks| jwks.clone().into()),
             )
+            .chain(
+                config_module
+                    .extensions()
+                    .introspection
+                    .iter()
+                    .map(|introspection| introspection.clone().into()),
+            )
+            .chain(
+                config_module
+                    .extensions()
+                    .api_keys
+                    .iter()
+                    .map(|api_keys| api_keys.clone().into()),
+            )
             .collect()
     }
 }
@@ -0,0 +1,289 @@
+use std::collections::{BTreeSet, HashSet};
+
+use super::directive::Directive;
+use super::{Blueprint, Definition, InterfaceTypeDefinition, ObjectTypeDefinition};
+use crate::core::config::Contract;
+
+/// Federation directive tagging fields and types for contracts.
+const TAG: &str = "tag";
+
+impl Blueprint {
+    /// The view of the blueprint served on a contract's route. Fields, objects
+    /// and interfaces tagged with one of the excluded tags are removed, along
+    /// with the fields returning removed types and the types no longer
+    /// reachable from the root operations.
+    #[must_use]
+    pub fn contract(&self, contract: &Contract) -> Blueprint {
+        let mut blueprint = self.clone();
+        blueprint.server.contracts.clear();
+
+        let is_excluded = |directives: &[Directive]| {
+            directives.iter().any(|directive| {
+                directive.name == TAG
+                    && directive
+                        .arguments
+                        .get("name")
+                        .and_then(serde_json::Value::as_str)
+                        .is_some_and(|name| contract.exclude_tags.iter().any(|tag| tag == name))
+            })
+        };
+
+        let query = blueprint.schema.query.clone();
+        let mut removed = blueprint
+            .definitions
+            .iter()
+            .filter(|def| matches!(def, Definition::Object(_) | Definition::Interface(_)))
+            .filter(|def| def.name() != query && is_excluded(def.directives()))
+            .map(|def| def.name().to_string())
+            .collect::<HashSet<_>>();
+
+        for def in &mut blueprint.definitions {
+            if let Definition::Object(ObjectTypeDefinition { fields, .. })
+            | Definition::Interface(InterfaceTypeDefinition { fields, .. }) = def
+            {
+                fields.retain(|field| !is_excluded(&field.directives));
+            }
+        }
+
+        // Removing a type removes the fields returning it, which can leave
+        // other types empty in turn
+        loop {
+            blueprint
+                .definitions
+                .retain(|def| !removed.contains(def.name()));
+            let implementations = implemented_fields(&blueprint.definitions);
+
+            let mut emptied = Vec::new();
+            for def in &mut blueprint.definitions {
+                let (name, fields) = match def {
+                    Definition::Object(def) => {
+                        def.implements.retain(|name| !removed.contains(name));
+                        (&def.name, &mut def.fields)
+                    }
+                    Definition::Interface(def) => {
+                        def.implements.retain(|name| !removed.contains(name));
+                        // Every implementation must still have the interface's fields
+                        let implemented = implementations
+                            .iter()
+                            .filter(|(implements, _)| implements.contains(&def.name))
+                            .map(|(_, fields)| fields)
+                            .collect::<Vec<_>>();
+                        def.fields.retain(|field| {
+                            implemented
+                                .iter()
+                                .all(|fields| fields.contains(&field.name))
+                        });
+                        (&def.name, &mut def.fields)
+                    }
+                    Definition::Union(def) => {
+                        def.types.retain(|name| !removed.contains(name));
+                        if def.types.is_empty() {
+                            emptied.push(def.name.clone());
+                        }
+                        continue;
+                    }
+                    _ => continue,
+                };
+
+                fields.retain(|field| !removed.contains(field.of_type.name()));
+                if fields.is_empty() && *name != query {
+                    emptied.push(name.clone());
+                }
+            }
+
+            if emptied.is_empty() {
+                break;
+            }
+            removed.extend(emptied);
+        }
+
+        if blueprint
+            .schema
+            .mutation
+            .as_ref()
+            .is_some_and(|name| removed.contains(name))
+        {
+            blueprint.schema.mutation = None;
+        }
+        if blueprint
+            .schema
+            .subscription
+            .as_ref()
+            .is_some_and(|name| removed.contains(name))
+        {
+            blueprint.schema.subscription = None;
+        }
+
+        let reachable = reachable_types(&blueprint);
+        blueprint
+            .definitions
+            .retain(|def| reachable.contains(def.name()));
+
+        blueprint
+    }
+}
+
+/// The interfaces each object and interface implements, with the names of its
+/// fields.
+fn implemented_fields(definitions: &[Definition]) -> Vec<(BTreeSet<String>, HashSet<String>)> {
+    definitions
+        .iter()
+        .filter_map(|def| match def {
+            Definition::Object(ObjectTypeDefinition { implements, fields, .. })
+            | Definition::Interface(InterfaceTypeDefinition { implements, fields, .. }) => {
+                Some((implements, fields))
+            }
+            _ => None,
+        })
+        .map(|(implements, fields)| {
+            (
+                implements.clone(),
+                fields.iter().map(|field| field.name.clone()).collect(),
+            )
+        })
+        .collect()
+}
+
+/// Names of the types reachable from the root operation types, including the
+/// implementations of reachable interfaces.
+fn reachable_types(blueprint: &Blueprint) -> HashSet<String> {
+    let mut reachable = HashSet::new();
+    let mut pending = std::iter::once(blueprint.schema.query.clone())
+        .chain(blueprint.schema.mutation.clone())
+        .chain(blueprint.schema.subscription.clone())
+        .collect::<Vec<_>>();
+
+    while let Some(name) = pending.pop() {
+        if !reachable.insert(name.clone()) {
+            continue;
+        }
+
+        for def in &blueprint.definitions {
+            match def {
+                Definition::Object(ObjectTypeDefinition {
+                    name: type_name,
+                    implements,
+                    fields,
+                    ..
+                })
+                | Definition::Interface(InterfaceTypeDefinition {
+                    name: type_name,
+                    implements,
+                    fields,
+                    ..
+                }) => {
+                    if *type_name == name {
+                        pending.extend(implements.iter().cloned());
+                        for field in fields {
+                            pending.push(field.of_type.name().clone());
+                            pending.extend(field.args.iter().map(|arg| arg.of_type.name().clone()));
+                        }
+                    } else if implements.contains(&name) {
+                        pending.push(type_name.clone());
+                    }
+                }
+                Definition::InputObject(def) if def.name == name => {
+                    pending.extend(def.fields.iter().map(|field| field.of_type.name().clone()));
+                }
+                Definition::Union(def) if def.name == name => {
+                    pending.extend(def.types.iter().cloned());
+                }
+                _ => {}
+            }
+        }
+    }
+
+    reachable
+}
+
+#[cfg(test)]
+mod tests {
+    #![expect(clippy::unwrap_used, reason = "test code")]
+    use gqlforge_valid::Validator;
+
+    use super::*;
+    use crate::core::config::{Config, ConfigModule};
+
+    fn blueprint(sdl: &str) -> Blueprint {
+        let config = Config::from_sdl(sdl).to_result().unwrap();
+        Blueprint::try_from(&ConfigModule::from(config)).unwrap()
+    }
+
+    fn type_names(blueprint: &Blueprint) -> BTreeSet<&str> {
+        blueprint.definitions.iter().map(Definition::name).collect()
+    }
+
+    fn field_names<'a>(blueprint: &'a Blueprint, type_name: &str) -> Vec<&'a str> {
+        blueprint
+            .definitions
+            .iter()
+            .find_map(|def| match def {
+                Definition::Object(ObjectTypeDefinition { name, fields, .. })
+                | Definition::Interface(InterfaceTypeDefinition { name, fields, .. })
+                    if name == type_name =>
+                {
+                    Some(fields)
+                }
+                _ => None,
+            })
+            .unwrap()
+            .iter()
+            .map(|field| field.name.as_str())
+            .collect()
+    }
+
+    const SDL: &str = r#"
+        schema @server @upstream {
+          query: Query
+        }
+
+        type Query {
+          users: [User] @http(url: "http://jsonplaceholder.typicode.com/users")
+          audit: [AuditEntry] @http(url: "http://jsonplaceholder.typicode.com/audit")
+          nodes: [Node] @http(url: "http://jsonplaceholder.typicode.com/nodes")
+        }
+
+        interface Node {
+          id: Int!
+          owner: String
+        }
+
+        type User implements Node {
+          id: Int!
+          name: String
+          owner: String @tag(name: "internal")
+        }
+
+        type AuditEntry @tag(name: "internal") {
+          id: Int!
+          action: String
+        }
+    "#;
+
+    #[test]
+    fn removes_tagged_fields_and_types() {
+        let full = blueprint(SDL);
+        let public = full.contract(&Contract {
+            route: "/graphql".to_string(),
+            exclude_tags: vec!["internal".to_string()],
+        });
+
+        assert_eq!(field_names(&public, "Query"), ["users", "nodes"]);
+        assert_eq!(field_names(&public, "User"), ["id", "name"]);
+        // `owner` isn't implemented by `User` anymore
+        assert_eq!(field_names(&public, "Node"), ["id"]);
+        assert!(!type_names(&public).contains("AuditEntry"));
+
+        assert!(type_names(&full).contains("AuditEntry"));
+        assert_eq!(field_names(&full, "User"), ["id", "name", "owner"]);
+    }
+
+    #[test]
+    fn keeps_everything_without_excluded_tags() {
+        let full = blueprint(SDL);
+        let internal = full
+            .contract(&Contract { route: "/internal/graphql".to_string(), exclude_tags: vec![] });
+
+        assert_eq!(type_names(&internal), type_names(&full));
+    }
+}
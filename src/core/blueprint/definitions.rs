@@ -12,10 +12,10 @@ use crate::core::blueprint::{
     BlueprintError, ConfigModule, Definition, DynamicValue, EnumTypeDefinition,
     EnumValueDefinition, FieldDefinition, InputFieldDefinition, InputObjectTypeDefinition,
     InterfaceTypeDefinition, ObjectTypeDefinition, ScalarTypeDefinition, UnionTypeDefinition,
-    blueprint, directive, interface_resolver, union_resolver, update_enum_alias, update_modify,
-    update_protected, update_resolver, validate_field_has_resolver,
+    blueprint, directive, interface_resolver, union_resolver, update_enum_alias, update_mask,
+    update_modify, update_protected, update_resolver, validate_field_has_resolver,
 };
-use crate::core::config::{Config, Enum, Field, GraphQLOperationType, Protected, Union};
+use crate::core::config::{Config, Enum, Field, GraphQLOperationType, Mask, Protected, Union};
 use crate::core::directive::DirectiveCodec;
 use crate::core::ir::model::{Cache, IR};
 use crate::core::try_fold::TryFold;
@@ -572,6 +572,7 @@ pub fn to_field_definition(
         .and(update_cache_resolvers())
         .and(update_protected(object_name).trace(Protected::trace_name().as_str()))
         .and(update_enum_alias())
+        .and(update_mask().trace(Mask::trace_name().as_str()))
         .and(update_union_resolver())
         .and(update_interface_resolver())
         .try_fold(
@@ -0,0 +1,547 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::Serialize;
+
+use super::{Blueprint, Definition, FieldDefinition, InputFieldDefinition};
+use crate::core::Type;
+
+/// How a schema change affects existing clients.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ChangeSeverity {
+    /// Existing operations can stop validating or start failing.
+    Breaking,
+    /// Existing operations keep validating but may observe new behaviour,
+    /// e.g. an unknown enum value or union member.
+    Dangerous,
+    /// Additive change that can't affect existing operations.
+    Safe,
+}
+
+/// A single difference between two schemas.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SchemaChange {
+    pub severity: ChangeSeverity,
+    /// Schema coordinate of the changed element, e.g. `User.name` or
+    /// `Query.user(id:)`.
+    pub path: String,
+    pub message: String,
+}
+
+/// Differences between two [Blueprint]s, ordered by severity and path.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct SchemaDiff {
+    pub changes: Vec<SchemaChange>,
+}
+
+impl SchemaDiff {
+    #[must_use]
+    pub fn new(old: &Blueprint, new: &Blueprint) -> Self {
+        let mut diff = SchemaDiff::default();
+        diff.compare_roots(old, new);
+        diff.compare_definitions(old, new);
+        diff.changes
+            .sort_by(|a, b| (a.severity, &a.path).cmp(&(b.severity, &b.path)));
+        diff
+    }
+
+    #[must_use]
+    pub fn is_breaking(&self) -> bool {
+        self.changes
+            .iter()
+            .any(|change| change.severity == ChangeSeverity::Breaking)
+    }
+
+    /// Changes with the given severity.
+    pub fn filter(&self, severity: ChangeSeverity) -> impl Iterator<Item = &SchemaChange> {
+        self.changes
+            .iter()
+            .filter(move |change| change.severity == severity)
+    }
+
+    fn push(&mut self, severity: ChangeSeverity, path: impl Into<String>, message: String) {
+        self.changes
+            .push(SchemaChange { severity, path: path.into(), message });
+    }
+
+    fn compare_roots(&mut self, old: &Blueprint, new: &Blueprint) {
+        let roots = [
+            ("query", Some(old.query()), Some(new.query())),
+            ("mutation", old.mutation(), new.mutation()),
+            ("subscription", old.subscription(), new.subscription()),
+        ];
+        for (operation, old, new) in roots {
+            match (old, new) {
+                (Some(old), Some(new)) if old != new => self.push(
+                    ChangeSeverity::Breaking,
+                    "schema",
+                    format!("Root {operation} type changed from `{old}` to `{new}`"),
+                ),
+                (Some(old), None) => self.push(
+                    ChangeSeverity::Breaking,
+                    "schema",
+                    format!("Root {operation} type `{old}` was removed"),
+                ),
+                (None, Some(new)) => self.push(
+                    ChangeSeverity::Safe,
+                    "schema",
+                    format!("Root {operation} type `{new}` was added"),
+                ),
+                _ => {}
+            }
+        }
+    }
+
+    fn compare_definitions(&mut self, old: &Blueprint, new: &Blueprint) {
+        let old_defs = definitions(old);
+        let new_defs = definitions(new);
+
+        for (name, old_def) in &old_defs {
+            match new_defs.get(name) {
+                None => self.push(
+                    ChangeSeverity::Breaking,
+                    *name,
+                    format!("Type `{name}` was removed"),
+                ),
+                Some(new_def) => self.compare_definition(old_def, new_def),
+            }
+        }
+
+        for name in new_defs.keys().filter(|name| !old_defs.contains_key(*name)) {
+            self.push(
+                ChangeSeverity::Safe,
+                *name,
+                format!("Type `{name}` was added"),
+            );
+        }
+    }
+
+    fn compare_definition(&mut self, old: &Definition, new: &Definition) {
+        let name = old.name();
+        match (old, new) {
+            (Definition::Object(old), Definition::Object(new)) => {
+                self.compare_fields(name, &old.fields, &new.fields);
+                self.compare_implements(name, &old.implements, &new.implements);
+            }
+            (Definition::Interface(old), Definition::Interface(new)) => {
+                self.compare_fields(name, &old.fields, &new.fields);
+                self.compare_implements(name, &old.implements, &new.implements);
+            }
+            (Definition::InputObject(old), Definition::InputObject(new)) => {
+                self.compare_input_fields(name, &old.fields, &new.fields);
+            }
+            (Definition::Enum(old), Definition::Enum(new)) => {
+                let old_values = old.enum_values.iter().map(|v| v.name.as_str());
+                let new_values = new.enum_values.iter().map(|v| v.name.as_str());
+                self.compare_members(name, "Enum value", old_values, new_values);
+            }
+            (Definition::Union(old), Definition::Union(new)) => {
+                let old_types = old.types.iter().map(String::as_str);
+                let new_types = new.types.iter().map(String::as_str);
+                self.compare_members(name, "Union member", old_types, new_types);
+            }
+            (Definition::Scalar(_), Definition::Scalar(_)) => {}
+            _ => self.push(
+                ChangeSeverity::Breaking,
+                name,
+                format!("Type `{name}` changed from {} to {}", kind(old), kind(new)),
+            ),
+        }
+    }
+
+    fn compare_fields(
+        &mut self,
+        type_name: &str,
+        old: &[FieldDefinition],
+        new: &[FieldDefinition],
+    ) {
+        for old_field in old {
+            let path = format!("{type_name}.{}", old_field.name);
+            let Some(new_field) = new.iter().find(|f| f.name == old_field.name) else {
+                self.push(
+                    ChangeSeverity::Breaking,
+                    path,
+                    format!("Field `{type_name}.{}` was removed", old_field.name),
+                );
+                continue;
+            };
+
+            if old_field.of_type != new_field.of_type {
+                let severity = if is_safe_output_change(&old_field.of_type, &new_field.of_type) {
+                    ChangeSeverity::Safe
+                } else {
+                    ChangeSeverity::Breaking
+                };
+                self.push(
+                    severity,
+                    path.clone(),
+                    format!(
+                        "Field `{path}` changed type from `{:?}` to `{:?}`",
+                        old_field.of_type, new_field.of_type
+                    ),
+                );
+            }
+
+            self.compare_arguments(&path, &old_field.args, &new_field.args);
+        }
+
+        for new_field in new.iter().filter(|f| !old.iter().any(|o| o.name == f.name)) {
+            self.push(
+                ChangeSeverity::Safe,
+                format!("{type_name}.{}", new_field.name),
+                format!("Field `{type_name}.{}` was added", new_field.name),
+            );
+        }
+    }
+
+    fn compare_arguments(
+        &mut self,
+        field_path: &str,
+        old: &[InputFieldDefinition],
+        new: &[InputFieldDefinition],
+    ) {
+        for old_arg in old {
+            let path = format!("{field_path}({}:)", old_arg.name);
+            match new.iter().find(|a| a.name == old_arg.name) {
+                None => self.push(
+                    ChangeSeverity::Breaking,
+                    path,
+                    format!(
+                        "Argument `{}` was removed from `{field_path}`",
+                        old_arg.name
+                    ),
+                ),
+                Some(new_arg) => self.compare_input_value("Argument", path, old_arg, new_arg),
+            }
+        }
+
+        for new_arg in new.iter().filter(|a| !old.iter().any(|o| o.name == a.name)) {
+            let path = format!("{field_path}({}:)", new_arg.name);
+            if is_required_input(new_arg) {
+                self.push(
+                    ChangeSeverity::Breaking,
+                    path,
+                    format!(
+                        "Required argument `{}` was added to `{field_path}`",
+                        new_arg.name
+                    ),
+                );
+            } else {
+                self.push(
+                    ChangeSeverity::Safe,
+                    path,
+                    format!(
+                        "Optional argument `{}` was added to `{field_path}`",
+                        new_arg.name
+                    ),
+                );
+            }
+        }
+    }
+
+    fn compare_input_fields(
+        &mut self,
+        type_name: &str,
+        old: &[InputFieldDefinition],
+        new: &[InputFieldDefinition],
+    ) {
+        for old_field in old {
+            let path = format!("{type_name}.{}", old_field.name);
+            match new.iter().find(|f| f.name == old_field.name) {
+                None => self.push(
+                    ChangeSeverity::Breaking,
+                    path.clone(),
+                    format!("Input field `{path}` was removed"),
+                ),
+                Some(new_field) => {
+                    self.compare_input_value("Input field", path, old_field, new_field)
+                }
+            }
+        }
+
+        for new_field in new.iter().filter(|f| !old.iter().any(|o| o.name == f.name)) {
+            let path = format!("{type_name}.{}", new_field.name);
+            if is_required_input(new_field) {
+                self.push(
+                    ChangeSeverity::Breaking,
+                    path.clone(),
+                    format!("Required input field `{path}` was added"),
+                );
+            } else {
+                self.push(
+                    ChangeSeverity::Safe,
+                    path.clone(),
+                    format!("Optional input field `{path}` was added"),
+                );
+            }
+        }
+    }
+
+    fn compare_input_value(
+        &mut self,
+        label: &str,
+        path: String,
+        old: &InputFieldDefinition,
+        new: &InputFieldDefinition,
+    ) {
+        if old.of_type != new.of_type {
+            let severity = if is_safe_input_change(&old.of_type, &new.of_type) {
+                ChangeSeverity::Safe
+            } else {
+                ChangeSeverity::Breaking
+            };
+            self.push(
+                severity,
+                path.clone(),
+                format!(
+                    "{label} `{path}` changed type from `{:?}` to `{:?}`",
+                    old.of_type, new.of_type
+                ),
+            );
+        }
+
+        if old.default_value != new.default_value {
+            self.push(
+                ChangeSeverity::Dangerous,
+                path.clone(),
+                format!("{label} `{path}` changed its default value"),
+            );
+        }
+    }
+
+    fn compare_implements(&mut self, name: &str, old: &BTreeSet<String>, new: &BTreeSet<String>) {
+        for interface in old.difference(new) {
+            self.push(
+                ChangeSeverity::Breaking,
+                name,
+                format!("`{name}` no longer implements interface `{interface}`"),
+            );
+        }
+        for interface in new.difference(old) {
+            self.push(
+                ChangeSeverity::Dangerous,
+                name,
+                format!("`{name}` now implements interface `{interface}`"),
+            );
+        }
+    }
+
+    /// Compares enum values or union members: removing one breaks clients
+    /// that select or send it, adding one may surprise exhaustive clients.
+    fn compare_members<'a>(
+        &mut self,
+        name: &str,
+        label: &str,
+        old: impl Iterator<Item = &'a str>,
+        new: impl Iterator<Item = &'a str>,
+    ) {
+        let old = old.collect::<BTreeSet<_>>();
+        let new = new.collect::<BTreeSet<_>>();
+        for member in old.difference(&new) {
+            self.push(
+                ChangeSeverity::Breaking,
+                format!("{name}.{member}"),
+                format!("{label} `{member}` was removed from `{name}`"),
+            );
+        }
+        for member in new.difference(&old) {
+            self.push(
+                ChangeSeverity::Dangerous,
+                format!("{name}.{member}"),
+                format!("{label} `{member}` was added to `{name}`"),
+            );
+        }
+    }
+}
+
+fn definitions(blueprint: &Blueprint) -> BTreeMap<&str, &Definition> {
+    blueprint
+        .definitions
+        .iter()
+        .map(|def| (def.name(), def))
+        .collect()
+}
+
+fn kind(definition: &Definition) -> &'static str {
+    match definition {
+        Definition::Interface(_) => "interface",
+        Definition::Object(_) => "object",
+        Definition::InputObject(_) => "input object",
+        Definition::Scalar(_) => "scalar",
+        Definition::Enum(_) => "enum",
+        Definition::Union(_) => "union",
+    }
+}
+
+fn is_required_input(input: &InputFieldDefinition) -> bool {
+    !input.of_type.is_nullable() && input.default_value.is_none()
+}
+
+/// An output type may only become stricter: clients that handled `null` keep
+/// working when the field becomes non-null.
+fn is_safe_output_change(old: &Type, new: &Type) -> bool {
+    match (old, new) {
+        (
+            Type::Named { name: old_name, non_null: old_non_null },
+            Type::Named { name: new_name, non_null: new_non_null },
+        ) => old_name == new_name && (!old_non_null || *new_non_null),
+        (
+            Type::List { of_type: old_of, non_null: old_non_null },
+            Type::List { of_type: new_of, non_null: new_non_null },
+        ) => (!old_non_null || *new_non_null) && is_safe_output_change(old_of, new_of),
+        _ => false,
+    }
+}
+
+/// An input type may only become looser: clients sending a value keep
+/// working when the argument starts accepting `null`.
+fn is_safe_input_change(old: &Type, new: &Type) -> bool {
+    match (old, new) {
+        (
+            Type::Named { name: old_name, non_null: old_non_null },
+            Type::Named { name: new_name, non_null: new_non_null },
+        ) => old_name == new_name && (*old_non_null || !new_non_null),
+        (
+            Type::List { of_type: old_of, non_null: old_non_null },
+            Type::List { of_type: new_of, non_null: new_non_null },
+        ) => (*old_non_null || !new_non_null) && is_safe_input_change(old_of, new_of),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![expect(clippy::unwrap_used, reason = "test code")]
+    use super::*;
+    use crate::core::config::{Config, ConfigModule};
+
+    fn blueprint(sdl: &str) -> Blueprint {
+        let config = Config::from_sdl(sdl).to_result().unwrap();
+        Blueprint::try_from(&ConfigModule::from(config)).unwrap()
+    }
+
+    fn changes(old: &str, new: &str) -> Vec<(ChangeSeverity, String)> {
+        SchemaDiff::new(&blueprint(old), &blueprint(new))
+            .changes
+            .into_iter()
+            .map(|change| (change.severity, change.path))
+            .collect()
+    }
+
+    const BASE: &str = r#"
+        schema @server @upstream {
+          query: Query
+        }
+
+        type Query {
+          user(id: Int!, verbose: Boolean): User @http(url: "http://localhost/users/{{.args.id}}")
+          search(filter: Filter): [User] @http(url: "http://localhost/users")
+        }
+
+        input Filter {
+          name: String
+        }
+
+        enum Role {
+          ADMIN
+          MEMBER
+        }
+
+        type User {
+          id: Int!
+          name: String
+          role: Role
+        }
+    "#;
+
+    #[test]
+    fn identical_schemas_have_no_changes() {
+        assert!(changes(BASE, BASE).is_empty());
+    }
+
+    #[test]
+    fn removed_field_is_breaking() {
+        let new = BASE.replace("          name: String\n          role", "          role");
+        let diff = SchemaDiff::new(&blueprint(BASE), &blueprint(&new));
+        assert!(diff.is_breaking());
+        assert!(
+            diff.filter(ChangeSeverity::Breaking)
+                .any(|change| change.path == "User.name")
+        );
+    }
+
+    #[test]
+    fn nullability_changes() {
+        let tightened_output = BASE.replace(
+            "name: String\n          role",
+            "name: String!\n          role",
+        );
+        assert_eq!(
+            changes(BASE, &tightened_output),
+            vec![(ChangeSeverity::Safe, "User.name".to_string())]
+        );
+
+        let tightened_arg = BASE.replace("verbose: Boolean)", "verbose: Boolean!)");
+        assert_eq!(
+            changes(BASE, &tightened_arg),
+            vec![(ChangeSeverity::Breaking, "Query.user(verbose:)".to_string())]
+        );
+
+        let loosened_arg = BASE.replace("id: Int!,", "id: Int,");
+        assert_eq!(
+            changes(BASE, &loosened_arg),
+            vec![(ChangeSeverity::Safe, "Query.user(id:)".to_string())]
+        );
+    }
+
+    #[test]
+    fn added_arguments() {
+        let required = BASE.replace("verbose: Boolean)", "verbose: Boolean, limit: Int!)");
+        assert_eq!(
+            changes(BASE, &required),
+            vec![(ChangeSeverity::Breaking, "Query.user(limit:)".to_string())]
+        );
+
+        let optional = BASE.replace("verbose: Boolean)", "verbose: Boolean, limit: Int)");
+        assert_eq!(
+            changes(BASE, &optional),
+            vec![(ChangeSeverity::Safe, "Query.user(limit:)".to_string())]
+        );
+    }
+
+    #[test]
+    fn enum_value_changes() {
+        let removed = BASE.replace("          MEMBER\n", "");
+        assert_eq!(
+            changes(BASE, &removed),
+            vec![(ChangeSeverity::Breaking, "Role.MEMBER".to_string())]
+        );
+
+        let added = BASE.replace("MEMBER\n", "MEMBER\n          GUEST\n");
+        assert_eq!(
+            changes(BASE, &added),
+            vec![(ChangeSeverity::Dangerous, "Role.GUEST".to_string())]
+        );
+    }
+
+    #[test]
+    fn removed_type_is_breaking() {
+        let new = BASE
+            .replace("search(filter: Filter)", "search(name: String)")
+            .replace(
+                "        input Filter {\n          name: String\n        }\n",
+                "",
+            );
+        assert_eq!(
+            changes(BASE, &new),
+            vec![
+                (ChangeSeverity::Breaking, "Filter".to_string()),
+                (
+                    ChangeSeverity::Breaking,
+                    "Query.search(filter:)".to_string()
+                ),
+                (ChangeSeverity::Safe, "Query.search(name:)".to_string()),
+            ]
+        );
+    }
+}
@@ -46,6 +46,12 @@ pub enum BlueprintError {
     #[error("Protobuf files were not specified in the config")]
     ProtobufFilesNotSpecifiedInConfig,
 
+    #[error("batchKey is not supported for client-streaming gRPC methods")]
+    GrpcBatchingWithClientStreaming,
+
+    #[error("Streaming gRPC methods can't be called with the {0} protocol")]
+    GrpcStreamingRequiresGrpcProtocol(String),
+
     #[error("GroupBy is only supported for GET and POST requests")]
     GroupByOnlyForGetAndPost,
 
@@ -76,6 +82,26 @@ pub enum BlueprintError {
     #[error("Invalid access expression: {0}")]
     InvalidAccessExpression(String),
 
+    #[error("Invalid authorization directive: {0}")]
+    InvalidAuthorizationDirective(String),
+
+    #[error("Policy {0} not found")]
+    PolicyNotFound(String),
+
+    #[error(
+        "Contract route {0} must start with `/`, be unique, and differ from the status and REST routes"
+    )]
+    InvalidContractRoute(String),
+
+    #[error("Invalid gRPC status mapping from {0} to {1}")]
+    InvalidGrpcStatusMapping(String, u16),
+
+    #[error("@mask can only be used on scalar and enum fields, found {0}")]
+    MaskRequiresScalar(String),
+
+    #[error("@mask(strategy: {0}) can't be used on a field of type {1}")]
+    InvalidMaskStrategy(String, String),
+
     #[error("syntax error when parsing `{0}`")]
     SyntaxErrorWhenParsing(String),
 
@@ -169,6 +195,27 @@ pub enum BlueprintError {
     #[error("Key is required for HTTP2")]
     KeyIsRequiredForHTTP2,
 
+    #[error("Client certificate authentication requires HTTP2")]
+    ClientAuthRequiresHTTP2,
+
+    #[error("Client CA bundle contains no certificates")]
+    ClientCaIsEmpty,
+
+    #[error("Host '{0}' is listed by more than one OAuth2 link")]
+    OAuth2HostConflict(String),
+
+    #[error("Identity key link '{0}' not found")]
+    IdentityKeyNotFound(String),
+
+    #[error("Invalid identity key: {0}")]
+    InvalidIdentityKey(String),
+
+    #[error("Identity tokens must be sent to at least one host")]
+    IdentityHostsEmpty,
+
+    #[error("Sampling ratio must be between 0 and 1, got {0}")]
+    InvalidSamplingRatio(f64),
+
     #[error("Experimental headers must start with 'x-' or 'X-'. Got: '{0}'")]
     ExperimentalHeaderInvalidFormat(String),
 
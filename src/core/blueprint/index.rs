@@ -2,7 +2,7 @@ use std::collections::HashSet;
 
 use indexmap::IndexMap;
 
-use super::InputObjectTypeDefinition;
+use super::{EnumTypeDefinition, InputObjectTypeDefinition};
 use crate::core::blueprint::{
     Blueprint, Definition, FieldDefinition, InputFieldDefinition, SchemaDefinition,
 };
@@ -121,6 +121,14 @@ impl Index {
             _ => None,
         }
     }
+
+    #[must_use]
+    pub fn get_enum_definition(&self, type_name: &str) -> Option<&EnumTypeDefinition> {
+        match self.map.get(type_name) {
+            Some((Definition::Enum(enum_), _)) => Some(enum_),
+            _ => None,
+        }
+    }
 }
 
 impl From<&Blueprint> for Index {
@@ -72,6 +72,7 @@ fn to_type(def: &Definition) -> dynamic::Type {
                 let field = field.clone();
                 let type_ref = TypeRef::from(&field.of_type);
                 let field_name = &field.name.clone();
+                let coordinate = Arc::new((def.name.clone(), field.name.clone()));
 
                 let mut dyn_schema_field = dynamic::Field::new(
                     field_name,
@@ -105,13 +106,16 @@ fn to_type(def: &Definition) -> dynamic::Type {
                                 );
 
                                 let expr = expr.to_owned();
+                                let coordinate = coordinate.clone();
                                 FieldFuture::new(
                                     async move {
                                         let ctx: ResolverContext = ctx.into();
-                                        let ctx = &mut EvalContext::new(req_ctx, &ctx);
+                                        let ctx = &mut EvalContext::new(req_ctx, &ctx)
+                                            .with_schema_coordinate(&coordinate.0, &coordinate.1);
 
-                                        let value =
-                                            expr.eval(ctx).await.map_err(|err| err.extend())?;
+                                        let value = expr.eval(ctx).await.map_err(|err| {
+                                            err.extend().into_async_graphql_error()
+                                        })?;
 
                                         if let ConstValue::Null = value {
                                             Ok(FieldValue::NONE)
@@ -269,8 +273,13 @@ fn to_subscription_type(def: &ObjectTypeDefinition) -> dynamic::Type {
                                 request,
                             )
                             .await
-                            .map_err(|e| {
-                                async_graphql::Error::new(format!("gRPC streaming failed: {e}"))
+                            .map_err(|e| match e
+                                .downcast::<crate::core::ir::Error>()
+                            {
+                                Ok(err) => err.extend().into_async_graphql_error(),
+                                Err(e) => {
+                                    async_graphql::Error::new(format!("gRPC streaming failed: {e}"))
+                                }
                             })?
                         }
                         IO::GraphQLStream { req_template, field_name, stream_url } => {
@@ -321,7 +330,7 @@ fn to_subscription_type(def: &ObjectTypeDefinition) -> dynamic::Type {
                     Ok(stream.map(
                         |result: Result<ConstValue, crate::core::ir::Error>| match result {
                             Ok(value) => Ok(FieldValue::from(value)),
-                            Err(e) => Err(async_graphql::Error::new(e.to_string())),
+                            Err(e) => Err(e.extend().into_async_graphql_error()),
                         },
                     ))
                 })
@@ -1,8 +1,10 @@
 mod auth;
 mod blueprint;
 mod compress;
+mod contract;
 mod cors;
 mod definitions;
+mod diff;
 mod directive;
 mod dynamic_value;
 mod error;
@@ -26,6 +28,7 @@ pub use auth::*;
 pub use blueprint::*;
 pub use cors::*;
 pub use definitions::*;
+pub use diff::*;
 pub use dynamic_value::*;
 pub use error::*;
 pub use from_config::*;
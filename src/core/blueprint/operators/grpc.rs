@@ -7,7 +7,7 @@ use prost_reflect::prost_types::FileDescriptorSet;
 use super::apply_select;
 use crate::core::blueprint::BlueprintError;
 use crate::core::config::group_by::GroupBy;
-use crate::core::config::{Config, ConfigModule, Field, GraphQLOperationType, Grpc};
+use crate::core::config::{Config, ConfigModule, Field, GraphQLOperationType, Grpc, GrpcProtocol};
 use crate::core::grpc::protobuf::{ProtobufOperation, ProtobufSet};
 use crate::core::grpc::request_template::RequestTemplate;
 use crate::core::helpers;
@@ -204,6 +204,20 @@ pub fn compile_grpc(inputs: CompileGrpc) -> Valid<IR, BlueprintError> {
             }
         })
         .and_then(|(operation, url, headers, body)| {
+            if !grpc.batch_key.is_empty() && operation.method.is_client_streaming() {
+                return Valid::fail(BlueprintError::GrpcBatchingWithClientStreaming);
+            }
+
+            let protocol = grpc.protocol.unwrap_or_default();
+            let is_streaming = operation.method.is_client_streaming()
+                || operation.method.is_server_streaming()
+                || matches!(operation_type, GraphQLOperationType::Subscription);
+            if protocol != GrpcProtocol::Grpc && is_streaming {
+                return Valid::fail(BlueprintError::GrpcStreamingRequiresGrpcProtocol(
+                    protocol.to_string(),
+                ));
+            }
+
             let validation = if validate_with_schema {
                 let field_schema = json_schema_from_field(config_module, field);
                 if grpc.batch_key.is_empty() {
@@ -216,7 +230,9 @@ pub fn compile_grpc(inputs: CompileGrpc) -> Valid<IR, BlueprintError> {
             };
             validation.map(|()| (url, headers, operation, body))
         })
-        .map(|(url, headers, operation, body)| {
+        .map(|(url, headers, mut operation, body)| {
+            operation.compression = grpc.compression;
+            operation.protocol = grpc.protocol.unwrap_or_default();
             let req_template = RequestTemplate {
                 url,
                 headers,
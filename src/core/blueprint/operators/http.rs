@@ -69,15 +69,18 @@ pub fn compile_http(
                 })
                 .collect();
 
-            match RequestTemplate::try_from(
-                Endpoint::new(base_url.to_string())
-                    .method(http.method)
-                    .query(query)
-                    .body(http.body.clone())
-                    .encoding(http.encoding),
-            )
-            .map(|req_tmpl| req_tmpl.headers(headers))
-            {
+            let mut endpoint = Endpoint::new(base_url.to_string())
+                .method(http.method)
+                .query(query)
+                .body(http.body.clone())
+                .input(http.input.clone().unwrap_or_default())
+                .encoding(http.encoding);
+            // Responses are only checked against `output` when asked to
+            if config_module.server.enable_output_validation() {
+                endpoint = endpoint.output(http.output.clone().unwrap_or_default());
+            }
+
+            match RequestTemplate::try_from(endpoint).map(|req_tmpl| req_tmpl.headers(headers)) {
                 Ok(data) => Valid::succeed(data),
                 Err(e) => Valid::fail(BlueprintError::Error(e)),
             }
@@ -177,6 +180,7 @@ mod test {
     use serde_json::json;
 
     use super::*;
+    use crate::core::json::JsonSchema;
 
     #[test]
     fn test_extract_expression_keys_from_nested_objects() {
@@ -200,4 +204,38 @@ mod test {
         let keys = count_dynamic_paths(&json);
         assert_eq!(keys, 1);
     }
+
+    #[test]
+    fn test_output_validation_flag() {
+        let http = config::Http {
+            url: "http://localhost/users".to_string(),
+            output: Some(JsonSchema::Str),
+            ..Default::default()
+        };
+        let output = |output_validation| {
+            let config = config::Config {
+                server: config::Server { output_validation, ..Default::default() },
+                ..Default::default()
+            };
+            let ir = compile_http(
+                &config::ConfigModule::from(config),
+                &http,
+                &Field::default(),
+                &GraphQLOperationType::Query,
+            )
+            .to_result()
+            .unwrap();
+            let IR::IO(io) = ir else {
+                panic!("expected an IO");
+            };
+            let IO::Http { req_template, .. } = *io else {
+                panic!("expected an HTTP IO");
+            };
+            req_template.endpoint.output
+        };
+
+        // Existing `output` schemas aren't checked unless asked to
+        assert_eq!(output(None), JsonSchema::default());
+        assert_eq!(output(Some(true)), JsonSchema::Str);
+    }
 }
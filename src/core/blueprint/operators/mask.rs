@@ -0,0 +1,94 @@
+use gqlforge_valid::Valid;
+
+use crate::core::blueprint::{Auth, BlueprintError, ConfigModule, FieldDefinition};
+use crate::core::config::{self, Field, MaskStrategy};
+use crate::core::ir::access_expr::AccessExpr;
+use crate::core::ir::model::{IR, Mask};
+use crate::core::try_fold::TryFold;
+use crate::core::wrapping_type::Type;
+
+/// Scalars a masked string can't be returned as.
+const NON_STRING_SCALARS: &[&str] = &["Int", "Float", "Boolean"];
+
+/// `NULL` can only be returned when the field and its list items are nullable.
+fn is_nullable(type_of: &Type) -> bool {
+    match type_of {
+        Type::Named { non_null, .. } => !non_null,
+        Type::List { of_type, non_null } => !non_null && is_nullable(of_type),
+    }
+}
+
+#[must_use]
+pub fn update_mask<'a>() -> TryFold<
+    'a,
+    (&'a ConfigModule, &'a Field, &'a config::Type, &'a str),
+    FieldDefinition,
+    BlueprintError,
+> {
+    TryFold::<(&ConfigModule, &Field, &config::Type, &'a str), FieldDefinition, BlueprintError>::new(
+        |(config, field, _, _), mut b_field| {
+            let Some(mask) = &field.mask else {
+                return Valid::succeed(b_field);
+            };
+
+            let type_name = field.type_of.name();
+            if !config.is_scalar(type_name) && config.find_enum(type_name).is_none() {
+                return Valid::fail(BlueprintError::MaskRequiresScalar(type_name.clone()));
+            }
+
+            let invalid = match mask.strategy {
+                MaskStrategy::Partial | MaskStrategy::Hash => {
+                    NON_STRING_SCALARS.contains(&type_name.as_str())
+                }
+                MaskStrategy::Null => !is_nullable(&field.type_of),
+            };
+            if invalid {
+                return Valid::fail(BlueprintError::InvalidMaskStrategy(
+                    mask.strategy.to_string(),
+                    format!("{:?}", field.type_of),
+                ));
+            }
+
+            let unless = match mask.unless.as_deref().map(AccessExpr::parse).transpose() {
+                Ok(unless) => unless,
+                Err(e) => return Valid::fail(BlueprintError::InvalidAccessExpression(e)),
+            };
+
+            // Claims are verified on demand when `unless` refers to them and the
+            // field isn't protected
+            let auth = if unless.is_some() && config.extensions().has_auth() {
+                Auth::from_config(config)
+            } else {
+                None
+            };
+
+            let inner = b_field
+                .resolver
+                .take()
+                .unwrap_or_else(|| IR::ContextPath(vec![b_field.name.clone()]));
+            b_field.resolver = Some(IR::Mask(
+                Mask { strategy: mask.strategy, unless, auth },
+                Box::new(inner),
+            ));
+
+            Valid::succeed(b_field)
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn named(name: &str, non_null: bool) -> Type {
+        Type::Named { name: name.to_string(), non_null }
+    }
+
+    #[test]
+    fn nullable_lists_need_nullable_items() {
+        assert!(is_nullable(&named("String", false)));
+        assert!(!is_nullable(&named("String", true)));
+        assert!(is_nullable(&named("String", false).into_list()));
+        assert!(!is_nullable(&named("String", true).into_list()));
+    }
+}
@@ -6,6 +6,7 @@ mod graphql;
 mod grpc;
 mod http;
 mod js;
+mod mask;
 mod modify;
 mod postgres;
 mod protected;
@@ -21,6 +22,7 @@ pub use graphql::*;
 pub use grpc::*;
 pub use http::*;
 pub use js::*;
+pub use mask::*;
 pub use modify::*;
 pub use postgres::*;
 pub use protected::*;
@@ -1,11 +1,77 @@
+use std::collections::BTreeMap;
+
 use gqlforge_valid::{Valid, Validator};
 
 use crate::core::blueprint::{Auth, BlueprintError, FieldDefinition, Provider};
-use crate::core::config::{self, ConfigModule, Field};
+use crate::core::config::{self, ConfigModule, Directive, Field};
 use crate::core::ir::access_expr::AccessExpr;
 use crate::core::ir::model::IR;
 use crate::core::try_fold::TryFold;
 
+/// Federation directive requiring the token to be granted scopes.
+const REQUIRES_SCOPES: &str = "requiresScopes";
+/// Federation directive requiring named policies from `@server(policies:)`.
+const POLICY: &str = "policy";
+
+fn requires_authorization(directives: &[Directive]) -> bool {
+    directives
+        .iter()
+        .any(|directive| directive.name == REQUIRES_SCOPES || directive.name == POLICY)
+}
+
+/// Reads an argument such as `scopes: [["read:users", "write:users"],
+/// ["admin"]]`, where each inner list is an alternative whose items are all
+/// required.
+fn alternatives(directive: &Directive, argument: &str) -> Valid<Vec<Vec<String>>, BlueprintError> {
+    let alternatives = directive
+        .arguments
+        .get(argument)
+        .and_then(|value| serde_json::from_value::<Vec<Vec<String>>>(value.clone()).ok())
+        .filter(|alternatives| {
+            !alternatives.is_empty() && alternatives.iter().all(|items| !items.is_empty())
+        });
+
+    Valid::from_option(
+        alternatives,
+        BlueprintError::InvalidAuthorizationDirective(format!(
+            "@{}({argument}:) must be a non-empty list of non-empty lists of strings",
+            directive.name
+        )),
+    )
+}
+
+fn policy(name: &str, policies: &BTreeMap<String, String>) -> Valid<AccessExpr, BlueprintError> {
+    Valid::from_option(
+        policies.get(name),
+        BlueprintError::PolicyNotFound(name.to_owned()),
+    )
+    .and_then(|expr| match AccessExpr::parse(expr) {
+        Ok(expr) => Valid::succeed(expr),
+        Err(e) => Valid::fail(BlueprintError::InvalidAccessExpression(e)),
+    })
+}
+
+/// Access expressions required by the `@requiresScopes` and `@policy`
+/// directives.
+fn authorization<'a>(
+    directives: impl IntoIterator<Item = &'a Directive>,
+    policies: &BTreeMap<String, String>,
+) -> Valid<Vec<AccessExpr>, BlueprintError> {
+    Valid::from_iter(directives, |directive| match directive.name.as_str() {
+        REQUIRES_SCOPES => {
+            alternatives(directive, "scopes").map(|scopes| Some(AccessExpr::Scopes(scopes)))
+        }
+        POLICY => alternatives(directive, "policies").and_then(|alternatives| {
+            Valid::from_iter(alternatives, |names| {
+                Valid::from_iter(names, |name| policy(&name, policies)).map(AccessExpr::all)
+            })
+            .map(|alternatives| AccessExpr::any(alternatives.into_iter().flatten()))
+        }),
+        _ => Valid::succeed(None),
+    })
+    .map(|exprs| exprs.into_iter().flatten().collect())
+}
+
 #[must_use]
 pub fn update_protected<'a>(
     type_name: &'a str,
@@ -17,12 +83,16 @@ pub fn update_protected<'a>(
 > {
     TryFold::<(&ConfigModule, &Field, &config::Type, &'a str), FieldDefinition, BlueprintError>::new(
         |(config, field, type_, _), mut b_field| {
+            let output_type = config.find_type(field.type_of.name());
+
             if field.protected.is_some() // check the field itself has marked as protected
                 || type_.protected.is_some() // check the type that contains current field
-                || config // check that output type of the field is protected
-                    .find_type(field.type_of.name())
+                || output_type // check that output type of the field is protected
                     .and_then(|type_| type_.protected.as_ref())
                     .is_some()
+                || requires_authorization(&field.directives)
+                || requires_authorization(&type_.directives)
+                || output_type.is_some_and(|type_| requires_authorization(&type_.directives))
             {
                 if config.input_types().contains(type_name) {
                     return Valid::fail(BlueprintError::InputTypesCannotBeProtected);
@@ -74,6 +144,19 @@ pub fn update_protected<'a>(
                     Valid::succeed(None)
                 };
 
+                // Scopes and policies required on the field, its type and the
+                // output type must all be satisfied, along with the expression.
+                let policies = config.server.get_policies();
+                let directives = [Some(type_), output_type]
+                    .into_iter()
+                    .flatten()
+                    .flat_map(|type_| &type_.directives)
+                    .chain(&field.directives);
+
+                let parsed_expr = parsed_expr
+                    .zip(authorization(directives, &policies))
+                    .map(|(expr, required)| AccessExpr::all(expr.into_iter().chain(required)));
+
                 parsed_expr.and_then(|access_expr| {
                     Valid::from_iter(protection.iter(), |id| {
                         if let Some(provider) = providers.get(id) {
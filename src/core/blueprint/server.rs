@@ -1,4 +1,4 @@
-use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::net::{AddrParseError, IpAddr};
 use std::path::PathBuf;
 use std::str::FromStr;
@@ -10,9 +10,16 @@ use http::header::{HeaderMap, HeaderName, HeaderValue};
 use rustls_pki_types::CertificateDer;
 
 use super::BlueprintError;
-use crate::core::blueprint::Cors;
+use crate::core::blueprint::{Auth, Cors, DynamicValue, Provider};
 use crate::core::config::headers::Headers;
-use crate::core::config::{self, ConfigModule, HttpVersion, PrivateKey, Routes};
+use crate::core::config::{
+    self, ClientAuthMode, ConfigModule, Contract, GrpcStatusMapping, HttpVersion, PrivateKey,
+    Routes,
+};
+use crate::core::grpc::status::{GrpcStatusMap, code_from_name};
+use crate::core::http::API_URL_PREFIX;
+use crate::core::ir::access_expr::AccessExpr;
+use crate::core::ir::model::IR;
 
 #[derive(Clone, Debug, Setters)]
 #[expect(
@@ -26,6 +33,7 @@ pub struct Server {
     pub enable_introspection: bool,
     pub enable_query_validation: bool,
     pub enable_response_validation: bool,
+    pub enable_output_validation: bool,
     pub enable_batch_requests: bool,
     pub enable_showcase: bool,
     pub global_response_timeout: i64,
@@ -44,6 +52,11 @@ pub struct Server {
     pub limit_depth: usize,
     pub limit_directives: usize,
     pub spa_dir: Option<PathBuf>,
+    /// Evaluated before introspection queries when `introspectionAccess` is
+    /// set.
+    pub introspection_protection: Option<IR>,
+    pub contracts: Vec<Contract>,
+    pub grpc_status_map: GrpcStatusMap,
 }
 
 /// Mimic of `mini_v8::Script` that's wasm compatible
@@ -59,9 +72,17 @@ pub enum Http {
     HTTP2 {
         cert: Vec<CertificateDer<'static>>,
         key: PrivateKey,
+        client_auth: Option<ClientAuth>,
     },
 }
 
+/// Verification of client certificates on the HTTP/2 listener.
+#[derive(Clone, Debug)]
+pub struct ClientAuth {
+    pub ca: Vec<CertificateDer<'static>>,
+    pub mode: ClientAuthMode,
+}
+
 impl Default for Server {
     fn default() -> Self {
         // NOTE: Using unwrap because try_from default will never fail
@@ -72,7 +93,7 @@ impl Default for Server {
 
 impl Server {
     pub fn get_enable_http_validation(&self) -> bool {
-        self.enable_response_validation
+        self.enable_response_validation || self.enable_output_validation
     }
     pub fn get_enable_cache_control(&self) -> bool {
         self.enable_cache_control_header
@@ -112,7 +133,21 @@ impl TryFrom<crate::core::config::ConfigModule> for Server {
                     .ok_or_else(|| ValidationError::new(BlueprintError::KeyIsRequiredForHTTP2))?
                     .clone();
 
-                Valid::succeed(Http::HTTP2 { cert, key })
+                let client_auth = match &config_server.client_auth {
+                    Some(_) if config_module.extensions().client_ca.is_empty() => {
+                        return Valid::fail(BlueprintError::ClientCaIsEmpty).to_result();
+                    }
+                    Some(client_auth) => Some(ClientAuth {
+                        ca: config_module.extensions().client_ca.clone(),
+                        mode: client_auth.mode,
+                    }),
+                    None => None,
+                };
+
+                Valid::succeed(Http::HTTP2 { cert, key, client_auth })
+            }
+            HttpVersion::HTTP1 if config_server.client_auth.is_some() => {
+                Valid::fail(BlueprintError::ClientAuthRequiresHTTP2)
             }
             HttpVersion::HTTP1 => Valid::succeed(Http::HTTP1),
         };
@@ -130,6 +165,12 @@ impl TryFrom<crate::core::config::ConfigModule> for Server {
                 config_server.headers.as_ref().and_then(Headers::get_cors),
             ))
             .fuse(validate_spa_dir(config_server.get_spa_dir()))
+            .fuse(to_introspection_protection(&config_module))
+            .fuse(validate_contracts(
+                &config_server.contracts,
+                &config_server.get_routes(),
+            ))
+            .fuse(to_grpc_status_map(&config_server.grpc_status_mapping))
             .map(
                 |(
                     hostname,
@@ -139,6 +180,9 @@ impl TryFrom<crate::core::config::ConfigModule> for Server {
                     experimental_headers,
                     cors,
                     spa_dir,
+                    introspection_protection,
+                    contracts,
+                    grpc_status_map,
                 )| Server {
                     enable_apollo_tracing: (config_server).enable_apollo_tracing(),
                     enable_cache_control_header: (config_server).enable_cache_control(),
@@ -146,6 +190,7 @@ impl TryFrom<crate::core::config::ConfigModule> for Server {
                     enable_introspection: (config_server).enable_introspection(),
                     enable_query_validation: (config_server).enable_query_validation(),
                     enable_response_validation: (config_server).enable_http_validation(),
+                    enable_output_validation: (config_server).enable_output_validation(),
                     enable_batch_requests: (config_server).enable_batch_requests(),
                     enable_showcase: (config_server).enable_showcase(),
                     experimental_headers,
@@ -176,6 +221,9 @@ impl TryFrom<crate::core::config::ConfigModule> for Server {
                         50,
                     ),
                     spa_dir,
+                    introspection_protection,
+                    contracts,
+                    grpc_status_map,
                 },
             )
             .to_result()
@@ -278,6 +326,92 @@ fn validate_spa_dir(spa_dir: Option<&str>) -> Valid<Option<PathBuf>, BlueprintEr
     }
 }
 
+/// Protects introspection like a `@protected` field, with the providers and
+/// access expression of `@server(introspectionAccess:)`.
+fn to_introspection_protection(config_module: &ConfigModule) -> Valid<Option<IR>, BlueprintError> {
+    let Some(access) = &config_module.server.introspection_access else {
+        return Valid::succeed(None);
+    };
+
+    if !config_module.extensions().has_auth() {
+        return Valid::fail(BlueprintError::ProtectedOperatorNoAuthProviders)
+            .trace("introspectionAccess")
+            .trace("@server")
+            .trace("schema");
+    }
+
+    let providers: HashMap<_, _> = Provider::from_config(config_module)
+        .into_iter()
+        .filter_map(|provider| provider.id.clone().map(|id| (id, provider.content)))
+        .collect();
+
+    let auth = Valid::from_iter(access.id.iter().flatten(), |id| {
+        Valid::from_option(
+            providers.get(id).cloned().map(Auth::Provider),
+            BlueprintError::AuthProviderNotFound(id.clone()),
+        )
+    })
+    .map(|auth| {
+        // If no providers are listed, all of them must authorize the request
+        auth.into_iter()
+            .reduce(Auth::and)
+            .or_else(|| Auth::from_config(config_module))
+    });
+
+    let expr = match access.expr.as_deref().map(AccessExpr::parse).transpose() {
+        Ok(expr) => Valid::succeed(expr),
+        Err(e) => Valid::fail(BlueprintError::InvalidAccessExpression(e)),
+    };
+
+    auth.zip(expr)
+        .map(|(auth, expr)| {
+            auth.map(|auth| IR::Protect(auth, expr, Box::new(IR::Dynamic(DynamicValue::default()))))
+        })
+        .trace("introspectionAccess")
+        .trace("@server")
+        .trace("schema")
+}
+
+fn validate_contracts(
+    contracts: &[Contract],
+    routes: &Routes,
+) -> Valid<Vec<Contract>, BlueprintError> {
+    let mut seen = HashSet::new();
+    Valid::from_iter(contracts.iter(), |contract| {
+        if !contract.route.starts_with('/')
+            || contract.route.starts_with(API_URL_PREFIX)
+            || contract.route == *routes.status()
+            || !seen.insert(contract.route.as_str())
+        {
+            Valid::fail(BlueprintError::InvalidContractRoute(contract.route.clone()))
+        } else {
+            Valid::succeed(contract.clone())
+        }
+    })
+    .trace("contracts")
+    .trace("@server")
+    .trace("schema")
+}
+
+fn to_grpc_status_map(mappings: &[GrpcStatusMapping]) -> Valid<GrpcStatusMap, BlueprintError> {
+    Valid::from_iter(mappings.iter(), |mapping| {
+        match (
+            code_from_name(&mapping.code),
+            http::StatusCode::from_u16(mapping.status),
+        ) {
+            (Some(code), Ok(status)) => Valid::succeed((code, status)),
+            _ => Valid::fail(BlueprintError::InvalidGrpcStatusMapping(
+                mapping.code.clone(),
+                mapping.status,
+            )),
+        }
+    })
+    .map(|overrides| GrpcStatusMap::new(overrides.into_iter().collect()))
+    .trace("grpcStatusMapping")
+    .trace("@server")
+    .trace("schema")
+}
+
 fn handle_experimental_headers(
     headers: &BTreeSet<String>,
 ) -> Valid<HashSet<HeaderName>, BlueprintError> {
@@ -300,11 +434,67 @@ fn handle_experimental_headers(
 
 #[cfg(test)]
 mod tests {
-    use crate::core::config::ConfigModule;
+    #![expect(clippy::unwrap_used, reason = "test code")]
+    use crate::core::config::{self, ClientAuthMode, Config, ConfigModule};
 
     #[test]
     fn test_try_from_default() {
         let actual = super::Server::try_from(ConfigModule::default());
         assert!(actual.is_ok());
     }
+
+    #[test]
+    fn test_client_auth_requires_http2() {
+        let mut config = Config::default();
+        config.server.client_auth =
+            Some(config::ClientAuth { ca: "ca.pem".to_owned(), mode: ClientAuthMode::Require });
+
+        let actual = super::Server::try_from(ConfigModule::from(config));
+        assert!(actual.is_err());
+    }
+
+    #[test]
+    fn test_introspection_access_requires_auth_providers() {
+        let mut config = Config::default();
+        config.server.introspection_access = Some(config::Protected::default());
+
+        let actual = super::Server::try_from(ConfigModule::from(config));
+        assert!(actual.is_err());
+    }
+
+    #[test]
+    fn test_contract_routes() {
+        let contract =
+            |route: &str| config::Contract { route: route.to_owned(), exclude_tags: vec![] };
+
+        let mut config = Config::default();
+        config.server.contracts = vec![contract("/graphql"), contract("/internal/graphql")];
+        assert!(super::Server::try_from(ConfigModule::from(config.clone())).is_ok());
+
+        config.server.contracts = vec![contract("/internal"), contract("/internal")];
+        assert!(super::Server::try_from(ConfigModule::from(config.clone())).is_err());
+
+        config.server.contracts = vec![contract("/status")];
+        assert!(super::Server::try_from(ConfigModule::from(config)).is_err());
+    }
+
+    #[test]
+    fn test_grpc_status_mapping() {
+        let mapping =
+            |code: &str, status: u16| config::GrpcStatusMapping { code: code.to_owned(), status };
+
+        let mut config = Config::default();
+        config.server.grpc_status_mapping = vec![mapping("NOT_FOUND", 410)];
+        let server = super::Server::try_from(ConfigModule::from(config.clone())).unwrap();
+        assert_eq!(
+            server.grpc_status_map.http_status(5),
+            http::StatusCode::GONE
+        );
+
+        config.server.grpc_status_mapping = vec![mapping("MISSING", 404)];
+        assert!(super::Server::try_from(ConfigModule::from(config.clone())).is_err());
+
+        config.server.grpc_status_mapping = vec![mapping("NOT_FOUND", 1000)];
+        assert!(super::Server::try_from(ConfigModule::from(config)).is_err());
+    }
 }
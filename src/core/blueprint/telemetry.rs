@@ -5,7 +5,10 @@ use http::header::{HeaderMap, HeaderName, HeaderValue};
 use url::Url;
 
 use super::{BlueprintError, TryFoldConfig};
-use crate::core::config::{self, ConfigModule, KeyValue, PrometheusExporter, StdoutExporter};
+use crate::core::config::{
+    self, ConfigModule, KeyValue, PrometheusExporter, StdoutExporter, TelemetryMetrics,
+    TelemetrySampler, TelemetrySampling,
+};
 use crate::core::directive::DirectiveCodec;
 use crate::core::try_fold::TryFold;
 
@@ -26,6 +29,9 @@ pub enum TelemetryExporter {
 pub struct Telemetry {
     pub export: Option<TelemetryExporter>,
     pub request_headers: Vec<String>,
+    pub metrics: TelemetryMetrics,
+    pub sampling: TelemetrySampling,
+    pub baggage: Vec<String>,
 }
 
 fn to_url(url: &str) -> Valid<Url, BlueprintError> {
@@ -55,6 +61,36 @@ fn to_headers(headers: &[KeyValue]) -> Valid<HeaderMap, BlueprintError> {
     .trace("headers")
 }
 
+fn validate_sampler(sampler: &TelemetrySampler) -> Valid<(), BlueprintError> {
+    match sampler {
+        TelemetrySampler::Always | TelemetrySampler::Never => Valid::succeed(()),
+        TelemetrySampler::Ratio(ratio) => {
+            if (0.0..=1.0).contains(ratio) {
+                Valid::succeed(())
+            } else {
+                Valid::fail(BlueprintError::InvalidSamplingRatio(*ratio)).trace("ratio")
+            }
+        }
+        TelemetrySampler::ParentBased(root) => validate_sampler(root).trace("parentBased"),
+    }
+}
+
+fn to_sampling(sampling: &TelemetrySampling) -> Valid<TelemetrySampling, BlueprintError> {
+    let sampler = match &sampling.sampler {
+        Some(sampler) => validate_sampler(sampler).trace("sampler"),
+        None => Valid::succeed(()),
+    };
+    let operations = Valid::from_iter(sampling.operations.iter(), |operation| {
+        validate_sampler(&operation.sampler).trace(&operation.operation)
+    })
+    .trace("operations");
+
+    sampler
+        .zip(operations)
+        .map(|_| sampling.clone())
+        .trace("sampling")
+}
+
 #[must_use]
 pub fn to_opentelemetry<'a>() -> TryFold<'a, ConfigModule, Telemetry, BlueprintError> {
     TryFoldConfig::<Telemetry>::new(|config, up| {
@@ -73,9 +109,13 @@ pub fn to_opentelemetry<'a>() -> TryFold<'a, ConfigModule, Telemetry, BlueprintE
             };
 
             export
-                .map(|export| Telemetry {
+                .zip(to_sampling(&config.telemetry.sampling))
+                .map(|(export, sampling)| Telemetry {
                     export: Some(export),
                     request_headers: config.telemetry.request_headers.clone(),
+                    metrics: config.telemetry.metrics.clone(),
+                    sampling,
+                    baggage: config.telemetry.baggage.clone(),
                 })
                 .trace(config::Telemetry::trace_name().as_str())
         } else {
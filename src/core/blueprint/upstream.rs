@@ -5,6 +5,7 @@ use gqlforge_valid::{Valid, ValidationError, Validator};
 
 use super::BlueprintError;
 use crate::core::config::{self, Batch, ConfigModule};
+use crate::core::http::{IdentitySigner, OAuth2Client};
 
 #[derive(PartialEq, Eq, Clone, Debug, schemars::JsonSchema)]
 pub struct Proxy {
@@ -29,6 +30,13 @@ pub struct Upstream {
     pub http2_only: bool,
     pub on_request: Option<String>,
     pub verify_ssl: bool,
+    /// Token clients of the `OAuth2` links, each sending its tokens to its
+    /// own hosts.
+    #[schemars(skip)]
+    pub oauth2: Vec<OAuth2Client>,
+    /// Signs the internal identity tokens sent to upstreams.
+    #[schemars(skip)]
+    pub identity: Option<IdentitySigner>,
 }
 
 impl Upstream {
@@ -58,16 +66,13 @@ impl TryFrom<&ConfigModule> for Upstream {
     fn try_from(config_module: &ConfigModule) -> Result<Self, Self::Error> {
         let config_upstream = config_module.upstream.clone();
 
-        let mut allowed_headers = config_upstream.get_allowed_headers();
-
-        if config_module.extensions().has_auth() {
-            // force add auth specific headers to use it to make actual validation
-            allowed_headers.insert(http::header::AUTHORIZATION.to_string());
-        }
+        let allowed_headers = config_upstream.get_allowed_headers();
 
         get_batch(&config_upstream)
             .fuse(get_proxy(&config_upstream))
-            .map(|(batch, proxy)| Upstream {
+            .fuse(get_oauth2(config_module))
+            .fuse(get_identity(config_module))
+            .map(|(batch, proxy, oauth2, identity)| Upstream {
                 pool_idle_timeout: (config_upstream).get_pool_idle_timeout(),
                 pool_max_idle_per_host: (config_upstream).get_pool_max_idle_per_host(),
                 keep_alive_interval: (config_upstream).get_keep_alive_interval(),
@@ -84,6 +89,8 @@ impl TryFrom<&ConfigModule> for Upstream {
                 http2_only: (config_upstream).get_http_2_only(),
                 on_request: (config_upstream).get_on_request(),
                 verify_ssl: (config_upstream).get_verify_ssl(),
+                oauth2,
+                identity,
             })
             .to_result()
     }
@@ -102,6 +109,55 @@ fn get_batch(upstream: &config::Upstream) -> Valid<Option<Batch>, BlueprintError
     )
 }
 
+/// Creates the `OAuth2` token clients, making sure each host gets its
+/// tokens from a single link. The clients are shared by every runtime built
+/// from the blueprint.
+fn get_oauth2(config_module: &ConfigModule) -> Valid<Vec<OAuth2Client>, BlueprintError> {
+    let links = &config_module.extensions().oauth2;
+    let mut hosts = BTreeSet::new();
+
+    Valid::from_iter(
+        links.iter().flat_map(|link| link.content.hosts.iter()),
+        |host| {
+            if hosts.insert(host.to_lowercase()) {
+                Valid::succeed(())
+            } else {
+                Valid::fail(BlueprintError::OAuth2HostConflict(host.clone()))
+            }
+        },
+    )
+    .map(|_| {
+        links
+            .iter()
+            .map(|link| OAuth2Client::new(link.content.clone()))
+            .collect()
+    })
+    .trace("@link")
+}
+
+/// Loads the key that signs identity tokens from the `@link(type: Key)` the
+/// identity references.
+fn get_identity(config_module: &ConfigModule) -> Valid<Option<IdentitySigner>, BlueprintError> {
+    let Some(identity) = config_module.upstream.identity.clone() else {
+        return Valid::succeed(None);
+    };
+
+    if identity.hosts.is_empty() {
+        return Valid::fail(BlueprintError::IdentityHostsEmpty).trace("@upstream");
+    }
+
+    let Some(pem) = &config_module.extensions().identity_key else {
+        return Valid::fail(BlueprintError::IdentityKeyNotFound(identity.key)).trace("@upstream");
+    };
+
+    match IdentitySigner::try_new(identity, pem) {
+        Ok(signer) => Valid::succeed(Some(signer)),
+        Err(err) => {
+            Valid::fail(BlueprintError::InvalidIdentityKey(format!("{err:#}"))).trace("@upstream")
+        }
+    }
+}
+
 fn get_proxy(upstream: &config::Upstream) -> Valid<Option<Proxy>, BlueprintError> {
     if let Some(ref proxy) = upstream.proxy {
         Valid::succeed(Some(Proxy { url: proxy.url.clone() }))
@@ -2,7 +2,7 @@ use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::fmt::{self, Display};
 
 use anyhow::{Result, anyhow};
-use async_graphql::parser::types::ServiceDocument;
+use async_graphql::parser::types::{ExecutableDocument, ServiceDocument};
 use derive_setters::Setters;
 use gqlforge_typedefs_common::ServiceDocumentBuilder;
 use gqlforge_typedefs_common::directive_definition::DirectiveDefinition;
@@ -16,10 +16,10 @@ use strum::IntoEnumIterator;
 use super::directive::Directive;
 use super::from_document::from_document;
 use super::{
-    AddField, Alias, Cache, Call, Discriminate, Expr, GraphQL, Grpc, Http, JS, Link, Modify, Omit,
-    Protected, ResolverSet, Server, Telemetry, Upstream,
+    AddField, Alias, Cache, Call, Discriminate, Expr, GraphQL, Grpc, Http, JS, Link, Mask, Modify,
+    Omit, Protected, ResolverSet, Server, Telemetry, Upstream,
 };
-use crate::core::config::npo::QueryPath;
+use crate::core::config::npo::{OperationNPlusOne, QueryPath};
 use crate::core::config::source::Source;
 use crate::core::is_default;
 use crate::core::macros::MergeRight;
@@ -216,6 +216,10 @@ pub struct Field {
     /// Marks field as protected by auth provider
     pub protected: Option<Protected>,
 
+    ///
+    /// Masks the value of the field unless an access expression holds
+    pub mask: Option<Mask>,
+
     ///
     /// Used to overwrite the default discrimination strategy
     pub discriminate: Option<Discriminate>,
@@ -485,6 +489,13 @@ impl Config {
         super::npo::PathTracker::new(self).find()
     }
 
+    /// Like [`Config::n_plus_one`], but only reports the paths selected by the
+    /// operations in `document`.
+    #[must_use]
+    pub fn n_plus_one_in(&self, document: &ExecutableDocument) -> Vec<OperationNPlusOne> {
+        super::npo::OperationTracker::new(self, document).find()
+    }
+
     ///
     /// Given a starting type, this function searches for all the unique types
     /// that this type can be connected to via it's fields
@@ -713,6 +724,7 @@ impl Config {
             .add_directive(Omit::directive_definition(generated_types))
             .add_directive(Protected::directive_definition(generated_types))
             .add_directive(Discriminate::directive_definition(generated_types))
+            .add_directive(Mask::directive_definition(generated_types))
             .add_input(GraphQL::input_definition())
             .add_input(Grpc::input_definition())
             .add_input(Http::input_definition())
@@ -1,18 +1,21 @@
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::ops::Deref;
+use std::time::Duration;
 
 use gqlforge_valid::{Valid, Validator};
 use jsonwebtoken::jwk::JwkSet;
 use prost_reflect::prost_types::{FileDescriptorProto, FileDescriptorSet};
 use rustls_pki_types::{CertificateDer, PrivateKeyDer};
+use serde::Deserialize;
 
 use crate::core::Transform;
 use crate::core::config::Config;
 use crate::core::macros::MergeRight;
 use crate::core::merge_right::MergeRight;
 use crate::core::postgres::schema::DatabaseSchema;
-use crate::core::proto_reader::ProtoMetadata;
+use crate::core::proto_reader::{ProtoMetadata, ReflectionRefresh};
 use crate::core::rest::{EndpointSet, Unchecked};
+use crate::core::rpc::MethodSet;
 
 mod merge;
 
@@ -142,6 +145,9 @@ pub struct Extensions {
     /// Contains the file descriptor set resolved from the links to proto files
     pub grpc_file_descriptors: HashMap<String, FileDescriptorProto>,
 
+    /// Reflection links whose services are reflected again periodically
+    pub grpc_refresh: Vec<ReflectionRefresh>,
+
     /// Contains the contents of the JS file
     pub script: Option<String>,
 
@@ -151,12 +157,32 @@ pub struct Extensions {
     /// Contains the key used on HTTP2 with TLS
     pub keys: Vec<PrivateKey>,
 
+    /// PEM of the `@link(type: Key)` referenced by `@upstream(identity:)`,
+    /// which signs identity tokens instead of serving TLS
+    pub identity_key: Option<String>,
+
+    /// CA certificates that client certificates are verified against, read
+    /// from `@server(clientAuth:)`
+    pub client_ca: Vec<CertificateDer<'static>>,
+
     /// Contains the endpoints
     pub endpoint_set: EndpointSet<Unchecked>,
 
+    /// Contains the gRPC methods served from `@rpc` operations
+    pub method_set: MethodSet<Unchecked>,
+
     pub htpasswd: Vec<Content<String>>,
 
-    pub jwks: Vec<Content<JwkSet>>,
+    /// Keys of each `@link(type: Jwks)`. Every provider built from a link
+    /// shares its store, so a refresh is seen by all protected fields.
+    pub jwks: Vec<Content<JwksLink>>,
+
+    pub introspection: Vec<Content<IntrospectionLink>>,
+
+    pub api_keys: Vec<Content<ApiKeyLink>>,
+
+    /// Token endpoints of each `@link(type: OAuth2)`.
+    pub oauth2: Vec<Content<OAuth2Link>>,
 
     /// Raw SQL migration contents, applied in order to build a
     /// `DatabaseSchema`.
@@ -171,6 +197,257 @@ pub struct Extensions {
     pub s3_configs: Vec<S3LinkConfig>,
 }
 
+/// Keys read from a `@link(type: Jwks)` and where requests carry the token.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JwksLink {
+    pub keys: JwkSet,
+    /// Set for keys served over HTTP, which are refreshed at runtime.
+    pub refresh: Option<JwksRefresh>,
+    pub token_sources: Vec<TokenSource>,
+}
+
+/// Where a JWT provider reads the token from. The sources listed in the
+/// `tokenSource` field of the link meta are tried in order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TokenSource {
+    /// A request header. When `scheme` is set, the value must start with it,
+    /// as in `Authorization: Bearer <token>`; otherwise the whole value is
+    /// the token.
+    Header {
+        name: String,
+        scheme: Option<String>,
+    },
+    Cookie(String),
+    Query(String),
+}
+
+/// An entry of the `tokenSource` list, before checking that it names a
+/// single source.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TokenSourceMeta {
+    header: Option<String>,
+    scheme: Option<String>,
+    cookie: Option<String>,
+    query: Option<String>,
+}
+
+impl TokenSource {
+    /// Reads the sources from the meta of a `Jwks` link, defaulting to the
+    /// bearer token of the `Authorization` header.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an entry doesn't name exactly one header, cookie
+    /// or query parameter, or sets a `scheme` on a cookie or query parameter.
+    pub fn from_meta(meta: Option<&serde_json::Value>) -> anyhow::Result<Vec<Self>> {
+        let Some(sources) = meta.and_then(|m| m.get("tokenSource")) else {
+            return Ok(vec![Self::default()]);
+        };
+
+        serde_json::from_value::<Vec<TokenSourceMeta>>(sources.clone())?
+            .into_iter()
+            .map(|source| match source {
+                TokenSourceMeta { header: Some(name), scheme, cookie: None, query: None } => {
+                    Ok(Self::Header { name, scheme })
+                }
+                TokenSourceMeta { header: None, scheme: None, cookie: Some(name), query: None } => {
+                    Ok(Self::Cookie(name))
+                }
+                TokenSourceMeta { header: None, scheme: None, cookie: None, query: Some(name) } => {
+                    Ok(Self::Query(name))
+                }
+                _ => Err(anyhow::anyhow!(
+                    "A tokenSource entry must set exactly one of header, cookie or query, and scheme only with header"
+                )),
+            })
+            .collect()
+    }
+}
+
+impl Default for TokenSource {
+    fn default() -> Self {
+        Self::Header {
+            name: "Authorization".to_owned(),
+            scheme: Some("Bearer".to_owned()),
+        }
+    }
+}
+
+/// How keys linked from a remote JWKS endpoint are refreshed, read from the
+/// `@link` meta.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JwksRefresh {
+    pub url: String,
+    /// Fixed refresh interval (`refreshInterval`). When unset, the `max-age`
+    /// of the endpoint's `Cache-Control` header is used.
+    pub interval: Option<Duration>,
+    /// Minimum time between two refetches triggered by an unknown `kid`
+    /// (`minRefetchInterval`).
+    pub min_refetch_interval: Duration,
+    /// How long keys removed from the endpoint are still accepted
+    /// (`gracePeriod`).
+    pub grace_period: Duration,
+}
+
+impl JwksRefresh {
+    /// Used when neither `refreshInterval` nor `Cache-Control` is available.
+    pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(300);
+    const DEFAULT_MIN_REFETCH_INTERVAL: Duration = Duration::from_secs(30);
+    const DEFAULT_GRACE_PERIOD: Duration = Duration::from_secs(600);
+
+    /// Reads the refresh settings (in seconds) from the meta of a Jwks link.
+    #[must_use]
+    pub fn from_meta(url: String, meta: Option<&serde_json::Value>) -> Self {
+        let seconds = |key: &str| {
+            meta.and_then(|m| m.get(key))
+                .and_then(serde_json::Value::as_u64)
+                .map(Duration::from_secs)
+        };
+
+        Self {
+            url,
+            interval: seconds("refreshInterval"),
+            min_refetch_interval: seconds("minRefetchInterval")
+                .unwrap_or(Self::DEFAULT_MIN_REFETCH_INTERVAL),
+            grace_period: seconds("gracePeriod").unwrap_or(Self::DEFAULT_GRACE_PERIOD),
+        }
+    }
+}
+
+/// An OAuth2 token introspection endpoint read from a
+/// `@link(type: Introspection)`. Everything but the URL comes from the link
+/// meta.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct IntrospectionLink {
+    /// The link `src`.
+    #[serde(skip)]
+    pub url: String,
+    /// Credentials the gateway authenticates to the endpoint with.
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+    /// Accepted `aud` values; any audience is accepted when empty.
+    #[serde(default)]
+    pub audiences: BTreeSet<String>,
+    /// Scopes the token must have been granted.
+    #[serde(default)]
+    pub scopes: Vec<String>,
+}
+
+/// An OAuth2 token endpoint read from a `@link(type: OAuth2)`, whose tokens
+/// are sent to the listed upstream hosts. Everything but the URL comes from
+/// the link meta.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct OAuth2Link {
+    /// The link `src`.
+    #[serde(skip)]
+    pub url: String,
+    /// Credentials the gateway authenticates to the endpoint with.
+    pub client_id: String,
+    pub client_secret: Option<String>,
+    #[serde(default)]
+    pub grant_type: OAuth2GrantType,
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    pub audience: Option<String>,
+    /// Token exchanged for an upstream token with the `TOKEN_EXCHANGE`
+    /// grant, such as a workload identity token.
+    pub subject_token: Option<String>,
+    /// Defaults to `urn:ietf:params:oauth:token-type:access_token`.
+    pub subject_token_type: Option<String>,
+    /// Upstream hosts the token is sent to, as `host`, `host:port` or
+    /// `*.domain`.
+    pub hosts: Vec<String>,
+    /// Seconds before its expiry at which a token is replaced.
+    pub refresh_before: Option<u64>,
+}
+
+impl OAuth2Link {
+    const DEFAULT_REFRESH_BEFORE: Duration = Duration::from_secs(60);
+
+    #[must_use]
+    pub fn refresh_before(&self) -> Duration {
+        self.refresh_before
+            .map_or(Self::DEFAULT_REFRESH_BEFORE, Duration::from_secs)
+    }
+}
+
+/// How an `OAuth2` link obtains tokens.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OAuth2GrantType {
+    #[default]
+    ClientCredentials,
+    /// RFC 8693 token exchange of the link's `subjectToken`.
+    TokenExchange,
+}
+
+/// Keys read from a `@link(type: ApiKey)` and where requests carry them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ApiKeyLink {
+    pub source: ApiKeySource,
+    pub keys: Vec<ApiKeyEntry>,
+}
+
+/// Where the `ApiKey` provider reads the key from, set by the `header` or
+/// `query` field of the link meta.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ApiKeySource {
+    Header(String),
+    Query(String),
+}
+
+impl Default for ApiKeySource {
+    fn default() -> Self {
+        Self::Header("X-Api-Key".to_owned())
+    }
+}
+
+impl ApiKeySource {
+    /// Reads the source from the meta of an `ApiKey` link.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if both a header and a query parameter are set.
+    pub fn from_meta(meta: Option<&serde_json::Value>) -> anyhow::Result<Self> {
+        let field = |key: &str| {
+            meta.and_then(|m| m.get(key))
+                .and_then(serde_json::Value::as_str)
+                .map(ToOwned::to_owned)
+        };
+
+        match (field("header"), field("query")) {
+            (Some(_), Some(_)) => Err(anyhow::anyhow!(
+                "An ApiKey link can read the key from either a header or a query parameter, not both"
+            )),
+            (Some(header), None) => Ok(Self::Header(header)),
+            (None, Some(query)) => Ok(Self::Query(query)),
+            (None, None) => Ok(Self::default()),
+        }
+    }
+}
+
+/// The content of a `@link(type: ApiKey)` file.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ApiKeyFile {
+    pub keys: Vec<ApiKeyEntry>,
+}
+
+/// A single API key. Only its hash is stored.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ApiKeyEntry {
+    /// Hex-encoded SHA-256 digest of the key.
+    pub hash: String,
+    /// Attributes of the key, such as its tenant and scopes, exposed as
+    /// claims.
+    #[serde(default)]
+    pub claims: serde_json::Map<String, serde_json::Value>,
+}
+
 /// Configuration for an S3-compatible connection derived from a `@link`.
 #[derive(Clone, Debug)]
 pub struct S3LinkConfig {
@@ -199,7 +476,10 @@ impl Extensions {
 
     #[must_use]
     pub fn has_auth(&self) -> bool {
-        !self.htpasswd.is_empty() || !self.jwks.is_empty()
+        !self.htpasswd.is_empty()
+            || !self.jwks.is_empty()
+            || !self.introspection.is_empty()
+            || !self.api_keys.is_empty()
     }
 
     pub fn add_sql_migration(&mut self, content: String) {
@@ -304,4 +584,37 @@ mod tests {
 
         assert!(ext.find_database_schema(None).is_none());
     }
+
+    #[test]
+    fn token_sources_from_meta() {
+        assert_eq!(
+            TokenSource::from_meta(None).unwrap(),
+            vec![TokenSource::default()]
+        );
+
+        let meta = serde_json::json!({
+            "tokenSource": [
+                {"header": "X-Auth", "scheme": "Token"},
+                {"cookie": "session"},
+                {"query": "access_token"},
+            ]
+        });
+        assert_eq!(
+            TokenSource::from_meta(Some(&meta)).unwrap(),
+            vec![
+                TokenSource::Header { name: "X-Auth".to_owned(), scheme: Some("Token".to_owned()) },
+                TokenSource::Cookie("session".to_owned()),
+                TokenSource::Query("access_token".to_owned()),
+            ]
+        );
+
+        for invalid in [
+            serde_json::json!({"tokenSource": [{"header": "X-Auth", "cookie": "session"}]}),
+            serde_json::json!({"tokenSource": [{"cookie": "session", "scheme": "Bearer"}]}),
+            serde_json::json!({"tokenSource": [{}]}),
+            serde_json::json!({"tokenSource": [{"param": "token"}]}),
+        ] {
+            assert!(TokenSource::from_meta(Some(&invalid)).is_err());
+        }
+    }
 }
@@ -21,7 +21,7 @@ pub static FEDERATION_DIRECTIVES: &[&str] = &[
     "external",
     "provides",
     "requires",
-    "requiresScope",
+    "requiresScopes",
     "composeDirective",
     "interfaceObject",
     "listSize",
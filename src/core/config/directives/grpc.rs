@@ -74,4 +74,61 @@ pub struct Grpc {
     /// body before it's sent back to the client.
     #[serde(rename = "onResponseBody", default, skip_serializing_if = "is_default")]
     pub on_response_body: Option<String>,
+
+    /// Compresses the request messages sent to the gRPC API. Compressed
+    /// responses are accepted regardless of this setting.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub compression: Option<GrpcCompression>,
+
+    /// The protocol the gRPC API is called with. Defaults to `GRPC`.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub protocol: Option<GrpcProtocol>,
+}
+
+/// Message encodings supported for gRPC requests and responses.
+#[derive(
+    Serialize,
+    Deserialize,
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    strum_macros::Display,
+    schemars::JsonSchema,
+)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[strum(serialize_all = "lowercase")]
+pub enum GrpcCompression {
+    /// Compresses messages with gzip.
+    Gzip,
+}
+
+/// Wire protocols gRPC APIs can be called with.
+#[derive(
+    Serialize,
+    Deserialize,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    strum_macros::Display,
+    schemars::JsonSchema,
+)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum GrpcProtocol {
+    /// gRPC over HTTP/2.
+    #[default]
+    Grpc,
+    /// gRPC-Web, which sends the trailers at the end of the response body and
+    /// works over HTTP/1.1.
+    GrpcWeb,
+    /// The Connect protocol, which sends unary messages unframed and reports
+    /// errors with HTTP status codes and JSON bodies.
+    Connect,
 }
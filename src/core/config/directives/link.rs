@@ -65,6 +65,19 @@ pub enum LinkType {
     /// Points to an S3 or S3-compatible endpoint. The endpoint URL and
     /// region/credentials metadata are used by the `@s3` directive.
     S3,
+
+    /// Points to an OAuth2 token introspection endpoint (RFC 7662). Opaque
+    /// bearer tokens are posted to it to authenticate users.
+    Introspection,
+
+    /// Points to a file of hashed API keys. The imported keys will be used by
+    /// the server to authenticate machine clients.
+    ApiKey,
+
+    /// Points to an OAuth2 token endpoint. Tokens obtained from it with the
+    /// client credentials in the link meta are sent to the listed upstream
+    /// hosts.
+    OAuth2,
 }
 
 /// The @link directive allows you to import external resources, such as
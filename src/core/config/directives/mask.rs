@@ -0,0 +1,62 @@
+use gqlforge_macros::{DirectiveDefinition, MergeRight};
+use serde::{Deserialize, Serialize};
+
+use crate::core::is_default;
+
+/// Masks the value of a field, such as an email address or phone number,
+/// unless the request satisfies an access expression.
+///
+/// Example: `@mask(strategy: PARTIAL, unless: "scopes contains 'pii:read'")`
+/// returns `j***@example.com` to callers without the `pii:read` scope.
+#[derive(
+    Clone,
+    Debug,
+    Deserialize,
+    Serialize,
+    PartialEq,
+    Eq,
+    Default,
+    schemars::JsonSchema,
+    MergeRight,
+    DirectiveDefinition,
+)]
+#[directive_definition(locations = "FieldDefinition")]
+#[serde(deny_unknown_fields)]
+pub struct Mask {
+    /// How the value is masked. @default `PARTIAL`.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub strategy: MaskStrategy,
+    /// An access control expression evaluated against the request context.
+    /// The value is returned unmasked when it holds, and always masked when
+    /// omitted.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub unless: Option<String>,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Deserialize,
+    Serialize,
+    PartialEq,
+    Eq,
+    Default,
+    strum_macros::Display,
+    schemars::JsonSchema,
+    MergeRight,
+)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum MaskStrategy {
+    /// Keeps the first character of an email address and its domain, and the
+    /// last four characters of other values of eight or more characters:
+    /// `j***@example.com`, `*******4567`. Shorter values are masked entirely.
+    #[default]
+    Partial,
+    /// Replaces the value with the hex-encoded SHA-256 hash of it, so masked
+    /// values can still be compared.
+    Hash,
+    /// Replaces the value with `null`.
+    Null,
+}
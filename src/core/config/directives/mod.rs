@@ -10,6 +10,7 @@ mod grpc;
 mod http;
 mod js;
 mod link;
+mod mask;
 mod modify;
 mod omit;
 mod postgres;
@@ -31,6 +32,7 @@ pub use grpc::*;
 pub use http::*;
 pub use js::*;
 pub use link::*;
+pub use mask::*;
 pub use modify::*;
 pub use omit::*;
 pub use postgres::*;
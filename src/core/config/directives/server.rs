@@ -6,7 +6,7 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::core::config::headers::Headers;
-use crate::core::config::{KeyValue, merge_key_value_vecs};
+use crate::core::config::{KeyValue, Protected, merge_key_value_vecs};
 use crate::core::is_default;
 use crate::core::macros::MergeRight;
 
@@ -66,6 +66,13 @@ pub struct Server {
     /// and operations. @default `true`.
     pub introspection: Option<bool>,
 
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// `introspectionAccess` restricts introspection to callers authorized
+    /// the same way as `@protected`: by the listed auth providers, and the
+    /// access expression if one is given. Other callers receive a `FORBIDDEN`
+    /// error.
+    pub introspection_access: Option<Protected>,
+
     /// `enableFederation` enables functionality to Gqlforge server to act
     /// as a federation subgraph.
     #[serde(default, skip_serializing_if = "is_default")]
@@ -76,6 +83,12 @@ pub struct Server {
     /// pipeline.
     pub pipeline_flush: Option<bool>,
 
+    #[serde(default, skip_serializing_if = "is_default")]
+    #[merge_right(merge_right_fn = "merge_right_vars")]
+    /// `policies` names access expressions, so that fields can require them
+    /// with `@policy(policies: [["name"]])`.
+    pub policies: Vec<KeyValue>,
+
     #[serde(default, skip_serializing_if = "is_default")]
     /// `port` sets the Gqlforge running port. @default `8000`.
     pub port: Option<u16>,
@@ -91,6 +104,11 @@ pub struct Server {
     /// upstream services using inferred schema. @default `false`.
     pub response_validation: Option<bool>,
 
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// `outputValidation` validates the responses of `@http` fields against
+    /// the schema of their `output`. @default `false`.
+    pub output_validation: Option<bool>,
+
     #[serde(default, skip_serializing_if = "is_default")]
     /// A link to an external JS file that listens on every HTTP request
     /// response event.
@@ -149,6 +167,24 @@ pub struct Server {
     /// Requests without file extensions that don't match any known route
     /// will serve `index.html` for client-side routing.
     pub spa: Option<Spa>,
+
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// `clientAuth` enables mutual TLS on the HTTP/2 listener: client
+    /// certificates are verified against the given CA bundle, and exposed as
+    /// `tls` to `@protected(expr:)` and templates.
+    pub client_auth: Option<ClientAuth>,
+
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// `contracts` serve filtered views of the schema on their own routes,
+    /// built from the same configuration. A contract on the `graphQL` route
+    /// replaces the full schema there.
+    pub contracts: Vec<Contract>,
+
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// `grpcStatusMapping` overrides the HTTP status `@rest` endpoints respond
+    /// with when a gRPC call fails. By default gRPC codes are mapped like
+    /// `google.rpc.Code` describes, e.g. `NOT_FOUND` to 404.
+    pub grpc_status_mapping: Vec<GrpcStatusMapping>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, MergeRight, JsonSchema, Getters)]
@@ -194,6 +230,68 @@ pub struct Spa {
     pub dir: String,
 }
 
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, MergeRight, JsonSchema)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+/// A filtered view of the schema: fields and types tagged with
+/// `@tag(name:)` of one of the excluded tags are removed from both
+/// introspection and execution on the contract's route.
+pub struct Contract {
+    /// Route the contract's GraphQL endpoint is served on.
+    pub route: String,
+
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// Tags of the fields and types removed from the contract.
+    pub exclude_tags: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, MergeRight, JsonSchema)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+/// The HTTP status of `@rest` responses failing with a gRPC status code.
+pub struct GrpcStatusMapping {
+    /// Canonical name of the gRPC status code, e.g. `NOT_FOUND`.
+    pub code: String,
+
+    /// HTTP status the endpoint responds with.
+    pub status: u16,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, MergeRight, JsonSchema)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientAuth {
+    /// Path to a PEM bundle of the CA certificates client certificates are
+    /// verified against.
+    pub ca: String,
+
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// Whether clients must present a certificate. @default `REQUIRE`.
+    pub mode: ClientAuthMode,
+}
+
+#[derive(
+    Deserialize,
+    Serialize,
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    Copy,
+    Default,
+    schemars::JsonSchema,
+    MergeRight,
+)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ClientAuthMode {
+    /// Connections without a valid client certificate are rejected.
+    #[default]
+    Require,
+    /// Connections without a client certificate are accepted, but a
+    /// certificate that is presented must be valid.
+    Optional,
+}
+
 fn merge_right_vars(mut left: Vec<KeyValue>, right: Vec<KeyValue>) -> Vec<KeyValue> {
     left = merge_key_value_vecs(&left, &right);
     drop(right);
@@ -240,6 +338,10 @@ impl Server {
         self.response_validation.unwrap_or(false)
     }
     #[must_use]
+    pub fn enable_output_validation(&self) -> bool {
+        self.output_validation.unwrap_or(false)
+    }
+    #[must_use]
     pub fn enable_cache_control(&self) -> bool {
         self.headers
             .as_ref()
@@ -282,6 +384,14 @@ impl Server {
             .collect()
     }
 
+    #[must_use]
+    pub fn get_policies(&self) -> BTreeMap<String, String> {
+        self.policies
+            .iter()
+            .map(|kv| (kv.key.clone(), kv.value.clone()))
+            .collect()
+    }
+
     #[must_use]
     pub fn get_response_headers(&self) -> Vec<(String, String)> {
         self.headers
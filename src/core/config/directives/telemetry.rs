@@ -66,6 +66,105 @@ pub enum TelemetryExporter {
     Prometheus(PrometheusExporter),
 }
 
+/// Attributes of the upstream metrics. Every distinct set of attributes is a
+/// separate time series, so they can be left out or capped.
+#[derive(
+    Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, schemars::JsonSchema, MergeRight,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct TelemetryMetrics {
+    /// Label metrics with the parent type and name of the resolved field.
+    /// @default `true`.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub fields: Option<bool>,
+    /// Label metrics with the upstream host, or the id of the database
+    /// link. @default `true`.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub upstreams: Option<bool>,
+    /// Maximum number of attribute sets recorded for each metric. Fields,
+    /// upstreams, operation names and client names over the limit are
+    /// recorded as `other`. @default `1000`.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub max_cardinality: Option<usize>,
+}
+
+impl TelemetryMetrics {
+    /// Cap applied when `maxCardinality` isn't set, so client-sent operation
+    /// and client names can't grow the metrics without bound.
+    pub const DEFAULT_MAX_CARDINALITY: usize = 1000;
+
+    #[must_use]
+    pub fn fields(&self) -> bool {
+        self.fields.unwrap_or(true)
+    }
+
+    #[must_use]
+    pub fn upstreams(&self) -> bool {
+        self.upstreams.unwrap_or(true)
+    }
+
+    #[must_use]
+    pub fn max_cardinality(&self) -> usize {
+        self.max_cardinality
+            .unwrap_or(Self::DEFAULT_MAX_CARDINALITY)
+    }
+}
+
+/// Decides which traces are sampled, and so exported.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, schemars::JsonSchema, MergeRight)]
+#[serde(rename_all = "camelCase")]
+pub enum TelemetrySampler {
+    /// Samples every trace.
+    Always,
+    /// Samples no trace.
+    Never,
+    /// Samples this fraction of the traces, between `0` and `1`, based on
+    /// their trace id.
+    Ratio(f64),
+    /// Follows the decision of the parent span, like the one sent in the
+    /// `traceparent` header. Traces that start here use the given sampler.
+    ParentBased(Box<TelemetrySampler>),
+}
+
+// Ratios are checked to be between `0` and `1` when the blueprint is built,
+// so they are never `NaN`.
+impl Eq for TelemetrySampler {}
+
+/// Samples the operations of a name with their own sampler.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, schemars::JsonSchema, MergeRight)]
+#[serde(rename_all = "camelCase")]
+pub struct OperationSampler {
+    /// Name of the operation, as sent by clients in `operationName`.
+    pub operation: String,
+    pub sampler: TelemetrySampler,
+}
+
+/// Sampling of the exported traces.
+#[derive(
+    Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, schemars::JsonSchema, MergeRight,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct TelemetrySampling {
+    /// Sampler of the traces. @default `{ parentBased: "always" }`.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub sampler: Option<TelemetrySampler>,
+    /// Samplers of the named operations, used instead of `sampler` for the
+    /// spans of these operations.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub operations: Vec<OperationSampler>,
+    /// Export the spans that fail, even in traces that aren't sampled.
+    /// @default `false`.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub errors: Option<bool>,
+}
+
+impl TelemetrySampling {
+    #[must_use]
+    pub fn errors(&self) -> bool {
+        self.errors.unwrap_or(false)
+    }
+}
+
 #[derive(
     Debug,
     Default,
@@ -93,6 +192,17 @@ pub struct Telemetry {
     /// contain sensitive data
     #[serde(default, skip_serializing_if = "is_default")]
     pub request_headers: Vec<String>,
+    /// Attributes of the upstream request metrics, and limits on their
+    /// cardinality.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub metrics: TelemetryMetrics,
+    /// Which traces are sampled and exported.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub sampling: TelemetrySampling,
+    /// Keys of the W3C baggage entries of incoming requests that are sent
+    /// along to upstreams.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub baggage: Vec<String>,
 }
 
 impl Telemetry {
@@ -105,6 +215,9 @@ impl Telemetry {
             (Some(left), Some(right)) => Some(left.clone().merge_right(right.clone())),
         };
         self.request_headers.extend(other.request_headers);
+        self.metrics = self.metrics.merge_right(other.metrics);
+        self.sampling = self.sampling.merge_right(other.sampling);
+        self.baggage.extend(other.baggage);
 
         self
     }
@@ -147,6 +260,7 @@ mod tests {
                 headers: vec![KeyValue { key: "header_a".to_owned(), value: "a".to_owned() }],
             })),
             request_headers: vec!["Api-Key-A".to_owned()],
+            ..Default::default()
         };
         let exporter_otlp_2 = Telemetry {
             export: Some(TelemetryExporter::Otlp(OtlpExporter {
@@ -154,6 +268,12 @@ mod tests {
                 headers: vec![KeyValue { key: "header_b".to_owned(), value: "b".to_owned() }],
             })),
             request_headers: vec!["Api-Key-B".to_owned()],
+            metrics: TelemetryMetrics { max_cardinality: Some(100), ..Default::default() },
+            sampling: TelemetrySampling {
+                sampler: Some(TelemetrySampler::Ratio(0.5)),
+                ..Default::default()
+            },
+            baggage: vec!["tenant".to_owned()],
         };
         let exporter_prometheus_1 = Telemetry {
             export: Some(TelemetryExporter::Prometheus(PrometheusExporter {
@@ -202,7 +322,13 @@ mod tests {
                     url: "test-url-2".to_owned(),
                     headers: vec![KeyValue { key: "header_b".to_owned(), value: "b".to_owned() }]
                 })),
-                request_headers: vec!["Api-Key-A".to_string(), "Api-Key-B".to_string(),]
+                request_headers: vec!["Api-Key-A".to_string(), "Api-Key-B".to_string(),],
+                metrics: TelemetryMetrics { max_cardinality: Some(100), ..Default::default() },
+                sampling: TelemetrySampling {
+                    sampler: Some(TelemetrySampler::Ratio(0.5)),
+                    ..Default::default()
+                },
+                baggage: vec!["tenant".to_owned()],
             }
         );
 
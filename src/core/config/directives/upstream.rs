@@ -34,6 +34,93 @@ pub struct Proxy {
     pub url: String,
 }
 
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug, schemars::JsonSchema, MergeRight)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub struct Identity {
+    /// Id of the `@link(type: Key)` holding the private key the tokens are
+    /// signed with.
+    pub key: String,
+
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// Signing algorithm, which must match the key. @default `RS256`.
+    pub algorithm: IdentityAlgorithm,
+
+    /// Hosts the tokens are sent to, as `host`, `host:port` or `*.domain`.
+    pub hosts: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// Verified claims copied into the tokens. `sub` is always copied.
+    pub claims: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// Value of the `iss` claim.
+    pub issuer: Option<String>,
+
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// Value of the `aud` claim.
+    pub audience: Option<String>,
+
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// Lifetime of the tokens in seconds. @default `60`.
+    pub ttl: Option<u64>,
+
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// Header the tokens are sent in. @default `X-Gqlforge-Identity`.
+    pub header: Option<String>,
+
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// Path the public signing key is served on as a JWKS. @default
+    /// `/.well-known/jwks.json`.
+    pub jwks_path: Option<String>,
+}
+
+impl Identity {
+    #[must_use]
+    pub fn get_ttl(&self) -> u64 {
+        self.ttl.unwrap_or(60)
+    }
+
+    #[must_use]
+    pub fn get_header(&self) -> String {
+        self.header
+            .clone()
+            .unwrap_or("X-Gqlforge-Identity".to_string())
+    }
+
+    #[must_use]
+    pub fn get_jwks_path(&self) -> String {
+        self.jwks_path
+            .clone()
+            .unwrap_or("/.well-known/jwks.json".to_string())
+    }
+}
+
+#[derive(
+    Serialize,
+    Deserialize,
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    Copy,
+    Default,
+    schemars::JsonSchema,
+    MergeRight,
+)]
+pub enum IdentityAlgorithm {
+    #[default]
+    RS256,
+    RS384,
+    RS512,
+    PS256,
+    PS384,
+    PS512,
+    ES256,
+    ES384,
+    EdDSA,
+}
+
 #[derive(
     Serialize,
     Deserialize,
@@ -147,6 +234,12 @@ pub struct Upstream {
     /// It is highly recommended to keep this enabled (`true`) in
     /// production.
     pub verify_ssl: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// `identity` mints a short-lived JWT for every request, carrying the
+    /// operation name and selected claims of the verified token, and sends it
+    /// to the given hosts, so they needn't trust the client's own token.
+    pub identity: Option<Identity>,
 }
 
 impl Upstream {
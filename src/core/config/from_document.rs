@@ -12,7 +12,7 @@ use gqlforge_valid::{Valid, ValidationError, Validator};
 use indexmap::IndexMap;
 
 use super::directive::{Directive, to_directive};
-use super::{Alias, Discriminate, FEDERATION_DIRECTIVES, Resolver, RuntimeConfig, Telemetry};
+use super::{Alias, Discriminate, FEDERATION_DIRECTIVES, Mask, Resolver, RuntimeConfig, Telemetry};
 use crate::core::config::{
     self, Cache, Config, Enum, Link, Modify, Omit, Protected, RootSchema, Server, Union, Upstream,
     Variant,
@@ -324,6 +324,7 @@ where
         .fuse(Omit::from_directives(directives.iter()))
         .fuse(Modify::from_directives(directives.iter()))
         .fuse(Protected::from_directives(directives.iter()))
+        .fuse(Mask::from_directives(directives.iter()))
         .fuse(Discriminate::from_directives(directives.iter()))
         .fuse(default_value)
         .fuse(to_federation_directives(directives))
@@ -334,6 +335,7 @@ where
                 omit,
                 modify,
                 protected,
+                mask,
                 discriminate,
                 default_value,
                 directives,
@@ -345,6 +347,7 @@ where
                 omit,
                 cache,
                 protected,
+                mask,
                 discriminate,
                 default_value,
                 resolvers,
@@ -0,0 +1,122 @@
+mod rules;
+
+use std::collections::BTreeMap;
+
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+
+use crate::core::config::Config;
+
+/// Severity of a lint diagnostic. `Off` disables a rule entirely.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LintSeverity {
+    Error,
+    Warning,
+    Info,
+    Off,
+}
+
+/// Lint settings, read from the `lint` section of `.gqlforgerc.json`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct LintConfig {
+    /// Overrides the severity of a rule by id; `off` disables it.
+    #[serde(default)]
+    pub rules: BTreeMap<String, LintSeverity>,
+    /// Schema coordinates (glob patterns such as `Query.legacy*`) for which a
+    /// rule is suppressed, keyed by rule id.
+    #[serde(default)]
+    pub ignore: BTreeMap<String, Vec<String>>,
+}
+
+/// A single finding reported by a lint rule.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    pub rule: &'static str,
+    pub severity: LintSeverity,
+    /// Schema coordinate the finding refers to, e.g. `Query.users`.
+    pub path: String,
+    pub message: String,
+}
+
+/// A location and message reported by a rule, before the configured
+/// severity and suppressions are applied.
+pub struct Finding {
+    pub path: String,
+    pub message: String,
+}
+
+impl Finding {
+    fn new(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self { path: path.into(), message: message.into() }
+    }
+}
+
+/// A style or safety check over a [Config].
+pub trait LintRule: Send + Sync {
+    /// Stable, kebab-case identifier used in `.gqlforgerc.json`.
+    fn id(&self) -> &'static str;
+    fn description(&self) -> &'static str;
+    fn default_severity(&self) -> LintSeverity;
+    fn check(&self, config: &Config) -> Vec<Finding>;
+}
+
+/// All built-in lint rules.
+#[must_use]
+pub fn lint_rules() -> Vec<Box<dyn LintRule>> {
+    rules::all()
+}
+
+impl Config {
+    /// Runs every enabled lint rule and returns the diagnostics ordered by
+    /// severity and path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `lint_config` refers to an unknown rule or holds
+    /// an invalid ignore pattern.
+    pub fn lint(&self, lint_config: &LintConfig) -> Result<Vec<Diagnostic>> {
+        let rules = lint_rules();
+        for id in lint_config.rules.keys().chain(lint_config.ignore.keys()) {
+            if !rules.iter().any(|rule| rule.id() == id) {
+                return Err(anyhow!("Unknown lint rule: {id}"));
+            }
+        }
+
+        let mut diagnostics = vec![];
+        for rule in &rules {
+            let severity = lint_config
+                .rules
+                .get(rule.id())
+                .copied()
+                .unwrap_or_else(|| rule.default_severity());
+            if severity == LintSeverity::Off {
+                continue;
+            }
+
+            let ignored = lint_config
+                .ignore
+                .get(rule.id())
+                .into_iter()
+                .flatten()
+                .map(|pattern| glob::Pattern::new(pattern))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            diagnostics.extend(
+                rule.check(self)
+                    .into_iter()
+                    .filter(|finding| !ignored.iter().any(|p| p.matches(&finding.path)))
+                    .map(|finding| Diagnostic {
+                        rule: rule.id(),
+                        severity,
+                        path: finding.path,
+                        message: finding.message,
+                    }),
+            );
+        }
+
+        diagnostics.sort_by(|a, b| (a.severity, &a.path).cmp(&(b.severity, &b.path)));
+        Ok(diagnostics)
+    }
+}
@@ -0,0 +1,399 @@
+use super::{Finding, LintRule, LintSeverity};
+use crate::core::config::{Config, Field, LinkType, PostgresOperation, Resolver, Type};
+
+pub(super) fn all() -> Vec<Box<dyn LintRule>> {
+    vec![
+        Box::new(HttpUpstreamTimeout),
+        Box::new(PostgresSelectLimit),
+        Box::new(UnprotectedMutation),
+        Box::new(CacheOnProtected),
+        Box::new(NamingConvention),
+    ]
+}
+
+/// Iterates over every field of every type as `(type_name, type, field_name,
+/// field)`.
+fn fields(config: &Config) -> impl Iterator<Item = (&str, &Type, &str, &Field)> {
+    config.types.iter().flat_map(|(type_name, type_of)| {
+        type_of
+            .fields
+            .iter()
+            .map(move |(name, field)| (type_name.as_str(), type_of, name.as_str(), field))
+    })
+}
+
+/// `@http` fields that rely on the implicit upstream timeout.
+struct HttpUpstreamTimeout;
+
+impl LintRule for HttpUpstreamTimeout {
+    fn id(&self) -> &'static str {
+        "http-upstream-timeout"
+    }
+
+    fn description(&self) -> &'static str {
+        "@http fields should run against an @upstream with an explicit timeout"
+    }
+
+    fn default_severity(&self) -> LintSeverity {
+        LintSeverity::Warning
+    }
+
+    fn check(&self, config: &Config) -> Vec<Finding> {
+        if config.upstream.timeout.is_some() {
+            return vec![];
+        }
+
+        fields(config)
+            .filter(|(_, _, _, field)| {
+                field
+                    .resolvers
+                    .iter()
+                    .any(|resolver| matches!(resolver, Resolver::Http(_)))
+            })
+            .map(|(type_name, _, name, _)| {
+                Finding::new(
+                    format!("{type_name}.{name}"),
+                    format!(
+                        "`{type_name}.{name}` uses @http but @upstream sets no `timeout`; the default of {}s applies",
+                        config.upstream.get_timeout()
+                    ),
+                )
+            })
+            .collect()
+    }
+}
+
+/// `@postgres` selects that can return an unbounded number of rows.
+struct PostgresSelectLimit;
+
+impl LintRule for PostgresSelectLimit {
+    fn id(&self) -> &'static str {
+        "postgres-select-limit"
+    }
+
+    fn description(&self) -> &'static str {
+        "@postgres(operation: SELECT) should set a `limit`"
+    }
+
+    fn default_severity(&self) -> LintSeverity {
+        LintSeverity::Warning
+    }
+
+    fn check(&self, config: &Config) -> Vec<Finding> {
+        fields(config)
+            .filter(|(_, _, _, field)| {
+                field.resolvers.iter().any(|resolver| {
+                    matches!(
+                        resolver,
+                        Resolver::Postgres(pg)
+                            if pg.operation == PostgresOperation::Select
+                                && pg.limit.is_none()
+                                && pg.batch_key.is_empty()
+                    )
+                })
+            })
+            .map(|(type_name, _, name, _)| {
+                Finding::new(
+                    format!("{type_name}.{name}"),
+                    format!("`{type_name}.{name}` selects rows without a `limit`"),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Mutations left open while the schema has an auth provider.
+struct UnprotectedMutation;
+
+impl LintRule for UnprotectedMutation {
+    fn id(&self) -> &'static str {
+        "unprotected-mutation"
+    }
+
+    fn description(&self) -> &'static str {
+        "Mutations should be @protected when an auth provider is configured"
+    }
+
+    fn default_severity(&self) -> LintSeverity {
+        LintSeverity::Warning
+    }
+
+    fn check(&self, config: &Config) -> Vec<Finding> {
+        let has_auth = config.links.iter().any(|link| {
+            matches!(
+                link.type_of,
+                LinkType::Htpasswd | LinkType::Jwks | LinkType::Introspection | LinkType::ApiKey
+            )
+        });
+        let Some(mutation) = config.schema.mutation.as_ref() else {
+            return vec![];
+        };
+        let Some(mutation_type) = config.types.get(mutation) else {
+            return vec![];
+        };
+        if !has_auth || mutation_type.protected.is_some() {
+            return vec![];
+        }
+
+        mutation_type
+            .fields
+            .iter()
+            .filter(|(_, field)| field.protected.is_none())
+            .map(|(name, _)| {
+                Finding::new(
+                    format!("{mutation}.{name}"),
+                    format!("Mutation `{mutation}.{name}` is not @protected"),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Cached responses are shared between callers, so protected data would leak.
+struct CacheOnProtected;
+
+impl LintRule for CacheOnProtected {
+    fn id(&self) -> &'static str {
+        "cache-on-protected"
+    }
+
+    fn description(&self) -> &'static str {
+        "@cache must not be combined with @protected"
+    }
+
+    fn default_severity(&self) -> LintSeverity {
+        LintSeverity::Warning
+    }
+
+    fn check(&self, config: &Config) -> Vec<Finding> {
+        fields(config)
+            .filter(|(_, type_of, _, field)| {
+                let protected = field.protected.is_some() || type_of.protected.is_some();
+                let cached = field.cache.is_some() || type_of.cache.is_some();
+                protected && cached
+            })
+            .map(|(type_name, _, name, _)| {
+                Finding::new(
+                    format!("{type_name}.{name}"),
+                    format!(
+                        "`{type_name}.{name}` is both cached and protected; cached values are shared across users"
+                    ),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Types in `PascalCase`, fields and arguments in `camelCase`, enum values in
+/// `SCREAMING_SNAKE_CASE`.
+struct NamingConvention;
+
+impl NamingConvention {
+    fn is_pascal_case(name: &str) -> bool {
+        name.starts_with(|c: char| c.is_ascii_uppercase())
+            && name.chars().all(|c| c.is_ascii_alphanumeric())
+    }
+
+    fn is_camel_case(name: &str) -> bool {
+        // Leading underscores mark internal fields such as `_entities`.
+        let name = name.trim_start_matches('_');
+        name.starts_with(|c: char| c.is_ascii_lowercase())
+            && name.chars().all(|c| c.is_ascii_alphanumeric())
+    }
+
+    fn is_screaming_snake_case(name: &str) -> bool {
+        name.starts_with(|c: char| c.is_ascii_uppercase())
+            && name
+                .chars()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+    }
+}
+
+impl LintRule for NamingConvention {
+    fn id(&self) -> &'static str {
+        "naming-convention"
+    }
+
+    fn description(&self) -> &'static str {
+        "Types use PascalCase, fields and arguments camelCase, enum values SCREAMING_SNAKE_CASE"
+    }
+
+    fn default_severity(&self) -> LintSeverity {
+        LintSeverity::Warning
+    }
+
+    fn check(&self, config: &Config) -> Vec<Finding> {
+        let mut findings = vec![];
+
+        let type_names = config
+            .types
+            .keys()
+            .chain(config.enums.keys())
+            .chain(config.unions.keys());
+        for name in type_names {
+            if !Self::is_pascal_case(name) {
+                findings.push(Finding::new(
+                    name,
+                    format!("Type `{name}` should be PascalCase"),
+                ));
+            }
+        }
+
+        for (type_name, _, name, field) in fields(config) {
+            if !Self::is_camel_case(name) {
+                findings.push(Finding::new(
+                    format!("{type_name}.{name}"),
+                    format!("Field `{type_name}.{name}` should be camelCase"),
+                ));
+            }
+            for arg in field.args.keys().filter(|arg| !Self::is_camel_case(arg)) {
+                findings.push(Finding::new(
+                    format!("{type_name}.{name}({arg}:)"),
+                    format!("Argument `{arg}` of `{type_name}.{name}` should be camelCase"),
+                ));
+            }
+        }
+
+        for (enum_name, enum_) in &config.enums {
+            for variant in &enum_.variants {
+                if !Self::is_screaming_snake_case(&variant.name) {
+                    findings.push(Finding::new(
+                        format!("{enum_name}.{}", variant.name),
+                        format!(
+                            "Enum value `{enum_name}.{}` should be SCREAMING_SNAKE_CASE",
+                            variant.name
+                        ),
+                    ));
+                }
+            }
+        }
+
+        findings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![expect(clippy::unwrap_used, reason = "test code")]
+    use std::collections::BTreeMap;
+
+    use super::super::{LintConfig, LintSeverity};
+    use crate::core::config::Config;
+
+    fn lint(sdl: &str, lint_config: &LintConfig) -> Vec<(&'static str, LintSeverity, String)> {
+        Config::from_sdl(sdl)
+            .to_result()
+            .unwrap()
+            .lint(lint_config)
+            .unwrap()
+            .into_iter()
+            .map(|d| (d.rule, d.severity, d.path))
+            .collect()
+    }
+
+    #[test]
+    fn reports_rule_violations() {
+        let sdl = r#"
+            schema
+              @server
+              @upstream
+              @link(type: Htpasswd, src: ".htpasswd") {
+              query: Query
+              mutation: Mutation
+            }
+
+            type Query {
+              users: [User] @postgres(table: "users")
+              me: User @http(url: "http://localhost/me") @protected @cache(maxAge: 100)
+              old_name: String @expr(body: "x")
+            }
+
+            type Mutation {
+              createUser(name: String): User @http(url: "http://localhost/users", method: POST)
+            }
+
+            type User {
+              id: Int
+            }
+        "#;
+
+        assert_eq!(
+            lint(sdl, &LintConfig::default()),
+            vec![
+                (
+                    "http-upstream-timeout",
+                    LintSeverity::Warning,
+                    "Mutation.createUser".into()
+                ),
+                (
+                    "unprotected-mutation",
+                    LintSeverity::Warning,
+                    "Mutation.createUser".into()
+                ),
+                (
+                    "http-upstream-timeout",
+                    LintSeverity::Warning,
+                    "Query.me".into()
+                ),
+                (
+                    "cache-on-protected",
+                    LintSeverity::Warning,
+                    "Query.me".into()
+                ),
+                (
+                    "naming-convention",
+                    LintSeverity::Warning,
+                    "Query.old_name".into()
+                ),
+                (
+                    "postgres-select-limit",
+                    LintSeverity::Warning,
+                    "Query.users".into()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn applies_configured_severities_and_ignores() {
+        let sdl = r#"
+            schema @server @upstream {
+              query: Query
+            }
+
+            type Query {
+              legacy_users: [String] @postgres(table: "users")
+              all_posts: [String] @postgres(table: "posts")
+            }
+        "#;
+        let lint_config = LintConfig {
+            rules: BTreeMap::from([
+                ("naming-convention".to_string(), LintSeverity::Off),
+                ("postgres-select-limit".to_string(), LintSeverity::Error),
+            ]),
+            ignore: BTreeMap::from([(
+                "postgres-select-limit".to_string(),
+                vec!["Query.legacy*".to_string()],
+            )]),
+        };
+
+        assert_eq!(
+            lint(sdl, &lint_config),
+            vec![(
+                "postgres-select-limit",
+                LintSeverity::Error,
+                "Query.all_posts".into()
+            )]
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_rules() {
+        let lint_config = LintConfig {
+            rules: BTreeMap::from([("no-such-rule".to_string(), LintSeverity::Off)]),
+            ..Default::default()
+        };
+        let error = Config::default().lint(&lint_config).unwrap_err();
+        assert_eq!(error.to_string(), "Unknown lint rule: no-such-rule");
+    }
+}
@@ -3,7 +3,8 @@ pub use config_module::*;
 pub use directive::Directive;
 pub use directives::*;
 pub use key_values::*;
-pub use npo::QueryPath;
+pub use lint::{Diagnostic, Finding, LintConfig, LintRule, LintSeverity, lint_rules};
+pub use npo::{OperationNPlusOne, QueryPath};
 pub use reader_context::*;
 pub use resolver::*;
 pub use source::*;
@@ -18,6 +19,7 @@ pub mod group_by;
 pub mod headers;
 mod into_document;
 mod key_values;
+mod lint;
 mod npo;
 pub mod reader;
 pub mod reader_context;
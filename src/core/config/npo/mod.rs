@@ -1,3 +1,5 @@
+mod operation;
 mod tracker;
 
+pub use operation::{OperationNPlusOne, OperationTracker};
 pub use tracker::{PathTracker, QueryPath};
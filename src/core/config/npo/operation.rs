@@ -0,0 +1,342 @@
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+
+use async_graphql::parser::types::{
+    ExecutableDocument, OperationType, Selection, SelectionSet, VariableDefinition,
+};
+use async_graphql::{Name, Positioned};
+use async_graphql_value::{ConstValue, Value};
+
+use super::QueryPath;
+use crate::core::config::{Config, Field, Resolver};
+use crate::core::http::Method;
+
+/// Number of items assumed for a list when the operation doesn't bound it
+/// with a size argument such as `first` or `limit`.
+const DEFAULT_LIST_SIZE: u64 = 10;
+
+const SIZE_ARGUMENTS: &[&str] = &["first", "last", "limit", "take", "pageSize"];
+
+/// A N + 1 query path that a recorded operation actually hits.
+#[derive(Debug, PartialEq)]
+pub struct OperationNPlusOne {
+    /// Name of the operation, `anonymous` when it has none.
+    pub operation: String,
+    pub path: QueryPath,
+    /// Estimated number of upstream calls issued for the last field of the
+    /// path in a single execution.
+    pub fan_out: u64,
+    /// Change that turns the calls into a single batched `DataLoader` call.
+    pub suggestion: String,
+}
+
+impl Display for OperationNPlusOne {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {} (~{} upstream calls)\n  fix: {}",
+            self.operation, self.path, self.fan_out, self.suggestion
+        )
+    }
+}
+
+/// Finds the N + 1 paths selected by the operations of a document, following
+/// the same rules as [super::PathTracker].
+pub struct OperationTracker<'a> {
+    config: &'a Config,
+    document: &'a ExecutableDocument,
+}
+
+impl<'a> OperationTracker<'a> {
+    pub fn new(config: &'a Config, document: &'a ExecutableDocument) -> Self {
+        Self { config, document }
+    }
+
+    pub fn find(self) -> Vec<OperationNPlusOne> {
+        let mut found = vec![];
+
+        for (name, operation) in self.document.operations.iter() {
+            let root = match operation.node.ty {
+                OperationType::Query => self.config.schema.query.as_deref(),
+                OperationType::Mutation => self.config.schema.mutation.as_deref(),
+                OperationType::Subscription => self.config.schema.subscription.as_deref(),
+            };
+            let Some(root) = root else {
+                continue;
+            };
+
+            let mut walker = Walker {
+                config: self.config,
+                document: self.document,
+                variables: &operation.node.variable_definitions,
+                operation: name.map_or_else(|| "anonymous".to_string(), ToString::to_string),
+                path: vec![],
+                fragments: HashSet::new(),
+                found: vec![],
+            };
+            walker.walk(root, &operation.node.selection_set.node, None);
+            found.extend(walker.found);
+        }
+
+        found
+    }
+}
+
+struct Walker<'a> {
+    config: &'a Config,
+    document: &'a ExecutableDocument,
+    variables: &'a [Positioned<VariableDefinition>],
+    operation: String,
+    path: Vec<String>,
+    /// Fragments currently being expanded, to stop on cycles.
+    fragments: HashSet<&'a str>,
+    found: Vec<OperationNPlusOne>,
+}
+
+impl<'a> Walker<'a> {
+    /// Walks a selection set of `type_name`. `fan_out` is the number of parent
+    /// items the selection is resolved for, `None` outside of any list.
+    fn walk(&mut self, type_name: &str, selection_set: &'a SelectionSet, fan_out: Option<u64>) {
+        let config = self.config;
+        for selection in &selection_set.items {
+            match &selection.node {
+                Selection::Field(field) => {
+                    let field = &field.node;
+                    let name = field.name.node.as_str();
+                    let Some(config_field) = config
+                        .find_type(type_name)
+                        .and_then(|type_of| type_of.fields.get(name))
+                    else {
+                        continue;
+                    };
+
+                    self.path.push(name.to_string());
+                    if let Some(fan_out) = fan_out
+                        && config_field.has_resolver()
+                        && !config_field.has_batched_resolver()
+                    {
+                        self.record(type_name, name, config_field, fan_out);
+                    } else {
+                        let fan_out = if config_field.type_of.is_list() {
+                            let size = self.list_size(&field.arguments);
+                            Some(fan_out.unwrap_or(1).saturating_mul(size))
+                        } else {
+                            fan_out
+                        };
+                        self.walk(
+                            config_field.type_of.name(),
+                            &field.selection_set.node,
+                            fan_out,
+                        );
+                    }
+                    self.path.pop();
+                }
+                Selection::FragmentSpread(spread) => {
+                    let name = spread.node.fragment_name.node.as_str();
+                    let Some(fragment) = self.document.fragments.get(name) else {
+                        continue;
+                    };
+                    if self.fragments.insert(name) {
+                        let type_condition = fragment.node.type_condition.node.on.node.as_str();
+                        self.walk(type_condition, &fragment.node.selection_set.node, fan_out);
+                        self.fragments.remove(name);
+                    }
+                }
+                Selection::InlineFragment(fragment) => {
+                    let type_condition = fragment
+                        .node
+                        .type_condition
+                        .as_ref()
+                        .map_or(type_name, |cond| cond.node.on.node.as_str());
+                    self.walk(type_condition, &fragment.node.selection_set.node, fan_out);
+                }
+            }
+        }
+    }
+
+    fn record(&mut self, type_name: &str, name: &str, field: &Field, fan_out: u64) {
+        let path = self.path.clone();
+        if self
+            .found
+            .iter()
+            .any(|found| found.path == QueryPath::from(path.clone()))
+        {
+            return;
+        }
+
+        self.found.push(OperationNPlusOne {
+            operation: self.operation.clone(),
+            path: QueryPath::from(path),
+            fan_out,
+            suggestion: suggest_fix(self.config, &format!("{type_name}.{name}"), field),
+        });
+    }
+
+    /// Size of a list field: the literal (or variable default) value of its
+    /// size argument if there is one, [DEFAULT_LIST_SIZE] otherwise.
+    fn list_size(&self, arguments: &[(Positioned<Name>, Positioned<Value>)]) -> u64 {
+        arguments
+            .iter()
+            .filter(|(name, _)| SIZE_ARGUMENTS.contains(&name.node.as_str()))
+            .find_map(|(_, value)| match &value.node {
+                Value::Number(number) => number.as_u64(),
+                Value::Variable(variable) => self
+                    .variables
+                    .iter()
+                    .find(|def| def.node.name.node == *variable)
+                    .and_then(|def| match &def.node.default_value.as_ref()?.node {
+                        ConstValue::Number(number) => number.as_u64(),
+                        _ => None,
+                    }),
+                _ => None,
+            })
+            .unwrap_or(DEFAULT_LIST_SIZE)
+    }
+}
+
+/// Describes the `batchKey` / `@upstream(batch)` change that would batch the
+/// calls made by `field`.
+fn suggest_fix(config: &Config, coordinate: &str, field: &Field) -> String {
+    let enable_batching = if config.upstream.batch.is_none() {
+        " and enable request batching with `@upstream(batch: {delay: 10})`"
+    } else {
+        ""
+    };
+
+    match field.resolvers.first() {
+        Some(Resolver::Http(http)) if http.method != Method::GET => format!(
+            "`{coordinate}` uses @http with {}; only GET requests can be batched, expose a GET endpoint that accepts multiple ids and add `batchKey`",
+            http.method
+        ),
+        Some(Resolver::Http(http)) => {
+            if let Some(query) = http
+                .query
+                .iter()
+                .find(|query| value_ref(&query.value).is_some())
+            {
+                format!(
+                    "add `batchKey: [\"{}\"]` to @http on `{coordinate}` so the calls are merged on the `{}` query parameter{enable_batching}",
+                    query.key, query.key
+                )
+            } else if let Some(key) = value_ref(&http.url) {
+                let url = http
+                    .url
+                    .replace(&format!("/{{{{.value.{key}}}}}"), "")
+                    .replace(&format!("{{{{.value.{key}}}}}"), "");
+                format!(
+                    "move `{{{{.value.{key}}}}}` out of the path of `{coordinate}`: @http(url: \"{url}\", query: [{{key: \"id\", value: \"{{{{.value.{key}}}}}\"}}], batchKey: [\"id\"]){enable_batching}"
+                )
+            } else {
+                format!("add a `batchKey` to @http on `{coordinate}`{enable_batching}")
+            }
+        }
+        Some(Resolver::Grpc(_)) => format!(
+            "call a batched RPC from @grpc on `{coordinate}` and add `batchKey: [\"id\"]`{enable_batching}"
+        ),
+        Some(Resolver::Graphql(_)) => {
+            format!("set `batch: true` on @graphql for `{coordinate}`{enable_batching}")
+        }
+        Some(Resolver::Postgres(pg)) => {
+            let column = pg.filter.as_ref().and_then(|filter| {
+                filter.as_object()?.iter().find_map(|(column, value)| {
+                    value.as_str().and_then(value_ref).map(|_| column.clone())
+                })
+            });
+            let column = column.unwrap_or_else(|| "id".to_string());
+            format!("add `batchKey: [\"{column}\"]` to @postgres on `{coordinate}`")
+        }
+        _ => format!(
+            "resolve `{coordinate}` with a resolver that supports `batchKey` (@http, @grpc, @graphql or @postgres)"
+        ),
+    }
+}
+
+/// Returns `X` for the first `{{.value.X}}` reference in a template.
+fn value_ref(template: &str) -> Option<&str> {
+    let start = template.find("{{.value.")? + "{{.value.".len();
+    let len = template[start..].find("}}")?;
+    Some(template[start..start + len].trim())
+}
+
+#[cfg(test)]
+mod tests {
+    #![expect(clippy::unwrap_used, reason = "test code")]
+    use super::*;
+
+    const CONFIG: &str = r#"
+        schema @server @upstream {
+          query: Query
+        }
+
+        type Query {
+          posts(limit: Int): [Post] @http(url: "http://localhost/posts")
+          users: [User] @http(url: "http://localhost/users")
+        }
+
+        type Post {
+          id: Int
+          userId: Int
+          user: User @http(url: "http://localhost/users/{{.value.userId}}")
+          comments: [Comment] @http(url: "http://localhost/comments", query: [{key: "postId", value: "{{.value.id}}"}])
+        }
+
+        type User {
+          id: Int
+          posts: [Post] @http(url: "http://localhost/posts", query: [{key: "userId", value: "{{.value.id}}"}], batchKey: ["userId"])
+        }
+
+        type Comment {
+          id: Int
+        }
+    "#;
+
+    fn find(operations: &str) -> Vec<OperationNPlusOne> {
+        let config = Config::from_sdl(CONFIG).to_result().unwrap();
+        let document = async_graphql::parser::parse_query(operations).unwrap();
+        config.n_plus_one_in(&document)
+    }
+
+    #[test]
+    fn reports_only_selected_paths() {
+        let found = find("query Feed { posts(limit: 20) { user { id } } }");
+
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].operation, "Feed");
+        assert_eq!(found[0].path.to_string(), "query { posts { user } }");
+        assert_eq!(found[0].fan_out, 20);
+        assert_eq!(
+            found[0].suggestion,
+            "move `{{.value.userId}}` out of the path of `Post.user`: @http(url: \"http://localhost/users\", query: [{key: \"id\", value: \"{{.value.userId}}\"}], batchKey: [\"id\"]) and enable request batching with `@upstream(batch: {delay: 10})`"
+        );
+    }
+
+    #[test]
+    fn follows_fragments_and_skips_batched_fields() {
+        let found = find(
+            r"
+            query {
+              users { posts { ...PostFields } }
+            }
+            fragment PostFields on Post { id comments { id } }
+            ",
+        );
+
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].operation, "anonymous");
+        assert_eq!(
+            found[0].path.to_string(),
+            "query { users { posts { comments } } }"
+        );
+        assert_eq!(found[0].fan_out, 100);
+        assert!(
+            found[0]
+                .suggestion
+                .starts_with("add `batchKey: [\"postId\"]`")
+        );
+    }
+
+    #[test]
+    fn ignores_unselected_paths() {
+        assert!(find("{ posts { id } }").is_empty());
+    }
+}
@@ -17,6 +17,12 @@ impl QueryPath {
     }
 }
 
+impl From<Vec<String>> for QueryPath {
+    fn from(path: Vec<String>) -> Self {
+        QueryPath(vec![path])
+    }
+}
+
 impl<'a> From<Chunk<Chunk<Name<'a>>>> for QueryPath {
     fn from(chunk: Chunk<Chunk<Name<'a>>>) -> Self {
         QueryPath(
@@ -2,15 +2,20 @@ use std::path::Path;
 
 use futures_util::future::join_all;
 use gqlforge_valid::{Valid, ValidationError, Validator};
+use indexmap::IndexMap;
 use rustls_pemfile;
 use rustls_pki_types::{CertificateDer, PrivateKeyDer};
 use url::Url;
 
-use super::{ConfigModule, Content, Link, LinkType, PrivateKey};
+use super::{
+    ApiKeyFile, ApiKeyLink, ApiKeySource, ConfigModule, Content, IntrospectionLink, JwksLink,
+    JwksRefresh, Link, LinkType, OAuth2GrantType, OAuth2Link, PrivateKey, TokenSource,
+};
 use crate::core::config::{Config, ConfigReaderContext, Source};
-use crate::core::proto_reader::ProtoReader;
+use crate::core::proto_reader::{ProtoReader, ReflectionRefresh};
 use crate::core::resource_reader::{Cached, Resource, ResourceReader};
 use crate::core::rest::EndpointSet;
+use crate::core::rpc::MethodSet;
 use crate::core::runtime::TargetRuntime;
 use crate::core::variance::Invariant;
 
@@ -60,7 +65,14 @@ impl ConfigReader {
         }
 
         let mut extensions = config_module.extensions().clone();
+        let identity_key = config_module
+            .upstream
+            .identity
+            .as_ref()
+            .map(|identity| identity.key.clone());
         let mut config_module = Valid::succeed(config_module);
+        let mut sql_migrations: IndexMap<Option<String>, Vec<String>> = IndexMap::new();
+        let mut postgres_urls: IndexMap<Option<String>, String> = IndexMap::new();
 
         for link in &links {
             let path = Self::resolve_path(&link.src, parent_dir);
@@ -101,13 +113,18 @@ impl ConfigReader {
                 LinkType::Key => {
                     let source = self.resource_reader.read_file(path).await?;
                     let content = source.content;
-                    extensions.keys = Self::load_private_key(&content);
+                    if link.id.is_some() && link.id == identity_key {
+                        extensions.identity_key = Some(content);
+                    } else {
+                        extensions.keys = Self::load_private_key(&content);
+                    }
                 }
                 LinkType::Operation => {
                     let source = self.resource_reader.read_file(path).await?;
                     let content = source.content;
 
                     extensions.endpoint_set = EndpointSet::try_new(&content)?;
+                    extensions.method_set = MethodSet::try_new(&content)?;
                 }
                 LinkType::Htpasswd => {
                     let source = self.resource_reader.read_file(path).await?;
@@ -123,37 +140,118 @@ impl ConfigReader {
 
                     let de = &mut serde_json::Deserializer::from_str(&content);
 
+                    // Keys served over HTTP can be rotated by the identity
+                    // provider, so they are refreshed at runtime.
+                    let refresh = Url::parse(&link.src)
+                        .ok()
+                        .filter(|url| matches!(url.scheme(), "http" | "https"))
+                        .map(|url| JwksRefresh::from_meta(url.to_string(), link.meta.as_ref()));
+
                     extensions.jwks.push(Content {
                         id: link.id.clone(),
-                        content: serde_path_to_error::deserialize(de)?,
+                        content: JwksLink {
+                            keys: serde_path_to_error::deserialize(de)?,
+                            refresh,
+                            token_sources: TokenSource::from_meta(link.meta.as_ref())?,
+                        },
+                    });
+                }
+                LinkType::Introspection => {
+                    let meta = link
+                        .meta
+                        .clone()
+                        .unwrap_or_else(|| serde_json::Value::Object(serde_json::Map::new()));
+
+                    extensions.introspection.push(Content {
+                        id: link.id.clone(),
+                        content: IntrospectionLink {
+                            url: link.src.clone(),
+                            ..serde_path_to_error::deserialize(meta)?
+                        },
                     });
                 }
+                LinkType::ApiKey => {
+                    let source = self.resource_reader.read_file(path).await?;
+                    let de = &mut serde_json::Deserializer::from_str(&source.content);
+                    let mut file: ApiKeyFile = serde_path_to_error::deserialize(de)?;
+
+                    for key in &mut file.keys {
+                        if key.hash.len() != 64 || !key.hash.chars().all(|c| c.is_ascii_hexdigit())
+                        {
+                            return Err(anyhow::anyhow!(
+                                "Invalid API key hash in {}: expected a hex-encoded SHA-256 digest",
+                                source.path
+                            ));
+                        }
+                        key.hash.make_ascii_lowercase();
+                    }
+
+                    extensions.api_keys.push(Content {
+                        id: link.id.clone(),
+                        content: ApiKeyLink {
+                            source: ApiKeySource::from_meta(link.meta.as_ref())?,
+                            keys: file.keys,
+                        },
+                    });
+                }
+                LinkType::OAuth2 => {
+                    let meta = link
+                        .meta
+                        .clone()
+                        .unwrap_or_else(|| serde_json::Value::Object(serde_json::Map::new()));
+                    let link_meta = OAuth2Link {
+                        url: link.src.clone(),
+                        ..serde_path_to_error::deserialize(meta)?
+                    };
+
+                    if link_meta.hosts.is_empty() {
+                        return Err(anyhow::anyhow!(
+                            "An OAuth2 link must list the upstream hosts its tokens are sent to"
+                        ));
+                    }
+                    if link_meta.grant_type == OAuth2GrantType::TokenExchange
+                        && link_meta.subject_token.is_none()
+                    {
+                        return Err(anyhow::anyhow!(
+                            "An OAuth2 link with the TOKEN_EXCHANGE grant requires a subjectToken"
+                        ));
+                    }
+
+                    extensions
+                        .oauth2
+                        .push(Content { id: link.id.clone(), content: link_meta });
+                }
                 LinkType::Grpc => {
                     let meta = self
                         .proto_reader
                         .fetch(link.src.as_str(), link.headers.clone())
                         .await?;
 
+                    extensions.grpc_refresh.extend(ReflectionRefresh::from_meta(
+                        link.src.clone(),
+                        link.headers.clone(),
+                        link.meta.as_ref(),
+                        &meta,
+                    ));
+
                     for m in meta {
                         extensions.add_proto(m);
                     }
                 }
                 LinkType::Sql => {
+                    // Migrations sharing a link id are parsed together (in
+                    // link order) once every link has been read, so later
+                    // files can alter tables created by earlier ones.
                     let source = self.resource_reader.read_file(path).await?;
                     extensions.add_sql_migration(source.content.clone());
-                    let schema =
-                        crate::core::postgres::sql_parser::parse_migrations(&[source.content])?;
                     let id = link.id.clone().or_else(|| Some("default".to_string()));
-                    extensions.add_database_schema(id, schema);
+                    sql_migrations.entry(id).or_default().push(source.content);
                 }
                 LinkType::Postgres => {
-                    // Online introspection: connect to the database,
-                    // introspect its schema, and attach the result to
-                    // the config extensions.
-                    let db_schema =
-                        crate::core::postgres::introspector::introspect(&link.src).await?;
+                    // Introspected once every link has been read, unless
+                    // migrations describe the database.
                     let id = link.id.clone().or_else(|| Some("default".to_string()));
-                    extensions.add_database_schema(id, db_schema);
+                    postgres_urls.insert(id, link.src.clone());
                 }
                 LinkType::S3 => {
                     // Extract region and forcePathStyle from link meta.
@@ -179,6 +277,21 @@ impl ConfigReader {
             }
         }
 
+        // Online introspection: connect to the databases that no `Sql` link
+        // with the same id describes, and attach their schema to the config
+        // extensions.
+        for (id, url) in postgres_urls {
+            if !sql_migrations.contains_key(&id) {
+                let schema = crate::core::postgres::introspector::introspect(&url).await?;
+                extensions.add_database_schema(id, schema);
+            }
+        }
+
+        for (id, migrations) in sql_migrations {
+            let schema = crate::core::postgres::sql_parser::parse_migrations(&migrations)?;
+            extensions.add_database_schema(id, schema);
+        }
+
         Ok(config_module
             .map(|config_module| config_module.set_extensions(extensions))
             .to_result()?)
@@ -292,7 +405,29 @@ impl ConfigReader {
         config.telemetry.render_mustache(&reader_ctx)?;
 
         // Create initial config set & extend it with the links
-        self.ext_links(ConfigModule::from(config), parent_dir).await
+        let config_module = self
+            .ext_links(ConfigModule::from(config), parent_dir)
+            .await?;
+        self.ext_client_ca(config_module, parent_dir).await
+    }
+
+    /// Reads the CA bundle of `@server(clientAuth:)`
+    async fn ext_client_ca(
+        &self,
+        config_module: ConfigModule,
+        parent_dir: Option<&Path>,
+    ) -> anyhow::Result<ConfigModule> {
+        let Some(client_auth) = config_module.server.client_auth.as_ref() else {
+            return Ok(config_module);
+        };
+
+        let path = Self::resolve_path(&client_auth.ca, parent_dir);
+        let source = self.resource_reader.read_file(path).await?;
+
+        let mut extensions = config_module.extensions().clone();
+        extensions.client_ca = Self::load_cert(&source.content)?;
+
+        Ok(config_module.set_extensions(extensions))
     }
 
     /// Checks if path is a URL or absolute path, returns directly if so.
@@ -324,7 +459,7 @@ mod reader_tests {
     use pretty_assertions::assert_eq;
 
     use crate::core::config::reader::ConfigReader;
-    use crate::core::config::{Config, Type};
+    use crate::core::config::{Config, Link, LinkType, Type};
 
     fn start_mock_server() -> httpmock::MockServer {
         httpmock::MockServer::start()
@@ -413,6 +548,44 @@ mod reader_tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_sql_migrations_describe_postgres_link() {
+        let runtime = crate::core::runtime::test::init(&None);
+        let dir = tempfile::tempdir().unwrap();
+        let migration = dir.path().join("001_init.sql");
+        std::fs::write(
+            &migration,
+            "CREATE TABLE users (id SERIAL PRIMARY KEY, name TEXT NOT NULL);",
+        )
+        .unwrap();
+
+        // The database isn't reachable, so it can't have been introspected
+        let config = Config {
+            links: vec![
+                Link {
+                    src: migration.to_string_lossy().to_string(),
+                    type_of: LinkType::Sql,
+                    ..Default::default()
+                },
+                Link {
+                    src: "postgres://localhost:1/app".to_string(),
+                    type_of: LinkType::Postgres,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let config_module = ConfigReader::init(runtime)
+            .resolve(config, None)
+            .await
+            .unwrap();
+
+        let schemas = &config_module.extensions().database_schemas;
+        assert_eq!(schemas.len(), 1);
+        assert_eq!(schemas[0].id.as_deref(), Some("default"));
+        assert!(schemas[0].content.find_table("users").is_some());
+    }
+
     #[test]
     fn test_relative_path() {
         let path_dir = Path::new("abc/xyz");
@@ -3,16 +3,27 @@ use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, Mutex, PoisonError};
+use std::sync::{Arc, LazyLock, Mutex, PoisonError};
 use std::time::Duration;
 
 use futures_channel::oneshot;
+use opentelemetry::KeyValue;
+use opentelemetry::metrics::Histogram;
 
 pub use super::cache::NoCache;
 pub use super::factory::CacheFactory;
 pub use super::loader::Loader;
 pub use super::storage::CacheStorage;
 
+static DATA_LOADER_BATCH_SIZE: LazyLock<Histogram<u64>> = LazyLock::new(|| {
+    let meter = opentelemetry::global::meter("data_loader");
+
+    meter
+        .u64_histogram("data_loader.batch.size")
+        .with_description("Number of keys loaded in a single batch")
+        .build()
+});
+
 /// Data loader.
 ///
 /// Reference: <https://github.com/facebook/dataloader>
@@ -25,6 +36,7 @@ pub struct DataLoader<
     delay: Duration,
     max_batch_size: usize,
     disable_cache: AtomicBool,
+    name: Option<&'static str>,
 }
 
 impl<K, T> DataLoader<K, T, NoCache>
@@ -42,6 +54,7 @@ where
             delay: Duration::from_millis(1),
             max_batch_size: 1000,
             disable_cache: false.into(),
+            name: None,
         }
     }
 }
@@ -62,6 +75,7 @@ where
             delay: Duration::from_millis(1),
             max_batch_size: 1000,
             disable_cache: false.into(),
+            name: None,
         }
     }
 
@@ -81,6 +95,13 @@ where
         Self { max_batch_size, ..self }
     }
 
+    /// Name the batch sizes of the loader are recorded with, in the
+    /// `data_loader.batch.size` metric. Unnamed loaders aren't recorded.
+    #[must_use]
+    pub fn name(self, name: &'static str) -> Self {
+        Self { name: Some(name), ..self }
+    }
+
     /// Get the loader.
     #[inline]
     pub fn loader(&self) -> &T {
@@ -198,13 +219,15 @@ where
             Action::ImmediateLoad(keys) => {
                 let inner = self.inner.clone();
                 let disable_cache = self.disable_cache.load(Ordering::SeqCst);
-                let task = async move { inner.do_load(disable_cache, keys).await };
+                let name = self.name;
+                let task = async move { inner.do_load(disable_cache, name, keys).await };
 
                 tokio::spawn(Box::pin(task));
             }
             Action::StartFetch => {
                 let inner = self.inner.clone();
                 let disable_cache = self.disable_cache.load(Ordering::SeqCst);
+                let name = self.name;
                 let delay = self.delay;
 
                 let task = async move {
@@ -219,7 +242,7 @@ where
                     };
 
                     if !keys.0.is_empty() {
-                        inner.do_load(disable_cache, keys).await;
+                        inner.do_load(disable_cache, name, keys).await;
                     }
                 };
                 tokio::spawn(Box::pin(task));
@@ -370,12 +393,22 @@ where
     T: Loader<K>,
     C: CacheFactory<K, T::Value>,
 {
-    async fn do_load(&self, disable_cache: bool, (keys, senders): KeysAndSender<K, T>)
-    where
+    async fn do_load(
+        &self,
+        disable_cache: bool,
+        name: Option<&'static str>,
+        (keys, senders): KeysAndSender<K, T>,
+    ) where
         K: Send + Sync + Hash + Eq + Clone + 'static,
         T: Loader<K>,
     {
         let keys = keys.into_iter().collect::<Vec<_>>();
+        if let Some(name) = name {
+            DATA_LOADER_BATCH_SIZE.record(
+                u64::try_from(keys.len()).unwrap_or(u64::MAX),
+                &[KeyValue::new("data_loader", name)],
+            );
+        }
 
         match self.loader.load(&keys).await {
             Ok(values) => {
@@ -1,10 +1,21 @@
 use std::collections::HashMap;
 use std::hash::Hash;
-use std::sync::{Arc, Mutex, PoisonError, Weak};
+use std::sync::{Arc, LazyLock, Mutex, PoisonError, Weak};
 
 use futures_util::Future;
+use opentelemetry::KeyValue;
+use opentelemetry::metrics::Counter;
 use tokio::sync::broadcast;
 
+static DEDUPE_HITS: LazyLock<Counter<u64>> = LazyLock::new(|| {
+    let meter = opentelemetry::global::meter("dedupe");
+
+    meter
+        .u64_counter("dedupe.hits")
+        .with_description("Number of operations served by an identical one")
+        .build()
+});
+
 pub trait Key: Send + Sync + Eq + Hash + Clone {}
 impl<A: Send + Sync + Eq + Hash + Clone> Key for A {}
 
@@ -20,6 +31,8 @@ pub struct Dedupe<Key, Value> {
     size: usize,
     /// When enabled allows the operations to be cached forever.
     persist: bool,
+    /// Name the hits are recorded with, in the `dedupe.hits` metric.
+    name: Option<&'static str>,
 }
 
 /// Represents the current state of the operation.
@@ -48,7 +61,12 @@ enum Step<Value> {
 
 impl<K: Key, V: Value> Dedupe<K, V> {
     pub fn new(size: usize, persist: bool) -> Self {
-        Self { cache: Arc::new(Mutex::new(HashMap::new())), size, persist }
+        Self {
+            cache: Arc::new(Mutex::new(HashMap::new())),
+            size,
+            persist,
+            name: None,
+        }
     }
 
     pub async fn dedupe<'a, Fn, Fut>(&'a self, key: &'a K, or_else: Fn) -> V
@@ -58,9 +76,15 @@ impl<K: Key, V: Value> Dedupe<K, V> {
     {
         loop {
             let value = match self.step(key) {
-                Step::Return(value) => value,
+                Step::Return(value) => {
+                    self.record_hit();
+                    value
+                }
                 Step::Await(mut rx) => match rx.recv().await {
-                    Ok(value) => value,
+                    Ok(value) => {
+                        self.record_hit();
+                        value
+                    }
                     Err(_) => {
                         // If we get an error that means the task with
                         // owned tx (sender) was dropped.i.e. there is no result in cache
@@ -86,6 +110,12 @@ impl<K: Key, V: Value> Dedupe<K, V> {
         }
     }
 
+    fn record_hit(&self) {
+        if let Some(name) = self.name {
+            DEDUPE_HITS.add(1, &[KeyValue::new("dedupe", name)]);
+        }
+    }
+
     fn step(&self, key: &K) -> Step<V> {
         let mut this = self.cache.lock().unwrap_or_else(PoisonError::into_inner);
 
@@ -121,6 +151,14 @@ impl<K: Key, V: Value, E: Value> DedupeResult<K, V, E> {
     pub fn new(persist: bool) -> Self {
         Self(Dedupe::new(1, persist))
     }
+
+    /// Records the hits of the deduplication as `name`, in the `dedupe.hits`
+    /// metric.
+    #[must_use]
+    pub fn name(mut self, name: &'static str) -> Self {
+        self.0.name = Some(name);
+        self
+    }
 }
 
 impl<K: Key, V: Value, E: Value> DedupeResult<K, V, E> {
@@ -1,12 +1,111 @@
 use convert_case::{Case, Casing};
 use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 
 use crate::core::Type;
 use crate::core::config::{
-    Arg, Config, Field, Link, LinkType, Postgres, PostgresOperation, Resolver, Type as ConfigType,
+    Arg, Config, Field, Postgres, PostgresOperation, Resolver, Type as ConfigType,
 };
-use crate::core::postgres::schema::{Column, DatabaseSchema, PgType, PrimaryKey, Table};
+use crate::core::postgres::schema::{
+    Column, DatabaseSchema, ForeignKey, PgType, PrimaryKey, Table,
+};
+
+/// Naming convention applied to generated field and argument names.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum NamingConvention {
+    /// `usersById`, `createdAt` (default).
+    #[default]
+    CamelCase,
+    /// `users_by_id`, `created_at`.
+    SnakeCase,
+}
+
+impl NamingConvention {
+    fn field_name(self, name: &str) -> String {
+        match self {
+            NamingConvention::CamelCase => name.to_case(Case::Camel),
+            NamingConvention::SnakeCase => name.to_case(Case::Snake),
+        }
+    }
+}
+
+/// A CRUD operation that can be generated for a table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CrudOperation {
+    /// `INSERT` via `create{Type}`.
+    Create,
+    /// `SELECT_ONE`/`SELECT` via `{table}ById` and `{table}List`.
+    Read,
+    /// `UPDATE` via `update{Type}`.
+    Update,
+    /// `DELETE` via `delete{Type}`.
+    Delete,
+}
+
+/// Options controlling which tables and operations `from_database` generates.
+#[derive(Debug, Clone)]
+pub struct DatabaseOptions {
+    /// Glob patterns of tables to include. Matched against both `name` and
+    /// `schema.name`. Empty means every table.
+    pub tables: Vec<String>,
+    /// Glob patterns of tables to exclude. Applied after `tables`.
+    pub exclude: Vec<String>,
+    pub naming: NamingConvention,
+    pub operations: Vec<CrudOperation>,
+}
+
+impl Default for DatabaseOptions {
+    fn default() -> Self {
+        Self {
+            tables: Vec::new(),
+            exclude: Vec::new(),
+            naming: NamingConvention::default(),
+            operations: vec![
+                CrudOperation::Create,
+                CrudOperation::Read,
+                CrudOperation::Update,
+                CrudOperation::Delete,
+            ],
+        }
+    }
+}
+
+impl DatabaseOptions {
+    fn generates(&self, operation: CrudOperation) -> bool {
+        self.operations.contains(&operation)
+    }
+
+    /// Returns a copy of `schema` containing only the selected tables.
+    fn select_tables(&self, schema: &DatabaseSchema) -> anyhow::Result<DatabaseSchema> {
+        let include = compile_patterns(&self.tables)?;
+        let exclude = compile_patterns(&self.exclude)?;
+        let matches = |patterns: &[glob::Pattern], table: &Table| {
+            patterns
+                .iter()
+                .any(|p| p.matches(&table.name) || p.matches(&table.qualified_name()))
+        };
+
+        let mut selected = DatabaseSchema::new();
+        for table in schema.tables.values() {
+            if (include.is_empty() || matches(&include, table)) && !matches(&exclude, table) {
+                selected.add_table(table.clone());
+            }
+        }
+        Ok(selected)
+    }
+}
+
+fn compile_patterns(patterns: &[String]) -> anyhow::Result<Vec<glob::Pattern>> {
+    patterns
+        .iter()
+        .map(|p| {
+            glob::Pattern::new(p).map_err(|e| anyhow::anyhow!("Invalid table pattern '{p}': {e}"))
+        })
+        .collect()
+}
 
 /// Generate a GraphQL `Config` from a `DatabaseSchema`.
 ///
@@ -16,17 +115,21 @@ use crate::core::postgres::schema::{Column, DatabaseSchema, PgType, PrimaryKey,
 /// - Mutation: `createTableName`, `updateTableName`, `deleteTableName`
 /// - FK relationships -> nested object fields with `@postgres(batchKey: ...)`
 ///
+/// Only the tables and operations selected by `options` are generated, and
+/// relationships pointing at tables that were filtered out are skipped. The
+/// caller is responsible for adding the `@link` that provides the schema.
+///
 /// # Errors
 ///
-/// Returns an error if the operation fails.
+/// Returns an error if a table pattern in `options` is invalid.
 #[expect(
     clippy::too_many_lines,
     reason = "generates config for all database tables and columns"
 )]
-pub fn from_database(schema: &DatabaseSchema, connection_url: &str) -> anyhow::Result<Config> {
+pub fn from_database(schema: &DatabaseSchema, options: &DatabaseOptions) -> anyhow::Result<Config> {
+    let schema = &options.select_tables(schema)?;
+    let naming = options.naming;
     let mut config = Config::default();
-    config.schema.query = Some("Query".to_string());
-    config.schema.mutation = Some("Mutation".to_string());
 
     let mut query_type = ConfigType::default();
     let mut mutation_type = ConfigType::default();
@@ -39,28 +142,26 @@ pub fn from_database(schema: &DatabaseSchema, connection_url: &str) -> anyhow::R
         for col in &table.columns {
             let gql_type = column_to_graphql_type(col);
             output_type.fields.insert(
-                col.name.to_case(Case::Camel),
+                naming.field_name(&col.name),
                 Field::default().type_of(gql_type),
             );
         }
 
         // --- FK relationship fields ---
-        for fk in &table.foreign_keys {
+        for fk in table
+            .foreign_keys
+            .iter()
+            .filter(|fk| references_table(schema, fk))
+        {
             let ref_type_name = table_to_type_name(&fk.referenced_table);
-            let base_name = fk.referenced_table.to_case(Case::Camel);
+            let base_name = naming.field_name(&fk.referenced_table);
             let field_name = if output_type.fields.contains_key(&base_name) {
-                let suffix = fk
-                    .columns
-                    .iter()
-                    .map(|c| c.to_case(Case::Camel))
-                    .collect::<Vec<_>>()
-                    .join("And");
-                format!("{base_name}By{suffix}")
+                disambiguate(naming, &base_name, &fk.columns)
             } else {
                 base_name
             };
 
-            let filter_obj = build_fk_filter(&fk.referenced_columns, &fk.columns);
+            let filter_obj = build_fk_filter(naming, &fk.referenced_columns, &fk.columns);
 
             let resolver = Resolver::Postgres(Postgres {
                 table: fk.referenced_table.clone(),
@@ -85,20 +186,15 @@ pub fn from_database(schema: &DatabaseSchema, connection_url: &str) -> anyhow::R
                     || fk.referenced_table == table.qualified_name()
                 {
                     let child_type_name = table_to_type_name(&other_table.name);
-                    let base_name = pluralise(&other_table.name.to_case(Case::Camel));
+                    let base_name =
+                        naming.field_name(&pluralise(&other_table.name.to_case(Case::Camel)));
                     let field_name = if output_type.fields.contains_key(&base_name) {
-                        let suffix = fk
-                            .columns
-                            .iter()
-                            .map(|c| c.to_case(Case::Camel))
-                            .collect::<Vec<_>>()
-                            .join("And");
-                        format!("{base_name}By{suffix}")
+                        disambiguate(naming, &base_name, &fk.columns)
                     } else {
                         base_name
                     };
 
-                    let filter_obj = build_fk_filter(&fk.columns, &fk.referenced_columns);
+                    let filter_obj = build_fk_filter(naming, &fk.columns, &fk.referenced_columns);
 
                     let resolver = Resolver::Postgres(Postgres {
                         table: other_table.name.clone(),
@@ -124,62 +220,70 @@ pub fn from_database(schema: &DatabaseSchema, connection_url: &str) -> anyhow::R
 
         config.types.insert(type_name.clone(), output_type);
 
-        // --- Query: byId ---
-        if let Some(pk) = &table.primary_key {
-            let by_id_name = format!("{}ById", table.name.to_case(Case::Camel));
-            let (args, filter) = build_pk_args_and_filter(table, pk);
+        if options.generates(CrudOperation::Read) {
+            // --- Query: byId ---
+            if let Some(pk) = &table.primary_key {
+                let by_id_name = naming.field_name(&format!("{}_by_id", table.name));
+                let (args, filter) = build_pk_args_and_filter(naming, table, pk);
 
-            let resolver = Resolver::Postgres(Postgres {
-                table: table.name.clone(),
-                operation: PostgresOperation::SelectOne,
-                filter: Some(filter),
-                ..Default::default()
-            });
+                let resolver = Resolver::Postgres(Postgres {
+                    table: table.name.clone(),
+                    operation: PostgresOperation::SelectOne,
+                    filter: Some(filter),
+                    ..Default::default()
+                });
 
-            query_type.fields.insert(
-                by_id_name,
-                Field::default()
-                    .type_of(Type::from(type_name.clone()))
-                    .args(args)
-                    .resolvers(resolver.into()),
-            );
-        }
+                query_type.fields.insert(
+                    by_id_name,
+                    Field::default()
+                        .type_of(Type::from(type_name.clone()))
+                        .args(args)
+                        .resolvers(resolver.into()),
+                );
+            }
 
-        // --- Query: list ---
-        {
-            let list_name = format!("{}List", table.name.to_case(Case::Camel));
+            // --- Query: list ---
+            {
+                let list_name = naming.field_name(&format!("{}_list", table.name));
 
-            let resolver = Resolver::Postgres(Postgres {
-                table: table.name.clone(),
-                operation: PostgresOperation::Select,
-                limit: Some("{{.args.limit}}".to_string()),
-                offset: Some("{{.args.offset}}".to_string()),
-                ..Default::default()
-            });
+                let resolver = Resolver::Postgres(Postgres {
+                    table: table.name.clone(),
+                    operation: PostgresOperation::Select,
+                    limit: Some("{{.args.limit}}".to_string()),
+                    offset: Some("{{.args.offset}}".to_string()),
+                    ..Default::default()
+                });
 
-            query_type.fields.insert(
-                list_name,
-                Field::default()
-                    .type_of(Type::from(type_name.clone()).into_list().into_required())
-                    .args(IndexMap::from([
-                        (
-                            "limit".to_string(),
-                            Arg { type_of: Type::from("Int".to_string()), ..Default::default() },
-                        ),
-                        (
-                            "offset".to_string(),
-                            Arg { type_of: Type::from("Int".to_string()), ..Default::default() },
-                        ),
-                    ]))
-                    .resolvers(resolver.into()),
-            );
+                query_type.fields.insert(
+                    list_name,
+                    Field::default()
+                        .type_of(Type::from(type_name.clone()).into_list().into_required())
+                        .args(IndexMap::from([
+                            (
+                                "limit".to_string(),
+                                Arg {
+                                    type_of: Type::from("Int".to_string()),
+                                    ..Default::default()
+                                },
+                            ),
+                            (
+                                "offset".to_string(),
+                                Arg {
+                                    type_of: Type::from("Int".to_string()),
+                                    ..Default::default()
+                                },
+                            ),
+                        ]))
+                        .resolvers(resolver.into()),
+                );
+            }
         }
 
         // Views are read-only: skip all mutation generation.
         if !table.is_view {
             // --- Mutation: create ---
-            {
-                let create_name = format!("create{type_name}");
+            if options.generates(CrudOperation::Create) {
+                let create_name = naming.field_name(&format!("create_{}", table.name));
                 let input_type_name = format!("Create{type_name}Input");
                 let mut input_type = ConfigType::default();
 
@@ -197,7 +301,7 @@ pub fn from_database(schema: &DatabaseSchema, connection_url: &str) -> anyhow::R
                     }
                     let gql_type = column_to_input_type(col);
                     input_type.fields.insert(
-                        col.name.to_case(Case::Camel),
+                        naming.field_name(&col.name),
                         Field::default().type_of(gql_type),
                     );
                 }
@@ -227,8 +331,10 @@ pub fn from_database(schema: &DatabaseSchema, connection_url: &str) -> anyhow::R
             }
 
             // --- Mutation: update ---
-            if let Some(pk) = &table.primary_key {
-                let update_name = format!("update{type_name}");
+            if let Some(pk) = &table.primary_key
+                && options.generates(CrudOperation::Update)
+            {
+                let update_name = naming.field_name(&format!("update_{}", table.name));
                 let input_type_name = format!("Update{type_name}Input");
                 let mut input_type = ConfigType::default();
 
@@ -242,13 +348,13 @@ pub fn from_database(schema: &DatabaseSchema, connection_url: &str) -> anyhow::R
                     // All fields optional for partial updates.
                     let gql_type = Type::from(scalar_type(&col.pg_type));
                     input_type.fields.insert(
-                        col.name.to_case(Case::Camel),
+                        naming.field_name(&col.name),
                         Field::default().type_of(gql_type),
                     );
                 }
 
                 config.types.insert(input_type_name.clone(), input_type);
-                let (mut args, filter) = build_pk_args_and_filter(table, pk);
+                let (mut args, filter) = build_pk_args_and_filter(naming, table, pk);
                 args.insert(
                     "input".to_string(),
                     Arg {
@@ -275,9 +381,11 @@ pub fn from_database(schema: &DatabaseSchema, connection_url: &str) -> anyhow::R
             }
 
             // --- Mutation: delete ---
-            if let Some(pk) = &table.primary_key {
-                let delete_name = format!("delete{type_name}");
-                let (args, filter) = build_pk_args_and_filter(table, pk);
+            if let Some(pk) = &table.primary_key
+                && options.generates(CrudOperation::Delete)
+            {
+                let delete_name = naming.field_name(&format!("delete_{}", table.name));
+                let (args, filter) = build_pk_args_and_filter(naming, table, pk);
 
                 let resolver = Resolver::Postgres(Postgres {
                     table: table.name.clone(),
@@ -297,25 +405,45 @@ pub fn from_database(schema: &DatabaseSchema, connection_url: &str) -> anyhow::R
         }
     }
 
-    config.types.insert("Query".to_string(), query_type);
-    config.types.insert("Mutation".to_string(), mutation_type);
-
-    // Add the Postgres link.
-    config.links.push(Link {
-        id: None,
-        src: connection_url.to_string(),
-        type_of: LinkType::Postgres,
-        headers: None,
-        meta: None,
-        proto_paths: None,
-    });
+    // An empty root type is invalid, so only emit the ones that have fields.
+    if !query_type.fields.is_empty() {
+        config.schema.query = Some("Query".to_string());
+        config.types.insert("Query".to_string(), query_type);
+    }
+    if !mutation_type.fields.is_empty() {
+        config.schema.mutation = Some("Mutation".to_string());
+        config.types.insert("Mutation".to_string(), mutation_type);
+    }
 
     Ok(config)
 }
 
+/// Checks whether the table referenced by `fk` is part of `schema`.
+fn references_table(schema: &DatabaseSchema, fk: &ForeignKey) -> bool {
+    schema
+        .find_table(&format!("{}.{}", fk.referenced_schema, fk.referenced_table))
+        .or_else(|| schema.find_table(&fk.referenced_table))
+        .is_some()
+}
+
+/// Builds a unique relationship field name by appending the FK columns, e.g.
+/// `usersByAuthorId`.
+fn disambiguate(naming: NamingConvention, base_name: &str, columns: &[String]) -> String {
+    let suffix = columns
+        .iter()
+        .map(|c| c.to_case(Case::Camel))
+        .collect::<Vec<_>>()
+        .join("And");
+    naming.field_name(&format!("{}By{suffix}", base_name.to_case(Case::Camel)))
+}
+
 /// Build a filter object that maps source columns to mustache expressions
 /// referencing target columns.
-fn build_fk_filter(source_columns: &[String], target_columns: &[String]) -> serde_json::Value {
+fn build_fk_filter(
+    naming: NamingConvention,
+    source_columns: &[String],
+    target_columns: &[String],
+) -> serde_json::Value {
     debug_assert_eq!(
         source_columns.len(),
         target_columns.len(),
@@ -327,7 +455,7 @@ fn build_fk_filter(source_columns: &[String], target_columns: &[String]) -> serd
         .map(|(src, tgt)| {
             (
                 src.clone(),
-                serde_json::Value::String(format!("{{{{.value.{}}}}}", tgt.to_case(Case::Camel))),
+                serde_json::Value::String(format!("{{{{.value.{}}}}}", naming.field_name(tgt))),
             )
         })
         .collect::<serde_json::Map<String, serde_json::Value>>()
@@ -336,6 +464,7 @@ fn build_fk_filter(source_columns: &[String], target_columns: &[String]) -> serd
 
 /// Build PK-based args and filter map for a table.
 fn build_pk_args_and_filter(
+    naming: NamingConvention,
     table: &Table,
     pk: &PrimaryKey,
 ) -> (IndexMap<String, Arg>, serde_json::Value) {
@@ -344,18 +473,16 @@ fn build_pk_args_and_filter(
     for pk_col in &pk.columns {
         let col = table.find_column(pk_col);
         let gql_type = col.map_or("ID".to_string(), |c| scalar_type(&c.pg_type));
+        let arg_name = naming.field_name(pk_col);
 
+        filter_map.insert(pk_col.clone(), json!(format!("{{{{.args.{arg_name}}}}}")));
         args.insert(
-            pk_col.to_case(Case::Camel),
+            arg_name,
             Arg {
                 type_of: Type::from(gql_type).into_required(),
                 ..Default::default()
             },
         );
-        filter_map.insert(
-            pk_col.clone(),
-            json!(format!("{{{{.args.{}}}}}", pk_col.to_case(Case::Camel))),
-        );
     }
     (args, serde_json::Value::Object(filter_map))
 }
@@ -474,7 +601,7 @@ mod tests {
     #[test]
     fn generates_query_types() {
         let schema = sample_schema();
-        let config = from_database(&schema, "postgres://localhost/test").unwrap();
+        let config = from_database(&schema, &DatabaseOptions::default()).unwrap();
 
         // Check output types exist
         assert!(config.types.contains_key("Users"));
@@ -497,7 +624,7 @@ mod tests {
     #[test]
     fn generates_fk_relationships() {
         let schema = sample_schema();
-        let config = from_database(&schema, "postgres://localhost/test").unwrap();
+        let config = from_database(&schema, &DatabaseOptions::default()).unwrap();
 
         // Posts should have a `users` field (FK to users)
         let posts_type = config.types.get("Posts").unwrap();
@@ -537,7 +664,7 @@ mod tests {
             is_view: true,
         });
 
-        let config = from_database(&schema, "postgres://localhost/test").unwrap();
+        let config = from_database(&schema, &DatabaseOptions::default()).unwrap();
 
         // Query: list should be generated.
         let query = config.types.get("Query").unwrap();
@@ -545,11 +672,10 @@ mod tests {
         // No byId since there is no PK.
         assert!(!query.fields.contains_key("activeUsersById"));
 
-        // Mutations must NOT be generated for views.
-        let mutation = config.types.get("Mutation").unwrap();
-        assert!(!mutation.fields.contains_key("createActiveUsers"));
-        assert!(!mutation.fields.contains_key("updateActiveUsers"));
-        assert!(!mutation.fields.contains_key("deleteActiveUsers"));
+        // Mutations must NOT be generated for views, so there is no
+        // Mutation type at all.
+        assert!(!config.types.contains_key("Mutation"));
+        assert_eq!(config.schema.mutation, None);
     }
 
     #[test]
@@ -580,10 +706,63 @@ mod tests {
             is_view: true,
         });
 
-        let config = from_database(&schema, "postgres://localhost/test").unwrap();
+        let config = from_database(&schema, &DatabaseOptions::default()).unwrap();
         let query = config.types.get("Query").unwrap();
 
         assert!(query.fields.contains_key("summaryViewList"));
         assert!(!query.fields.contains_key("summaryViewById"));
     }
+
+    #[test]
+    fn filters_tables_and_skips_dangling_relationships() {
+        let schema = sample_schema();
+        let options =
+            DatabaseOptions { exclude: vec!["public.users".into()], ..Default::default() };
+        let config = from_database(&schema, &options).unwrap();
+
+        assert!(!config.types.contains_key("Users"));
+        let posts_type = config.types.get("Posts").unwrap();
+        assert!(!posts_type.fields.contains_key("users"));
+
+        let options = DatabaseOptions { tables: vec!["us*".into()], ..Default::default() };
+        let config = from_database(&schema, &options).unwrap();
+        assert!(config.types.contains_key("Users"));
+        assert!(!config.types.contains_key("Posts"));
+    }
+
+    #[test]
+    fn generates_selected_operations_only() {
+        let schema = sample_schema();
+        let options =
+            DatabaseOptions { operations: vec![CrudOperation::Read], ..Default::default() };
+        let config = from_database(&schema, &options).unwrap();
+
+        let query = config.types.get("Query").unwrap();
+        assert!(query.fields.contains_key("usersById"));
+        assert!(!config.types.contains_key("Mutation"));
+        assert!(!config.types.contains_key("CreateUsersInput"));
+    }
+
+    #[test]
+    fn applies_snake_case_naming() {
+        let schema = sample_schema();
+        let options = DatabaseOptions { naming: NamingConvention::SnakeCase, ..Default::default() };
+        let config = from_database(&schema, &options).unwrap();
+
+        let query = config.types.get("Query").unwrap();
+        assert!(query.fields.contains_key("users_by_id"));
+        assert!(query.fields.contains_key("posts_list"));
+
+        let mutation = config.types.get("Mutation").unwrap();
+        assert!(mutation.fields.contains_key("create_users"));
+
+        let posts_type = config.types.get("Posts").unwrap();
+        assert!(posts_type.fields.contains_key("user_id"));
+        let Some(Resolver::Postgres(pg)) =
+            posts_type.fields.get("users").unwrap().resolvers.0.first()
+        else {
+            panic!("expected a @postgres resolver");
+        };
+        assert_eq!(pg.filter, Some(json!({"id": "{{.value.user_id}}"})));
+    }
 }
@@ -0,0 +1,755 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use convert_case::{Case, Casing};
+use serde_json::{Map, Value};
+use url::Url;
+
+use super::json::HttpDirectiveGenerator;
+use crate::core::Type;
+use crate::core::config::{
+    Arg, Config, Enum, Field, GraphQLOperationType, KeyValue, Resolver, Type as ConfigType, Union,
+    Variant,
+};
+use crate::core::helpers::gql_type::is_valid_field_name;
+use crate::core::http::Method;
+use crate::core::json::JsonSchema;
+use crate::core::scalar::Scalar;
+
+/// An upstream endpoint whose request and response bodies are described by
+/// JSON Schema documents (draft 2020-12).
+pub struct JsonSchemaSample {
+    pub url: Url,
+    pub method: Method,
+    pub field_name: String,
+    pub operation_type: GraphQLOperationType,
+    pub headers: Option<BTreeMap<String, String>>,
+    /// Schema of the response body.
+    pub output: Value,
+    /// Schema of the request body, exposed as an `input` argument.
+    pub input: Option<Value>,
+}
+
+/// Generates a `Config` with a single `@http` field for the given sample.
+///
+/// Object schemas become GraphQL types (input types for the request body),
+/// `enum` becomes a GraphQL enum, `oneOf`/`anyOf` of objects becomes a union
+/// and anything that can't be represented falls back to the `JSON` scalar.
+/// Local `$ref`s (`#/$defs/...`) become named types that are shared across
+/// the document. The response schema is also attached as `output` on the
+/// generated `@http` directive.
+///
+/// # Errors
+///
+/// Returns an error if a `$ref` can't be resolved within the document.
+pub fn from_json_schema(
+    sample: &JsonSchemaSample,
+    query: &str,
+    mutation: Option<&str>,
+) -> anyhow::Result<Config> {
+    let mut config = Config::default();
+    let root_name = sample.field_name.to_case(Case::Pascal);
+
+    let output = SchemaConverter::new(&sample.output, Mode::Output, &mut config)
+        .convert(&sample.output, &root_name)?;
+
+    let mut field = Field { type_of: output.type_of.clone(), ..Default::default() };
+    let mut http = HttpDirectiveGenerator::new(&sample.url).generate_http_directive(&mut field);
+    http.method = sample.method;
+    http.output = Some(output.schema);
+    if let Some(headers) = &sample.headers {
+        http.headers = headers
+            .iter()
+            .map(|(key, value)| KeyValue { key: key.clone(), value: value.clone() })
+            .collect();
+    }
+
+    if let Some(input_schema) = &sample.input {
+        let input = SchemaConverter::new(input_schema, Mode::Input, &mut config)
+            .convert(input_schema, &format!("{root_name}Input"))?;
+        let type_of = if input.nullable {
+            input.type_of
+        } else {
+            input.type_of.into_required()
+        };
+        http.body = Some(Value::String("{{.args.input}}".to_string()));
+        http.input = Some(input.schema);
+        field
+            .args
+            .insert("input".to_string(), Arg { type_of, ..Default::default() });
+    }
+
+    field.resolvers = Resolver::Http(http).into();
+
+    let root_type_name = match sample.operation_type {
+        GraphQLOperationType::Mutation => {
+            let name = mutation.unwrap_or("Mutation").to_owned();
+            config.schema.mutation = Some(name.clone());
+            name
+        }
+        _ => {
+            config.schema.query = Some(query.to_owned());
+            query.to_owned()
+        }
+    };
+    config
+        .types
+        .entry(root_type_name)
+        .or_default()
+        .fields
+        .insert(sample.field_name.clone(), field);
+
+    Ok(config)
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Mode {
+    Input,
+    Output,
+}
+
+/// The GraphQL type and runtime schema generated for a JSON Schema node.
+#[derive(Clone)]
+struct Converted {
+    /// Named or list type, always nullable. Callers make it required when
+    /// the schema doesn't allow `null`.
+    type_of: Type,
+    nullable: bool,
+    schema: JsonSchema,
+}
+
+impl Converted {
+    fn new(name: impl Into<String>, schema: JsonSchema) -> Self {
+        Self { type_of: Type::from(name.into()), nullable: false, schema }
+    }
+
+    fn json() -> Self {
+        Self::new(Scalar::JSON.name(), JsonSchema::Any)
+    }
+
+    fn nullable(mut self) -> Self {
+        self.nullable = true;
+        self
+    }
+
+    /// Wraps the type and schema according to `nullable`, for use as a field
+    /// or list item.
+    fn into_member(self) -> (Type, JsonSchema) {
+        if self.nullable {
+            (self.type_of, self.schema.optional())
+        } else {
+            (self.type_of.into_required(), self.schema)
+        }
+    }
+}
+
+struct SchemaConverter<'a> {
+    root: &'a Value,
+    mode: Mode,
+    config: &'a mut Config,
+    refs: HashMap<String, Converted>,
+    /// Name reserved by `convert_ref` for the object type of its target.
+    reserved: Option<String>,
+    /// References being converted, and those among them that were reached
+    /// again from their own target.
+    pending: HashSet<String>,
+    recursive: HashSet<String>,
+}
+
+impl<'a> SchemaConverter<'a> {
+    fn new(root: &'a Value, mode: Mode, config: &'a mut Config) -> Self {
+        Self {
+            root,
+            mode,
+            config,
+            refs: HashMap::new(),
+            reserved: None,
+            pending: HashSet::new(),
+            recursive: HashSet::new(),
+        }
+    }
+
+    fn convert(&mut self, schema: &Value, name: &str) -> anyhow::Result<Converted> {
+        // Only the target of a reference itself takes its reserved name.
+        let reserved = self.reserved.take();
+        let Value::Object(schema) = schema else {
+            // `true`, `false` and anything else that isn't a schema object.
+            return Ok(Converted::json());
+        };
+
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            return self.convert_ref(reference);
+        }
+
+        let name = schema
+            .get("title")
+            .and_then(Value::as_str)
+            .map_or_else(|| name.to_string(), |title| title.to_case(Case::Pascal));
+
+        if let Some(variants) = schema
+            .get("oneOf")
+            .or_else(|| schema.get("anyOf"))
+            .and_then(Value::as_array)
+        {
+            return self.convert_variants(variants, &name, reserved);
+        }
+
+        if let Some(parts) = schema.get("allOf").and_then(Value::as_array) {
+            let merged = self.merge_all_of(schema, parts)?;
+            return self.convert_object(&merged, &name, reserved);
+        }
+
+        if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+            return Ok(self.convert_enum(values, &name));
+        }
+
+        if schema.get("const").is_some_and(Value::is_string) {
+            return Ok(Converted::new("String", JsonSchema::Str));
+        }
+
+        let (types, nullable) = match schema.get("type") {
+            Some(Value::String(ty)) => (vec![ty.as_str()], false),
+            Some(Value::Array(types)) => {
+                let types = types.iter().filter_map(Value::as_str).collect::<Vec<_>>();
+                let nullable = types.contains(&"null");
+                let types = types.into_iter().filter(|ty| *ty != "null").collect();
+                (types, nullable)
+            }
+            _ if schema.contains_key("properties") => (vec!["object"], false),
+            _ if schema.contains_key("items") => (vec!["array"], false),
+            _ => (vec![], false),
+        };
+
+        let converted = match types.as_slice() {
+            ["object"] => self.convert_object(schema, &name, reserved)?,
+            ["array"] => match schema.get("items") {
+                Some(items) => {
+                    let (item_type, item_schema) =
+                        self.convert(items, &format!("{name}Item"))?.into_member();
+                    Converted {
+                        type_of: item_type.into_list(),
+                        nullable: false,
+                        schema: JsonSchema::Arr(Box::new(item_schema)),
+                    }
+                }
+                None => Converted::json(),
+            },
+            ["string"] => Converted::new(
+                string_format(schema.get("format").and_then(Value::as_str)),
+                JsonSchema::Str,
+            ),
+            ["integer"] => Converted::new("Int", JsonSchema::Num),
+            ["number"] => Converted::new("Float", JsonSchema::Num),
+            ["boolean"] => Converted::new("Boolean", JsonSchema::Bool),
+            _ => Converted::json(),
+        };
+
+        Ok(if nullable {
+            converted.nullable()
+        } else {
+            converted
+        })
+    }
+
+    fn convert_ref(&mut self, reference: &str) -> anyhow::Result<Converted> {
+        if let Some(converted) = self.refs.get(reference) {
+            if self.pending.contains(reference) {
+                self.recursive.insert(reference.to_string());
+            }
+            return Ok(converted.clone());
+        }
+
+        let target = reference
+            .strip_prefix('#')
+            .and_then(|pointer| self.root.pointer(pointer))
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Unable to resolve $ref '{reference}': only local references are supported"
+                )
+            })?;
+
+        let name = target
+            .get("title")
+            .and_then(Value::as_str)
+            .unwrap_or_else(|| reference.rsplit('/').next().unwrap_or_default())
+            .to_case(Case::Pascal);
+
+        // Reserve the name before descending so that recursive references
+        // resolve to the named type instead of looping forever.
+        let reserved = self.unique_name(&self.object_name(&name));
+        self.refs.insert(
+            reference.to_string(),
+            Converted::new(reserved.clone(), JsonSchema::Any),
+        );
+        self.reserved = Some(reserved.clone());
+        self.pending.insert(reference.to_string());
+        let converted = self.convert(target, &name)?;
+        self.pending.remove(reference);
+
+        // Recursive references point at the reserved name, which only object
+        // types and unions take.
+        if self.recursive.contains(reference)
+            && !self.config.types.contains_key(&reserved)
+            && !self.config.unions.contains_key(&reserved)
+        {
+            anyhow::bail!(
+                "Unable to convert recursive $ref '{reference}': only objects and unions can reference themselves"
+            );
+        }
+        self.refs.insert(reference.to_string(), converted.clone());
+
+        Ok(converted)
+    }
+
+    fn convert_variants(
+        &mut self,
+        variants: &[Value],
+        name: &str,
+        reserved: Option<String>,
+    ) -> anyhow::Result<Converted> {
+        let is_null = |variant: &Value| variant.get("type").and_then(Value::as_str) == Some("null");
+        let nullable = variants.iter().any(is_null);
+        let variants = variants
+            .iter()
+            .filter(|variant| !is_null(*variant))
+            .collect::<Vec<_>>();
+
+        let converted = match variants.as_slice() {
+            [] => Converted::json(),
+            [variant] => {
+                self.reserved = reserved;
+                self.convert(variant, name)?
+            }
+            variants if self.mode == Mode::Output => {
+                let mut members = BTreeSet::new();
+                for (i, variant) in variants.iter().enumerate() {
+                    let member = self.convert(variant, &format!("{name}{}", i + 1))?;
+                    let member_name = member.type_of.name().clone();
+                    if member.type_of.is_list() || !self.config.types.contains_key(&member_name) {
+                        // Unions can only contain object types.
+                        members.clear();
+                        break;
+                    }
+                    members.insert(member_name);
+                }
+
+                if members.is_empty() {
+                    Converted::json()
+                } else {
+                    // Members may have claimed the reserved name meanwhile.
+                    let name = reserved
+                        .filter(|reserved| self.unique_name(reserved) == *reserved)
+                        .unwrap_or_else(|| self.unique_name(name));
+                    self.config
+                        .unions
+                        .insert(name.clone(), Union { types: members, doc: None });
+                    Converted::new(name, JsonSchema::Any)
+                }
+            }
+            // Input unions aren't supported by GraphQL.
+            _ => Converted::json(),
+        };
+
+        Ok(if nullable {
+            converted.nullable()
+        } else {
+            converted
+        })
+    }
+
+    /// Flattens `allOf` into a single object schema by merging `properties`
+    /// and `required` of every part.
+    fn merge_all_of(
+        &self,
+        schema: &Map<String, Value>,
+        parts: &[Value],
+    ) -> anyhow::Result<Map<String, Value>> {
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        let own = Value::Object(schema.clone());
+
+        for part in parts.iter().chain(std::iter::once(&own)) {
+            let part = match part.get("$ref").and_then(Value::as_str) {
+                Some(reference) => reference
+                    .strip_prefix('#')
+                    .and_then(|pointer| self.root.pointer(pointer))
+                    .ok_or_else(|| anyhow::anyhow!("Unable to resolve $ref '{reference}'"))?,
+                None => part,
+            };
+            if let Some(Value::Object(props)) = part.get("properties") {
+                properties.extend(props.clone());
+            }
+            if let Some(Value::Array(req)) = part.get("required") {
+                required.extend(req.iter().cloned());
+            }
+        }
+
+        let mut merged = Map::new();
+        merged.insert("type".to_string(), Value::String("object".to_string()));
+        merged.insert("properties".to_string(), Value::Object(properties));
+        merged.insert("required".to_string(), Value::Array(required));
+        Ok(merged)
+    }
+
+    fn convert_object(
+        &mut self,
+        schema: &Map<String, Value>,
+        name: &str,
+        reserved: Option<String>,
+    ) -> anyhow::Result<Converted> {
+        let Some(Value::Object(properties)) = schema.get("properties") else {
+            // Free-form objects and maps (`additionalProperties` only).
+            return Ok(Converted::json());
+        };
+        if properties.is_empty() || properties.keys().any(|key| !is_valid_field_name(key)) {
+            return Ok(Converted::json());
+        }
+
+        let required = schema
+            .get("required")
+            .and_then(Value::as_array)
+            .map(|required| {
+                required
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .collect::<BTreeSet<_>>()
+            })
+            .unwrap_or_default();
+
+        let name = reserved.unwrap_or_else(|| self.unique_name(&self.object_name(name)));
+        // Reserve the name so nested schemas can't claim it.
+        self.config
+            .types
+            .insert(name.clone(), ConfigType::default());
+
+        let mut ty = ConfigType {
+            doc: schema
+                .get("description")
+                .and_then(Value::as_str)
+                .map(ToString::to_string),
+            ..Default::default()
+        };
+        let mut fields = BTreeMap::new();
+
+        for (property, property_schema) in properties {
+            let converted = self.convert(
+                property_schema,
+                &format!("{name}{}", property.to_case(Case::Pascal)),
+            )?;
+            let converted = if required.contains(property.as_str()) {
+                converted
+            } else {
+                converted.nullable()
+            };
+            let (type_of, field_schema) = converted.into_member();
+
+            let doc = property_schema
+                .get("description")
+                .and_then(Value::as_str)
+                .map(ToString::to_string);
+            ty.fields.insert(
+                property.clone(),
+                Field { type_of, doc, ..Default::default() },
+            );
+            fields.insert(property.clone(), field_schema);
+        }
+
+        self.config.types.insert(name.clone(), ty);
+        Ok(Converted::new(name, JsonSchema::Obj(fields)))
+    }
+
+    fn convert_enum(&mut self, values: &[Value], name: &str) -> Converted {
+        let variants = values
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<BTreeSet<_>>();
+        let nullable = values.iter().any(Value::is_null);
+
+        let converted = if variants.is_empty()
+            || !values
+                .iter()
+                .all(|value| value.is_string() || value.is_null())
+        {
+            Converted::json()
+        } else if variants.iter().any(|variant| !is_valid_field_name(variant)) {
+            // Values GraphQL can't use as enum names.
+            Converted::new("String", JsonSchema::Str)
+        } else {
+            let schema = JsonSchema::Enum(variants.iter().map(ToString::to_string).collect());
+            let existing = self.config.enums.get(name).map(|en| {
+                en.variants
+                    .iter()
+                    .map(|variant| variant.name.as_str())
+                    .collect::<BTreeSet<_>>()
+            });
+            let name = match existing {
+                Some(existing) if existing == variants => name.to_string(),
+                _ => self.unique_name(name),
+            };
+            self.config.enums.insert(
+                name.clone(),
+                Enum {
+                    variants: variants
+                        .iter()
+                        .map(|variant| Variant { name: (*variant).to_string(), alias: None })
+                        .collect(),
+                    doc: None,
+                },
+            );
+            Converted::new(name, schema)
+        };
+
+        if nullable {
+            converted.nullable()
+        } else {
+            converted
+        }
+    }
+
+    /// Input object types get an `Input` suffix to keep them apart from the
+    /// output types generated from the same definitions.
+    fn object_name(&self, name: &str) -> String {
+        if self.mode == Mode::Input && !name.ends_with("Input") {
+            format!("{name}Input")
+        } else {
+            name.to_string()
+        }
+    }
+
+    /// Returns `name`, or `name` with a numeric suffix if it's already taken by
+    /// another type.
+    fn unique_name(&self, name: &str) -> String {
+        let taken = |name: &str| {
+            self.config.types.contains_key(name)
+                || self.config.enums.contains_key(name)
+                || self.config.unions.contains_key(name)
+        };
+        if !taken(name) {
+            return name.to_string();
+        }
+        (2..)
+            .map(|i| format!("{name}{i}"))
+            .find(|candidate| !taken(candidate))
+            .unwrap_or_default()
+    }
+}
+
+fn string_format(format: Option<&str>) -> String {
+    match format {
+        Some("date-time") => Scalar::DateTime.name(),
+        Some("date") => Scalar::Date.name(),
+        Some("email") => Scalar::Email.name(),
+        Some("uri" | "url") => Scalar::Url.name(),
+        _ => "String".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![expect(clippy::unwrap_used, reason = "test code")]
+    use serde_json::json;
+
+    use super::*;
+
+    fn sample(output: Value) -> JsonSchemaSample {
+        JsonSchemaSample {
+            url: "https://api.example.com/users/1".parse().unwrap(),
+            method: Method::GET,
+            field_name: "user".to_string(),
+            operation_type: GraphQLOperationType::Query,
+            headers: None,
+            output,
+            input: None,
+        }
+    }
+
+    fn user_schema() -> Value {
+        json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "type": "object",
+            "required": ["id", "name", "role"],
+            "properties": {
+                "id": {"type": "integer"},
+                "name": {"type": "string"},
+                "email": {"type": ["string", "null"], "format": "email"},
+                "role": {"enum": ["ADMIN", "MEMBER"]},
+                "address": {"$ref": "#/$defs/address"},
+                "contact": {
+                    "oneOf": [{"$ref": "#/$defs/phone"}, {"$ref": "#/$defs/address"}]
+                }
+            },
+            "$defs": {
+                "address": {
+                    "type": "object",
+                    "required": ["city"],
+                    "properties": {"city": {"type": "string"}, "zip": {"type": "string"}}
+                },
+                "phone": {
+                    "type": "object",
+                    "properties": {"number": {"type": "string"}}
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn generates_types_from_json_schema() {
+        let config = from_json_schema(&sample(user_schema()), "Query", None).unwrap();
+
+        let user = config.types.get("User").unwrap();
+        assert_eq!(
+            user.fields["id"].type_of,
+            Type::from("Int".to_string()).into_required()
+        );
+        assert_eq!(
+            user.fields["email"].type_of,
+            Type::from("Email".to_string())
+        );
+        assert_eq!(
+            user.fields["role"].type_of,
+            Type::from("UserRole".to_string()).into_required()
+        );
+        assert_eq!(
+            user.fields["contact"].type_of,
+            Type::from("UserContact".to_string())
+        );
+        assert!(config.types.contains_key("Address"));
+        assert!(config.enums.contains_key("UserRole"));
+        assert_eq!(
+            config.unions["UserContact"].types,
+            BTreeSet::from(["Address".to_string(), "Phone".to_string()])
+        );
+        assert_eq!(config.schema.query, Some("Query".to_string()));
+    }
+
+    #[test]
+    fn attaches_output_schema_to_http() {
+        let config = from_json_schema(&sample(user_schema()), "Query", None).unwrap();
+        let field = config.types["Query"].fields.get("user").unwrap();
+        let Some(Resolver::Http(http)) = field.resolvers.0.first() else {
+            panic!("expected an @http resolver");
+        };
+        let Some(JsonSchema::Obj(fields)) = &http.output else {
+            panic!("expected an object output schema");
+        };
+
+        assert_eq!(fields["id"], JsonSchema::Num);
+        assert_eq!(fields["email"], JsonSchema::Str.optional());
+        assert_eq!(
+            fields["role"],
+            JsonSchema::Enum(BTreeSet::from(["ADMIN".to_string(), "MEMBER".to_string()]))
+        );
+    }
+
+    #[test]
+    fn generates_input_types_for_request_body() {
+        let mut sample = sample(user_schema());
+        sample.operation_type = GraphQLOperationType::Mutation;
+        sample.method = Method::POST;
+        sample.input = Some(json!({
+            "type": "object",
+            "required": ["name"],
+            "properties": {
+                "name": {"type": "string"},
+                "address": {"$ref": "#/$defs/address"}
+            },
+            "$defs": {
+                "address": {"type": "object", "properties": {"city": {"type": "string"}}}
+            }
+        }));
+
+        let config = from_json_schema(&sample, "Query", None).unwrap();
+        let field = config.types["Mutation"].fields.get("user").unwrap();
+
+        assert_eq!(
+            field.args["input"].type_of,
+            Type::from("UserInput".to_string()).into_required()
+        );
+        assert!(config.types.contains_key("AddressInput"));
+        assert_eq!(config.schema.mutation, Some("Mutation".to_string()));
+    }
+
+    #[test]
+    fn handles_recursive_references() {
+        let schema = json!({
+            "$ref": "#/$defs/node",
+            "$defs": {
+                "node": {
+                    "type": "object",
+                    "properties": {
+                        "value": {"type": "string"},
+                        "children": {"type": "array", "items": {"$ref": "#/$defs/node"}}
+                    }
+                }
+            }
+        });
+
+        let config = from_json_schema(&sample(schema), "Query", None).unwrap();
+        let node = config.types.get("Node").unwrap();
+        assert_eq!(
+            node.fields["children"].type_of,
+            Type::from("Node".to_string()).into_required().into_list()
+        );
+    }
+
+    #[test]
+    fn names_colliding_references_once() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "author": {"$ref": "#/$defs/author"},
+                "reviewer": {"$ref": "#/$defs/reviewer"}
+            },
+            "$defs": {
+                "author": {
+                    "title": "Person",
+                    "type": "object",
+                    "properties": {"name": {"type": "string"}}
+                },
+                "reviewer": {
+                    "title": "Person",
+                    "type": "object",
+                    "properties": {
+                        "score": {"type": "integer"},
+                        "mentor": {"$ref": "#/$defs/reviewer"}
+                    }
+                }
+            }
+        });
+
+        let config = from_json_schema(&sample(schema), "Query", None).unwrap();
+        let user = config.types.get("User").unwrap();
+        assert_eq!(
+            user.fields["author"].type_of,
+            Type::from("Person".to_string())
+        );
+        assert_eq!(
+            user.fields["reviewer"].type_of,
+            Type::from("Person2".to_string())
+        );
+        // The recursive reference points at the type it was reserved for
+        let reviewer = config.types.get("Person2").unwrap();
+        assert_eq!(
+            reviewer.fields["mentor"].type_of,
+            Type::from("Person2".to_string())
+        );
+        assert!(config.types["Person"].fields.contains_key("name"));
+    }
+
+    #[test]
+    fn rejects_recursive_references_without_a_type() {
+        let schema = json!({
+            "type": "object",
+            "properties": {"tree": {"$ref": "#/$defs/tree"}},
+            "$defs": {
+                "tree": {"type": "array", "items": {"$ref": "#/$defs/tree"}}
+            }
+        });
+        assert!(from_json_schema(&sample(schema), "Query", None).is_err());
+    }
+
+    #[test]
+    fn rejects_external_references() {
+        let schema = json!({"$ref": "https://example.com/user.json"});
+        assert!(from_json_schema(&sample(schema), "Query", None).is_err());
+    }
+}
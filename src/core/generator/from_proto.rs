@@ -16,6 +16,7 @@ use super::proto::path_field::PathField;
 use crate::core::Type;
 use crate::core::config::transformer::{AmbiguousType, TreeShake};
 use crate::core::config::{self, Arg, Config, Enum, Field, Grpc, Resolver, Union, Variant};
+use crate::core::grpc::well_known;
 use crate::core::transform::{Transform, TransformerOps};
 
 /// Assists in the mapping and retrieval of proto type names to custom formatted
@@ -31,6 +32,9 @@ struct Context {
     /// Root GraphQL query type
     query: String,
 
+    /// Root GraphQL mutation type
+    mutation: String,
+
     /// Root GraphQL subscription type
     subscription: String,
 
@@ -46,9 +50,10 @@ struct Context {
 }
 
 impl Context {
-    fn new(query: &str, subscription: &str) -> Self {
+    fn new(query: &str, mutation: &str, subscription: &str) -> Self {
         Self {
             query: query.to_string(),
+            mutation: mutation.to_string(),
             subscription: subscription.to_string(),
             namespace: Vec::new(),
             config: Config::default(),
@@ -306,7 +311,23 @@ impl Context {
                     }
                 };
 
-                if let Some(type_name) = &field.type_name {
+                if let Some(type_of) = field
+                    .type_name
+                    .as_deref()
+                    .and_then(well_known::graphql_type)
+                {
+                    // well-known types are mapped to scalars matching their JSON
+                    // mapping, and the paths of a field mask to a list
+                    cfg_field.type_of = match field.type_name() {
+                        ".google.protobuf.FieldMask" if field.label() == Label::Repeated => {
+                            cfg_field.type_of.with_name("JSON".to_string())
+                        }
+                        ".google.protobuf.FieldMask" => {
+                            Type::from(type_of.to_string()).into_required().into_list()
+                        }
+                        _ => cfg_field.type_of.with_name(type_of.to_string()),
+                    };
+                } else if let Some(type_name) = &field.type_name {
                     // check that current field is map.
                     // it's done by checking that we've seen this type before
                     // inside the nested type. It works only if we explore nested types
@@ -368,16 +389,6 @@ impl Context {
                 let is_server_streaming = method.server_streaming.unwrap_or(false);
                 let is_client_streaming = method.client_streaming.unwrap_or(false);
 
-                // client-streaming and bidi-streaming are not supported
-                if is_client_streaming {
-                    tracing::warn!(
-                        "Skipping client/bidi-streaming method: {}.{}",
-                        service_name,
-                        method.name()
-                    );
-                    continue;
-                }
-
                 let field_name = GraphQLType::new(method.name())
                     .extend(self.namespace.as_slice())
                     .push(service_name)
@@ -388,9 +399,15 @@ impl Context {
 
                 if let Some(graphql_type) = get_input_type(method.input_type())? {
                     let key = graphql_type.clone().into_field().to_string();
-                    let type_of = graphql_type.into_object_type().to_string();
+                    let type_of = Type::from(graphql_type.into_object_type().to_string());
+                    // client streams take one message per element of a list
+                    let type_of = if is_client_streaming {
+                        type_of.into_required().into_list().into_required()
+                    } else {
+                        type_of.into_required()
+                    };
                     let val = Arg {
-                        type_of: Type::from(type_of).into_required(),
+                        type_of,
                         /* Setting it not null by default. There's no way to infer this
                          * from proto file */
                         doc: None,
@@ -416,6 +433,8 @@ impl Context {
                     dedupe: None,
                     select: None,
                     on_response_body: None,
+                    compression: None,
+                    protocol: None,
                 })
                 .into();
 
@@ -423,8 +442,12 @@ impl Context {
                     .extend(PathField::Method, i32::try_from(method_index).unwrap_or(0));
                 cfg_field.doc = self.comments_builder.get_comments(&method_path);
 
+                // server-streaming and bidi-streaming methods are subscriptions,
+                // client-streaming methods are mutations
                 let root_type_name = if is_server_streaming {
                     &self.subscription
+                } else if is_client_streaming {
+                    &self.mutation
                 } else {
                     &self.query
                 };
@@ -436,6 +459,8 @@ impl Context {
                     .or_insert_with(|| {
                         if is_server_streaming {
                             self.config.schema.subscription = Some(root_type_name.clone());
+                        } else if is_client_streaming {
+                            self.config.schema.mutation = Some(root_type_name.clone());
                         } else {
                             self.config.schema.query = Some(root_type_name.clone());
                         }
@@ -508,7 +533,7 @@ fn get_input_type(input_ty: &str) -> Result<Option<GraphQLType<Unparsed>>> {
 
 /// The main entry point that builds a Config object from proto descriptor sets.
 pub fn from_proto(descriptor_sets: &[FileDescriptorSet], query: &str, url: &str) -> Result<Config> {
-    let mut ctx = Context::new(query, "Subscription");
+    let mut ctx = Context::new(query, "Mutation", "Subscription");
     for descriptor_set in descriptor_sets {
         for file_descriptor in &descriptor_set.file {
             ctx.namespace = vec![file_descriptor.package().to_string()];
@@ -648,4 +673,9 @@ mod test {
     fn test_streaming_proto_file() {
         assert_gen!(protobuf::STREAMING);
     }
+
+    #[test]
+    fn test_well_known_types() {
+        assert_gen!(protobuf::WELL_KNOWN);
+    }
 }
@@ -7,11 +7,12 @@ use prost_reflect::prost_types::FileDescriptorSet;
 use serde_json::Value;
 use url::Url;
 
-use super::from_database::from_database;
+use super::from_database::{DatabaseOptions, from_database};
+use super::from_json_schema::{JsonSchemaSample, from_json_schema};
 use super::from_proto::from_proto;
 use super::proto::connect_rpc::ConnectRPC;
 use super::{FromJsonGenerator, NameGenerator, PREFIX, RequestSample};
-use crate::core::config::{self, Config, ConfigModule, Link, LinkType};
+use crate::core::config::{self, Config, ConfigModule, GraphQLOperationType, Link, LinkType};
 use crate::core::http::Method;
 use crate::core::merge_right::MergeRight;
 use crate::core::postgres::schema::DatabaseSchema;
@@ -47,6 +48,15 @@ pub enum Input {
         is_subscription: bool,
         headers: Option<BTreeMap<String, String>>,
     },
+    JsonSchema {
+        url: Url,
+        method: Method,
+        field_name: String,
+        is_mutation: bool,
+        headers: Option<BTreeMap<String, String>>,
+        output: Value,
+        input: Option<Value>,
+    },
     Proto {
         url: String,
         metadata: ProtoMetadata,
@@ -59,6 +69,13 @@ pub enum Input {
     Postgres {
         database_schema: DatabaseSchema,
         connection_url: String,
+        options: DatabaseOptions,
+    },
+    Sql {
+        database_schema: DatabaseSchema,
+        migrations: Vec<String>,
+        connection_url: Option<String>,
+        options: DatabaseOptions,
     },
 }
 
@@ -155,6 +172,34 @@ impl Generator {
                     config = config
                         .merge_right(self.generate_from_json(&type_name_generator, &[req_sample])?);
                 }
+                Input::JsonSchema {
+                    url,
+                    method,
+                    field_name,
+                    is_mutation,
+                    headers,
+                    output,
+                    input,
+                } => {
+                    let sample = JsonSchemaSample {
+                        url: url.to_owned(),
+                        method: method.to_owned(),
+                        field_name: field_name.to_owned(),
+                        operation_type: if *is_mutation {
+                            GraphQLOperationType::Mutation
+                        } else {
+                            GraphQLOperationType::Query
+                        },
+                        headers: headers.to_owned(),
+                        output: output.to_owned(),
+                        input: input.to_owned(),
+                    };
+                    config = config.merge_right(from_json_schema(
+                        &sample,
+                        &self.query,
+                        self.mutation.as_deref(),
+                    )?);
+                }
                 Input::Proto { metadata, url, connect_rpc } => {
                     let proto_config = Self::generate_from_proto(metadata, &self.query, url)?;
                     let proto_config = if connect_rpc == &Some(true) {
@@ -164,10 +209,27 @@ impl Generator {
                     };
                     config = config.merge_right(proto_config);
                 }
-                Input::Postgres { database_schema, connection_url } => {
-                    let pg_config = from_database(database_schema, connection_url)?;
+                Input::Postgres { database_schema, connection_url, options } => {
+                    let mut pg_config = from_database(database_schema, options)?;
+                    pg_config
+                        .links
+                        .push(database_link(connection_url, LinkType::Postgres));
                     config = config.merge_right(pg_config);
                 }
+                Input::Sql { database_schema, migrations, connection_url, options } => {
+                    let mut sql_config = from_database(database_schema, options)?;
+                    sql_config.links.extend(
+                        migrations
+                            .iter()
+                            .map(|path| database_link(path, LinkType::Sql)),
+                    );
+                    if let Some(connection_url) = connection_url {
+                        sql_config
+                            .links
+                            .push(database_link(connection_url, LinkType::Postgres));
+                    }
+                    config = config.merge_right(sql_config);
+                }
             }
         }
 
@@ -181,6 +243,17 @@ impl Generator {
     }
 }
 
+fn database_link(src: &str, type_of: LinkType) -> Link {
+    Link {
+        id: None,
+        src: src.to_string(),
+        type_of,
+        headers: None,
+        meta: None,
+        proto_paths: None,
+    }
+}
+
 // this function resolves all the names to fully-qualified syntax in descriptors
 // that is important for generation to work
 // TODO: probably we can drop this in case the config_reader will use
@@ -210,7 +283,9 @@ pub mod test {
     use url::Url;
 
     use super::Generator;
+    use crate::core::config::LinkType;
     use crate::core::config::transformer::Preset;
+    use crate::core::generator::DatabaseOptions;
     use crate::core::generator::generator::Input;
     use crate::core::http::Method;
     use crate::core::proto_reader::ProtoMetadata;
@@ -311,6 +386,38 @@ pub mod test {
         Ok(())
     }
 
+    #[test]
+    fn should_generate_config_from_sql_migrations() -> anyhow::Result<()> {
+        let migration = "CREATE TABLE users (id SERIAL PRIMARY KEY, name TEXT NOT NULL);";
+        let database_schema =
+            crate::core::postgres::sql_parser::parse_migrations(&[migration.to_string()])?;
+
+        let cfg_module = Generator::default()
+            .inputs(vec![Input::Sql {
+                database_schema,
+                migrations: vec!["migrations/001_init.sql".to_string()],
+                connection_url: Some("postgres://localhost/app".to_string()),
+                options: DatabaseOptions::default(),
+            }])
+            .generate(false)?;
+
+        let links = cfg_module
+            .config()
+            .links
+            .iter()
+            .map(|link| (link.type_of.clone(), link.src.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            links,
+            vec![
+                (LinkType::Sql, "migrations/001_init.sql"),
+                (LinkType::Postgres, "postgres://localhost/app"),
+            ]
+        );
+        assert!(cfg_module.config().types.contains_key("Users"));
+        Ok(())
+    }
+
     #[test]
     fn should_generate_config_from_configs() -> anyhow::Result<()> {
         let cfg_module = Generator::default()
@@ -3,6 +3,7 @@ mod operation_generator;
 mod schema_generator;
 mod types_generator;
 
+pub use http_directive_generator::HttpDirectiveGenerator;
 pub use operation_generator::OperationTypeGenerator;
 pub use schema_generator::SchemaGenerator;
 pub use types_generator::GraphQLTypesGenerator;
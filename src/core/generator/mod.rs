@@ -1,13 +1,15 @@
 mod from_database;
 mod from_json;
+mod from_json_schema;
 mod from_proto;
 mod generator;
 mod graphql_type;
 mod json;
 mod proto;
 
-pub use from_database::from_database;
+pub use from_database::{CrudOperation, DatabaseOptions, NamingConvention, from_database};
 pub use from_json::{FromJsonGenerator, RequestSample};
+pub use from_json_schema::{JsonSchemaSample, from_json_schema};
 pub use generator::{Generator, Input};
 
 use crate::core::counter::{Count, Counter};
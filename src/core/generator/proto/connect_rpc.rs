@@ -111,6 +111,8 @@ mod tests {
             dedupe: None,
             select: None,
             on_response_body: None,
+            compression: None,
+            protocol: None,
         };
 
         let http = Http::from(grpc);
@@ -131,6 +133,8 @@ mod tests {
             dedupe: None,
             select: None,
             on_response_body: None,
+            compression: None,
+            protocol: None,
         };
 
         let http = Http::from(grpc);
@@ -149,6 +153,8 @@ mod tests {
             dedupe: None,
             select: None,
             on_response_body: None,
+            compression: None,
+            protocol: None,
         };
 
         let http = Http::from(grpc);
@@ -184,6 +190,8 @@ mod tests {
             dedupe: Some(true),
             select: Some(Value::String("select_value".to_string())),
             on_response_body: Some("on_response_body_value".to_string()),
+            compression: None,
+            protocol: None,
         };
 
         let http = Http::from(grpc);
@@ -30,6 +30,7 @@ impl GraphqlDataLoader {
         DataLoader::new(self)
             .delay(Duration::from_millis(batch.delay as u64))
             .max_batch_size(batch.max_size.unwrap_or_default())
+            .name("graphql")
     }
 }
 
@@ -0,0 +1,208 @@
+use std::borrow::Cow;
+use std::io::{Read, Write};
+use std::time::Duration;
+
+use anyhow::{Result, bail};
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use http::HeaderMap;
+use http::header::{CONTENT_ENCODING, HeaderValue};
+
+use crate::core::config::GrpcCompression;
+
+pub static GRPC_ENCODING: &str = "grpc-encoding";
+pub static GRPC_ACCEPT_ENCODING: &str = "grpc-accept-encoding";
+pub static GRPC_TIMEOUT: &str = "grpc-timeout";
+
+/// Encodings advertised in `grpc-accept-encoding`.
+pub static ACCEPTED_ENCODINGS: HeaderValue = HeaderValue::from_static("gzip");
+
+/// Largest value of a `grpc-timeout` header, which allows at most 8 digits.
+const MAX_TIMEOUT_VALUE: u64 = 99_999_999;
+
+/// Largest decompressed message, the default receive limit of gRPC
+/// implementations.
+pub const MAX_MESSAGE_SIZE: u64 = 4 * 1024 * 1024;
+
+/// A compressed message that decompresses to more than `MAX_MESSAGE_SIZE`
+/// bytes, answered with `RESOURCE_EXHAUSTED`.
+#[derive(Debug, thiserror::Error)]
+#[error("Decompressed message is larger than {MAX_MESSAGE_SIZE} bytes")]
+pub struct MessageTooLarge;
+
+///
+/// # Errors
+///
+/// Returns an error if the message can't be compressed.
+pub fn compress(compression: GrpcCompression, message: &[u8]) -> Result<Vec<u8>> {
+    match compression {
+        GrpcCompression::Gzip => {
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(message)?;
+            Ok(encoder.finish()?)
+        }
+    }
+}
+
+///
+/// # Errors
+///
+/// Returns an error if the message isn't validly compressed, or
+/// [`MessageTooLarge`] if it decompresses to more than `MAX_MESSAGE_SIZE`
+/// bytes.
+pub fn decompress(compression: GrpcCompression, message: &[u8]) -> Result<Vec<u8>> {
+    match compression {
+        GrpcCompression::Gzip => {
+            let mut decoded = Vec::new();
+            // One byte past the limit tells a message of exactly the limit apart
+            // from a larger one
+            let mut decoder = GzDecoder::new(message).take(MAX_MESSAGE_SIZE + 1);
+            decoder.read_to_end(&mut decoded)?;
+            if decoder.limit() == 0 {
+                return Err(MessageTooLarge.into());
+            }
+            Ok(decoded)
+        }
+    }
+}
+
+/// The encoding of compressed messages, read from the `grpc-encoding` header
+/// of an upstream response or of a call served by the gateway.
+///
+/// # Errors
+///
+/// Returns an error if the encoding isn't supported.
+pub fn message_encoding(headers: &HeaderMap) -> Result<Option<GrpcCompression>> {
+    read_encoding(headers, GRPC_ENCODING)
+}
+
+/// The encoding of an unframed Connect response, read from its
+/// `content-encoding` header.
+///
+/// # Errors
+///
+/// Returns an error if the upstream used an encoding that wasn't accepted.
+pub fn content_encoding(headers: &HeaderMap) -> Result<Option<GrpcCompression>> {
+    read_encoding(headers, CONTENT_ENCODING.as_str())
+}
+
+fn read_encoding(headers: &HeaderMap, name: &str) -> Result<Option<GrpcCompression>> {
+    let Some(encoding) = headers.get(name) else {
+        return Ok(None);
+    };
+
+    match encoding.to_str()? {
+        "identity" => Ok(None),
+        "gzip" => Ok(Some(GrpcCompression::Gzip)),
+        encoding => bail!("Unsupported {name}: {encoding}"),
+    }
+}
+
+/// The payload of a message, decompressed when the compressed flag of its
+/// frame is set.
+///
+/// # Errors
+///
+/// Returns an error if the message is compressed without an encoding, or
+/// can't be decompressed.
+pub fn decode_message(
+    compressed: bool,
+    payload: &[u8],
+    encoding: Option<GrpcCompression>,
+) -> Result<Cow<'_, [u8]>> {
+    if !compressed {
+        return Ok(Cow::Borrowed(payload));
+    }
+
+    match encoding {
+        Some(encoding) => Ok(Cow::Owned(decompress(encoding, payload)?)),
+        None => bail!("Received a compressed message without grpc-encoding"),
+    }
+}
+
+/// Formats a deadline as a `grpc-timeout` header, in milliseconds when it
+/// fits in the 8 digits allowed and in seconds otherwise.
+#[must_use]
+pub fn grpc_timeout(timeout: Duration) -> HeaderValue {
+    let millis = timeout.as_millis();
+    let value = if millis <= u128::from(MAX_TIMEOUT_VALUE) {
+        format!("{millis}m")
+    } else {
+        format!("{}S", timeout.as_secs().min(MAX_TIMEOUT_VALUE))
+    };
+
+    HeaderValue::from_str(&value).unwrap_or_else(|_| HeaderValue::from_static("0m"))
+}
+
+#[cfg(test)]
+mod tests {
+    #![expect(clippy::unwrap_used, reason = "test code")]
+    use super::*;
+
+    #[test]
+    fn gzip_round_trip() {
+        let compressed = compress(GrpcCompression::Gzip, b"hello gzip").unwrap();
+        assert_ne!(compressed.as_slice(), b"hello gzip");
+
+        let message = decode_message(true, &compressed, Some(GrpcCompression::Gzip)).unwrap();
+        assert_eq!(message.as_ref(), b"hello gzip");
+    }
+
+    #[test]
+    fn limits_decompressed_size() {
+        let limit = usize::try_from(MAX_MESSAGE_SIZE).unwrap();
+
+        let compressed = compress(GrpcCompression::Gzip, &vec![0; limit]).unwrap();
+        assert_eq!(
+            decompress(GrpcCompression::Gzip, &compressed)
+                .unwrap()
+                .len(),
+            limit
+        );
+
+        let compressed = compress(GrpcCompression::Gzip, &vec![0; limit + 1]).unwrap();
+        let error = decompress(GrpcCompression::Gzip, &compressed).unwrap_err();
+        assert!(error.is::<MessageTooLarge>());
+    }
+
+    #[test]
+    fn compressed_message_requires_encoding() {
+        assert!(decode_message(true, b"hello", None).is_err());
+        assert_eq!(
+            decode_message(false, b"hello", None).unwrap().as_ref(),
+            b"hello"
+        );
+    }
+
+    #[test]
+    fn reads_message_encoding() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(message_encoding(&headers).unwrap(), None);
+
+        headers.insert(GRPC_ENCODING, HeaderValue::from_static("identity"));
+        assert_eq!(message_encoding(&headers).unwrap(), None);
+
+        headers.insert(GRPC_ENCODING, HeaderValue::from_static("gzip"));
+        assert_eq!(
+            message_encoding(&headers).unwrap(),
+            Some(GrpcCompression::Gzip)
+        );
+
+        headers.insert(GRPC_ENCODING, HeaderValue::from_static("snappy"));
+        assert!(message_encoding(&headers).is_err());
+
+        headers.insert(CONTENT_ENCODING, HeaderValue::from_static("gzip"));
+        assert_eq!(
+            content_encoding(&headers).unwrap(),
+            Some(GrpcCompression::Gzip)
+        );
+    }
+
+    #[test]
+    fn formats_grpc_timeout() {
+        assert_eq!(grpc_timeout(Duration::from_millis(1500)), "1500m");
+        assert_eq!(grpc_timeout(Duration::ZERO), "0m");
+        assert_eq!(grpc_timeout(Duration::from_secs(200_000)), "200000S");
+    }
+}
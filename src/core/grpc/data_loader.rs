@@ -31,6 +31,7 @@ impl GrpcDataLoader {
         DataLoader::new(self)
             .delay(Duration::from_millis(batch.delay as u64))
             .max_batch_size(batch.max_size.unwrap_or_default())
+            .name("grpc")
     }
 
     async fn load_dedupe_only(
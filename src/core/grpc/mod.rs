@@ -1,9 +1,13 @@
+pub mod compression;
 pub mod data_loader;
 pub mod data_loader_request;
 pub mod protobuf;
+pub mod protocol;
 pub mod request;
 pub mod request_template;
+pub mod status;
 pub mod stream;
+pub mod well_known;
 
 pub use data_loader_request::DataLoaderRequest;
 pub use request_template::RequestTemplate;
@@ -11,9 +11,29 @@ use prost_reflect::{
 };
 use serde_json::Deserializer;
 
+use super::compression::{compress, decode_message, decompress};
+use super::well_known;
 use crate::core::blueprint::GrpcMethod;
+use crate::core::config::{GrpcCompression, GrpcProtocol};
 
 fn to_message(descriptor: &MessageDescriptor, input: &str) -> Result<DynamicMessage> {
+    if well_known::has_field_mask(descriptor) {
+        let mut value: serde_json::Value = serde_json::from_str(input).with_context(|| {
+            format!(
+                "Failed to parse input according to type {}",
+                descriptor.full_name()
+            )
+        })?;
+        well_known::field_masks_to_strings(descriptor, &mut value);
+
+        return DynamicMessage::deserialize(descriptor.clone(), value).with_context(|| {
+            format!(
+                "Failed to parse input according to type {}",
+                descriptor.full_name()
+            )
+        });
+    }
+
     let mut deserializer = Deserializer::from_str(input);
     let message =
         DynamicMessage::deserialize(descriptor.clone(), &mut deserializer).with_context(|| {
@@ -27,14 +47,27 @@ fn to_message(descriptor: &MessageDescriptor, input: &str) -> Result<DynamicMess
     Ok(message)
 }
 
-fn message_to_bytes(message: &DynamicMessage) -> Result<Vec<u8>> {
-    let mut buf: Vec<u8> = Vec::with_capacity(message.encoded_len() + 5);
-    // set compression flag
-    buf.put_u8(0);
-    // next 4 bytes should encode message length
-    buf.put_u32(u32::try_from(message.encoded_len()).unwrap_or(u32::MAX));
-    // encode the message itself
-    message.encode(&mut buf)?;
+fn message_to_bytes(
+    message: &DynamicMessage,
+    compression: Option<GrpcCompression>,
+) -> Result<Vec<u8>> {
+    let Some(compression) = compression else {
+        let mut buf: Vec<u8> = Vec::with_capacity(message.encoded_len() + 5);
+        // set compression flag
+        buf.put_u8(0);
+        // next 4 bytes should encode message length
+        buf.put_u32(u32::try_from(message.encoded_len()).unwrap_or(u32::MAX));
+        // encode the message itself
+        message.encode(&mut buf)?;
+
+        return Ok(buf);
+    };
+
+    let payload = compress(compression, &message.encode_to_vec())?;
+    let mut buf: Vec<u8> = Vec::with_capacity(payload.len() + 5);
+    buf.put_u8(1);
+    buf.put_u32(u32::try_from(payload.len()).unwrap_or(u32::MAX));
+    buf.extend_from_slice(&payload);
 
     Ok(buf)
 }
@@ -149,7 +182,14 @@ pub struct ProtobufOperation {
     pub method: MethodDescriptor,
     pub input_type: MessageDescriptor,
     pub output_type: MessageDescriptor,
+    /// Encoding the request messages are compressed with.
+    pub compression: Option<GrpcCompression>,
+    /// Protocol the messages are framed with.
+    pub protocol: GrpcProtocol,
     serialize_options: SerializeOptions,
+    /// Whether the response has field masks, whose paths are split into
+    /// lists.
+    has_field_mask: bool,
 }
 
 impl Eq for ProtobufOperation {}
@@ -159,10 +199,11 @@ impl PartialEq for ProtobufOperation {
         self.method.eq(&other.method)
             && self.input_type.eq(&other.input_type)
             && self.output_type.eq(&other.output_type)
+            && self.compression.eq(&other.compression)
+            && self.protocol.eq(&other.protocol)
     }
 }
 
-// TODO: support compression
 impl ProtobufOperation {
     #[must_use]
     pub fn new(
@@ -170,11 +211,16 @@ impl ProtobufOperation {
         input_type: MessageDescriptor,
         output_type: MessageDescriptor,
     ) -> Self {
+        let has_field_mask = well_known::has_field_mask(&output_type);
+
         Self {
             method,
             input_type,
             output_type,
+            compression: None,
+            protocol: GrpcProtocol::default(),
             serialize_options: SerializeOptions::default().skip_default_fields(false),
+            has_field_mask,
         }
     }
     #[must_use]
@@ -192,9 +238,50 @@ impl ProtobufOperation {
     ///
     /// Returns an error if the operation fails.
     pub fn convert_input(&self, input: &str) -> Result<Vec<u8>> {
+        if self.method.is_client_streaming() {
+            return self.convert_stream_input(input);
+        }
+
         let message = to_message(&self.input_type, input)?;
 
-        message_to_bytes(&message)
+        self.encode_message(&message)
+    }
+
+    /// Encode a request message, unframed for Connect unary calls and in a
+    /// length-prefixed frame otherwise.
+    fn encode_message(&self, message: &DynamicMessage) -> Result<Vec<u8>> {
+        match (self.protocol, self.compression) {
+            (GrpcProtocol::Connect, None) => Ok(message.encode_to_vec()),
+            (GrpcProtocol::Connect, Some(compression)) => {
+                compress(compression, &message.encode_to_vec())
+            }
+            (GrpcProtocol::Grpc | GrpcProtocol::GrpcWeb, compression) => {
+                message_to_bytes(message, compression)
+            }
+        }
+    }
+
+    /// Encode the request of a client-streaming method, sending a frame for
+    /// each element of a list input and a single frame otherwise.
+    fn convert_stream_input(&self, input: &str) -> Result<Vec<u8>> {
+        let input: serde_json::Value = serde_json::from_str(input).with_context(|| {
+            format!(
+                "Failed to parse input according to type {}",
+                self.input_type.full_name()
+            )
+        })?;
+        let inputs = match input {
+            serde_json::Value::Array(inputs) => inputs,
+            input => vec![input],
+        };
+
+        let mut buf = Vec::new();
+        for input in inputs {
+            let message = to_message(&self.input_type, &input.to_string())?;
+            buf.extend(message_to_bytes(&message, self.compression)?);
+        }
+
+        Ok(buf)
     }
 
     ///
@@ -237,7 +324,7 @@ impl ProtobufOperation {
             ),
         );
 
-        message_to_bytes(&message).map(|result| (result, ids))
+        self.encode_message(&message).map(|result| (result, ids))
     }
 
     ///
@@ -245,6 +332,20 @@ impl ProtobufOperation {
     ///
     /// Returns an error if the operation fails.
     pub fn convert_output<T: serde::de::DeserializeOwned>(&self, bytes: &[u8]) -> Result<T> {
+        self.convert_encoded_output(bytes, None)
+    }
+
+    /// Decode a gRPC-framed response whose compressed messages use
+    /// `encoding`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the operation fails.
+    pub fn convert_encoded_output<T: serde::de::DeserializeOwned>(
+        &self,
+        bytes: &[u8],
+        encoding: Option<GrpcCompression>,
+    ) -> Result<T> {
         if bytes.len() < 5 {
             bail!("Empty response");
         }
@@ -252,18 +353,26 @@ impl ProtobufOperation {
         // see https://www.oreilly.com/library/view/grpc-up-and/9781492058328/ch04.html#:~:text=Length%2DPrefixed%20Message%20Framing
         // 1st byte - compression flag
         // 2-4th bytes - length of the message
-        let message =
-            DynamicMessage::decode(self.output_type.clone(), &bytes[5..]).with_context(|| {
-                format!(
-                    "Failed to parse response for type {}",
-                    self.output_type.full_name()
-                )
-            })?;
+        let payload = decode_message(bytes[0] == 1, &bytes[5..], encoding)?;
 
-        let mut serializer = serde_json::Serializer::new(vec![]);
-        message.serialize_with_options(&mut serializer, &self.serialize_options)?;
-        let json = serde_json::from_slice::<T>(serializer.into_inner().as_ref())?;
-        Ok(json)
+        self.convert_output_raw(&payload)
+    }
+
+    /// Decode an unframed response, like the ones of Connect unary calls,
+    /// compressed with `encoding`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the operation fails.
+    pub fn convert_unframed_output<T: serde::de::DeserializeOwned>(
+        &self,
+        bytes: &[u8],
+        encoding: Option<GrpcCompression>,
+    ) -> Result<T> {
+        match encoding {
+            Some(encoding) => self.convert_output_raw(&decompress(encoding, bytes)?),
+            None => self.convert_output_raw(bytes),
+        }
     }
 
     /// Decode a raw protobuf payload (without the 5-byte gRPC frame header).
@@ -280,6 +389,15 @@ impl ProtobufOperation {
                 )
             })?;
 
+        if self.has_field_mask {
+            let mut json = message.serialize_with_options(
+                serde_json::value::Serializer,
+                &self.serialize_options,
+            )?;
+            well_known::field_masks_to_lists(&self.output_type, &mut json);
+            return Ok(serde_json::from_value::<T>(json)?);
+        }
+
         let mut serializer = serde_json::Serializer::new(vec![]);
         message.serialize_with_options(&mut serializer, &self.serialize_options)?;
         let json = serde_json::from_slice::<T>(serializer.into_inner().as_ref())?;
@@ -448,6 +566,62 @@ pub mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn greetings_proto_file_with_gzip() -> Result<()> {
+        let grpc_method = GrpcMethod::try_from("greetings.Greeter.SayHello").unwrap();
+        let file = ProtobufSet::from_proto_file(get_proto_file(protobuf::GREETINGS).await?)?;
+        let service = file.find_service(&grpc_method)?;
+        let mut operation = service.find_operation(&grpc_method)?;
+
+        let uncompressed = operation.convert_input(r#"{ "name": "test" }"#)?;
+        operation.compression = Some(GrpcCompression::Gzip);
+        let input = operation.convert_input(r#"{ "name": "test" }"#)?;
+
+        assert_eq!(input[0], 1);
+        assert_eq!(
+            decode_message(true, &input[5..], operation.compression)?.as_ref(),
+            &uncompressed[5..]
+        );
+
+        let payload = compress(GrpcCompression::Gzip, b"\n\x0ctest message")?;
+        let mut output = vec![1];
+        output.extend_from_slice(&u32::try_from(payload.len())?.to_be_bytes());
+        output.extend_from_slice(&payload);
+
+        let parsed = operation
+            .convert_encoded_output::<serde_json::Value>(&output, Some(GrpcCompression::Gzip))?;
+        assert_eq!(parsed, json!({ "message": "test message" }));
+        assert!(
+            operation
+                .convert_output::<serde_json::Value>(&output)
+                .is_err()
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn streaming_proto_file_client_stream() -> Result<()> {
+        let grpc_method = GrpcMethod::try_from("streaming.EventService.RecordEvents").unwrap();
+        let file = ProtobufSet::from_proto_file(get_proto_file(protobuf::STREAMING).await?)?;
+        let service = file.find_service(&grpc_method)?;
+        let operation = service.find_operation(&grpc_method)?;
+
+        let input = operation.convert_input(r#"[{ "topic": "a" }, { "topic": "bc" }]"#)?;
+        assert_eq!(input, b"\0\0\0\0\x03\n\x01a\0\0\0\0\x04\n\x02bc");
+
+        let input = operation.convert_input(r#"{ "topic": "a" }"#)?;
+        assert_eq!(input, b"\0\0\0\0\x03\n\x01a");
+
+        assert!(operation.convert_input("[]")?.is_empty());
+
+        let output = b"\0\0\0\0\x02\x08\x02";
+        let parsed = operation.convert_output::<serde_json::Value>(output)?;
+        assert_eq!(parsed, json!({ "count": 2 }));
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn news_proto_file() -> Result<()> {
         let grpc_method = GrpcMethod::try_from("news.NewsService.GetNews").unwrap();
@@ -0,0 +1,243 @@
+use std::time::Duration;
+
+use anyhow::{Result, bail};
+use async_graphql_value::{ConstValue, Name};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD_NO_PAD;
+use bytes::{Bytes, BytesMut};
+use http::HeaderMap;
+use http::header::{HeaderName, HeaderValue};
+use indexmap::IndexMap;
+use serde::Deserialize;
+use tonic::Code;
+
+use super::compression::{GRPC_TIMEOUT, grpc_timeout};
+use super::protobuf::ProtobufOperation;
+use super::status::{code_from_name, decode_error_details};
+use crate::core::config::GrpcProtocol;
+use crate::core::ir::Error;
+
+pub static CONNECT_PROTOCOL_VERSION: &str = "connect-protocol-version";
+pub static CONNECT_TIMEOUT: &str = "connect-timeout-ms";
+pub static X_GRPC_WEB: &str = "x-grpc-web";
+
+static GRPC_MIME_TYPE: HeaderValue = HeaderValue::from_static("application/grpc");
+static GRPC_WEB_MIME_TYPE: HeaderValue = HeaderValue::from_static("application/grpc-web+proto");
+static CONNECT_MIME_TYPE: HeaderValue = HeaderValue::from_static("application/proto");
+
+/// Flag of the gRPC-Web frame holding the trailers.
+const TRAILERS_FLAG: u8 = 0x80;
+
+/// Largest value of a `connect-timeout-ms` header, which allows at most 10
+/// digits.
+const MAX_CONNECT_TIMEOUT: u64 = 9_999_999_999;
+
+/// The content type of the requests sent with `protocol`.
+#[must_use]
+pub fn content_type(protocol: GrpcProtocol) -> HeaderValue {
+    match protocol {
+        GrpcProtocol::Grpc => GRPC_MIME_TYPE.clone(),
+        GrpcProtocol::GrpcWeb => GRPC_WEB_MIME_TYPE.clone(),
+        GrpcProtocol::Connect => CONNECT_MIME_TYPE.clone(),
+    }
+}
+
+/// The header telling the upstream the time left before the deadline of a
+/// call.
+#[must_use]
+pub fn timeout_header(protocol: GrpcProtocol, timeout: Duration) -> (HeaderName, HeaderValue) {
+    match protocol {
+        GrpcProtocol::Grpc | GrpcProtocol::GrpcWeb => {
+            (HeaderName::from_static(GRPC_TIMEOUT), grpc_timeout(timeout))
+        }
+        GrpcProtocol::Connect => {
+            let millis = u64::try_from(timeout.as_millis()).unwrap_or(u64::MAX);
+            (
+                HeaderName::from_static(CONNECT_TIMEOUT),
+                HeaderValue::from(millis.min(MAX_CONNECT_TIMEOUT)),
+            )
+        }
+    }
+}
+
+/// Splits a gRPC-Web response body into its message frames and the trailers
+/// sent in its last frame.
+///
+/// # Errors
+///
+/// Returns an error if the body ends with an incomplete frame or the
+/// trailers aren't valid headers.
+pub fn split_trailers(body: &Bytes) -> Result<(Bytes, HeaderMap)> {
+    let mut body = body.clone();
+    let mut messages = BytesMut::new();
+    let mut trailers = HeaderMap::new();
+
+    while !body.is_empty() {
+        if body.len() < 5 {
+            bail!("Incomplete gRPC-Web frame");
+        }
+
+        let flag = body[0];
+        let len = u32::from_be_bytes([body[1], body[2], body[3], body[4]]) as usize;
+        if body.len() < 5 + len {
+            bail!("Incomplete gRPC-Web frame");
+        }
+
+        let frame = body.split_to(5 + len);
+        if flag & TRAILERS_FLAG == 0 {
+            messages.extend_from_slice(&frame);
+        } else {
+            parse_trailers(&frame[5..], &mut trailers)?;
+        }
+    }
+
+    Ok((messages.freeze(), trailers))
+}
+
+/// Parses the trailers of a gRPC-Web response, sent like HTTP/1.1 headers.
+fn parse_trailers(block: &[u8], trailers: &mut HeaderMap) -> Result<()> {
+    for line in std::str::from_utf8(block)?.split("\r\n") {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+
+        trailers.append(
+            HeaderName::from_bytes(name.trim().to_ascii_lowercase().as_bytes())?,
+            HeaderValue::from_str(value.trim())?,
+        );
+    }
+
+    Ok(())
+}
+
+/// The JSON body of a failed Connect unary call.
+#[derive(Deserialize)]
+struct ConnectError {
+    code: String,
+    #[serde(default)]
+    message: String,
+    #[serde(default)]
+    details: Vec<ConnectErrorDetail>,
+}
+
+#[derive(Deserialize)]
+struct ConnectErrorDetail {
+    #[serde(rename = "type")]
+    type_name: String,
+    /// The encoded detail message, in base64 with optional padding.
+    value: String,
+}
+
+/// The error of a failed Connect unary call, from the JSON body of its
+/// response. Returns `None` when the body isn't a Connect error.
+#[must_use]
+pub fn connect_error(operation: &ProtobufOperation, body: &str) -> Option<Error> {
+    let error: ConnectError = serde_json::from_str(body).ok()?;
+    let code = code_from_name(&error.code.to_uppercase()).unwrap_or(Code::Unknown as i32);
+
+    let details = error
+        .details
+        .iter()
+        .filter_map(|detail| {
+            let value = STANDARD_NO_PAD
+                .decode(detail.value.trim_end_matches('='))
+                .inspect_err(|e| tracing::error!("Error while decoding Connect error detail: {e}"))
+                .ok()?;
+            Some((detail.type_name.as_str(), value))
+        })
+        .collect::<Vec<_>>();
+    let (status_details, grpc_extensions) = decode_error_details(
+        operation,
+        details
+            .iter()
+            .map(|(type_name, value)| (*type_name, value.as_slice())),
+    );
+
+    let mut obj = IndexMap::new();
+    obj.insert(Name::new("code"), code.into());
+    obj.insert(Name::new("message"), error.message.clone().into());
+    obj.insert(Name::new("details"), ConstValue::List(status_details));
+
+    Some(Error::GRPC {
+        grpc_code: code,
+        grpc_description: Code::from_i32(code).description().to_owned(),
+        grpc_status_message: error.message,
+        grpc_status_details: ConstValue::Object(obj),
+        grpc_extensions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #![expect(clippy::unwrap_used, reason = "test code")]
+    use gqlforge_fixtures::protobuf;
+    use serde_json::json;
+
+    use super::*;
+    use crate::core::blueprint::GrpcMethod;
+    use crate::core::grpc::protobuf::ProtobufSet;
+
+    #[test]
+    fn splits_grpc_web_trailers() {
+        let trailers = b"grpc-status: 5\r\ngrpc-message: Not Found\r\n";
+        let mut body = b"\0\0\0\0\x02\x08\x02".to_vec();
+        body.push(TRAILERS_FLAG);
+        body.extend_from_slice(&u32::try_from(trailers.len()).unwrap().to_be_bytes());
+        body.extend_from_slice(trailers);
+
+        let (messages, trailers) = split_trailers(&Bytes::from(body)).unwrap();
+
+        assert_eq!(messages.as_ref(), b"\0\0\0\0\x02\x08\x02");
+        assert_eq!(trailers.get("grpc-status").unwrap(), "5");
+        assert_eq!(trailers.get("grpc-message").unwrap(), "Not Found");
+    }
+
+    #[test]
+    fn rejects_incomplete_grpc_web_frame() {
+        assert!(split_trailers(&Bytes::from_static(b"\0\0\0\0\x05\x08")).is_err());
+    }
+
+    #[test]
+    fn formats_timeout_headers() {
+        let (name, value) = timeout_header(GrpcProtocol::Connect, Duration::from_millis(1500));
+        assert_eq!(name, CONNECT_TIMEOUT);
+        assert_eq!(value, "1500");
+
+        let (name, value) = timeout_header(GrpcProtocol::GrpcWeb, Duration::from_millis(1500));
+        assert_eq!(name, GRPC_TIMEOUT);
+        assert_eq!(value, "1500m");
+    }
+
+    #[test]
+    fn reads_connect_errors() {
+        let file_descriptor_set =
+            protox::compile([protobuf::GREETINGS, protobuf::ERRORS], [protobuf::SELF]).unwrap();
+        let grpc_method = GrpcMethod::try_from("greetings.Greeter.SayHello").unwrap();
+        let file = ProtobufSet::from_proto_file(file_descriptor_set).unwrap();
+        let operation = file
+            .find_service(&grpc_method)
+            .unwrap()
+            .find_operation(&grpc_method)
+            .unwrap();
+
+        // google.rpc.ErrorInfo { reason: "MISSING" }
+        let body = json!({
+            "code": "not_found",
+            "message": "greeting not found",
+            "details": [{ "type": "google.rpc.ErrorInfo", "value": "CgdNSVNTSU5H" }]
+        });
+        let error = connect_error(&operation, &body.to_string()).unwrap();
+
+        let Error::GRPC { grpc_code, grpc_status_message, grpc_extensions, .. } = error else {
+            panic!("Expected a gRPC error");
+        };
+        assert_eq!(grpc_code, Code::NotFound as i32);
+        assert_eq!(grpc_status_message, "greeting not found");
+        assert_eq!(
+            grpc_extensions.get("reason"),
+            Some(&ConstValue::String("MISSING".to_string()))
+        );
+
+        assert!(connect_error(&operation, "Not Found").is_none());
+    }
+}
@@ -2,13 +2,17 @@ use std::pin::Pin;
 
 use anyhow::{Result, bail};
 use async_graphql_value::ConstValue;
-use futures_util::{Stream, StreamExt};
+use futures_util::Stream;
 use http::{HeaderMap, Method};
+use http_body_util::BodyExt;
 use reqwest::Request;
 use url::Url;
 
+use super::compression::message_encoding;
 use super::protobuf::ProtobufOperation;
+use super::protocol::{connect_error, split_trailers};
 use super::stream::GrpcFrameDecoder;
+use crate::core::config::GrpcProtocol;
 use crate::core::http::Response;
 use crate::core::ir::Error;
 use crate::core::runtime::TargetRuntime;
@@ -33,7 +37,29 @@ pub async fn execute_grpc_request(
     operation: &ProtobufOperation,
     request: Request,
 ) -> Result<Response<async_graphql::Value>> {
-    let response = runtime.http2_only.execute(request).await?;
+    let response = match operation.protocol {
+        GrpcProtocol::Grpc => runtime.http2_only.execute(request).await?,
+        GrpcProtocol::GrpcWeb => {
+            // The status of gRPC-Web calls is sent at the end of the body
+            let mut response = runtime.http.execute(request).await?;
+            let (body, trailers) = split_trailers(&response.body)?;
+            response.headers.extend(trailers);
+            response.body = body;
+            response
+        }
+        GrpcProtocol::Connect => {
+            return match runtime.http.execute(request).await {
+                Ok(response) => response.to_grpc_value(operation),
+                Err(error) => {
+                    let connect_error = match error.downcast_ref::<Error>() {
+                        Some(Error::HTTP { body, .. }) => connect_error(operation, body),
+                        _ => None,
+                    };
+                    Err(connect_error.map_or(error, anyhow::Error::from))
+                }
+            };
+        }
+    };
 
     let grpc_status = response
         .headers
@@ -44,7 +70,7 @@ pub async fn execute_grpc_request(
         return if grpc_status.is_none() || grpc_status == Some("0") {
             response.to_grpc_value(operation)
         } else {
-            Err(response.to_grpc_error(operation))
+            Err(response.to_grpc_error(operation).into())
         };
     }
     bail!("Failed to execute request");
@@ -70,28 +96,62 @@ pub async fn execute_grpc_streaming_request(
         );
     }
 
+    // Calls failing before any message respond with the status in the headers
+    if let Some(grpc_status) = response.headers().get(GRPC_STATUS)
+        && grpc_status != "0"
+    {
+        let response = Response {
+            status: response.status(),
+            headers: response.headers().clone(),
+            body: bytes::Bytes::new(),
+        };
+        return Err(response.to_grpc_error(operation).into());
+    }
+
+    let encoding = message_encoding(response.headers())?;
     let operation = operation.clone();
-    let byte_stream = response.bytes_stream();
+    let (parts, mut body) = http::Response::<reqwest::Body>::from(response).into_parts();
 
     let stream = async_stream::stream! {
-        let mut decoder = GrpcFrameDecoder::new();
-
-        futures_util::pin_mut!(byte_stream);
-        while let Some(chunk_result) = byte_stream.next().await {
-            match chunk_result {
-                Ok(chunk) => {
-                    let frames = decoder.decode(&chunk);
-                    for frame in frames {
-                        match operation.convert_output_raw(&frame) {
-                            Ok(value) => yield Ok(value),
-                            Err(e) => yield Err(Error::IO(format!("Failed to decode gRPC frame: {e}"))),
-                        }
+        let mut decoder = GrpcFrameDecoder::with_encoding(encoding);
+
+        while let Some(frame_result) = body.frame().await {
+            let frame = match frame_result {
+                Ok(frame) => frame,
+                Err(e) => {
+                    yield Err(Error::IO(format!("Stream error: {e}")));
+                    break;
+                }
+            };
+            let chunk = match frame.into_data() {
+                Ok(chunk) => chunk,
+                // Calls failing after a message send the status in the trailers
+                Err(frame) => {
+                    if let Some(trailers) = frame.trailers_ref()
+                        && trailers.get(GRPC_STATUS).is_some_and(|status| status != "0")
+                    {
+                        let response = Response {
+                            status: parts.status,
+                            headers: trailers.clone(),
+                            body: bytes::Bytes::new(),
+                        };
+                        yield Err(response.to_grpc_error(&operation));
                     }
+                    continue;
                 }
+            };
+            let frames = match decoder.decode(&chunk) {
+                Ok(frames) => frames,
                 Err(e) => {
-                    yield Err(Error::IO(format!("Stream error: {e}")));
+                    yield Err(Error::IO(format!("Failed to decode gRPC frame: {e}")));
                     break;
                 }
+            };
+            for frame in frames {
+                match operation.convert_output_raw(&frame) {
+                    Ok(value) => yield Ok(value),
+                    Err(e) => yield Err(Error::IO(format!("Failed to decode gRPC frame: {e}"))),
+                }
             }
         }
     };
@@ -102,14 +162,21 @@ pub async fn execute_grpc_streaming_request(
 #[cfg(test)]
 mod tests {
     #![expect(clippy::unwrap_used, reason = "test code")]
+    use std::convert::Infallible;
     use std::sync::Arc;
 
-    use anyhow::Result;
+    use anyhow::{Result, bail};
     use async_trait::async_trait;
     use bytes::Bytes;
+    use futures_util::StreamExt;
     use gqlforge_fixtures::protobuf;
     use http::header::HeaderMap;
     use http::{Method, StatusCode};
+    use http_body_util::StreamBody;
+    use hyper::body::Frame;
+    use hyper::service::service_fn;
+    use hyper_util::rt::{TokioExecutor, TokioIo};
+    use hyper_util::server::conn::auto::Builder;
     use reqwest::Request;
     use serde_json::json;
     use tonic::{Code, Status};
@@ -117,7 +184,8 @@ mod tests {
     use crate::core::HttpIO;
     use crate::core::blueprint::GrpcMethod;
     use crate::core::grpc::protobuf::{ProtobufOperation, ProtobufSet};
-    use crate::core::grpc::request::execute_grpc_request;
+    use crate::core::grpc::request::{execute_grpc_request, execute_grpc_streaming_request};
+    use crate::core::grpc::stream::GrpcFrameDecoder;
     use crate::core::http::Response;
     use crate::core::ir::Error;
     use crate::core::runtime::TargetRuntime;
@@ -163,6 +231,87 @@ mod tests {
             }
         }
     }
+
+    /// Answers a bidirectional-streaming `Chat` call with an event for each
+    /// message of the request, echoing its topic.
+    struct ChatHttp;
+
+    #[async_trait]
+    impl HttpIO for ChatHttp {
+        async fn execute(&self, _request: Request) -> Result<Response<Bytes>> {
+            bail!("Chat is a streaming call")
+        }
+
+        async fn execute_raw(&self, request: Request) -> Result<reqwest::Response> {
+            let body = request
+                .body()
+                .and_then(reqwest::Body::as_bytes)
+                .unwrap_or_default();
+            let messages = GrpcFrameDecoder::new().decode(&Bytes::copy_from_slice(body))?;
+
+            let mut events = Vec::new();
+            for (id, message) in (1u8..).zip(messages) {
+                // `EventRequest.topic` is field 1, `Event.data` is field 2
+                let mut event = vec![0x08, id, 0x12];
+                event.extend_from_slice(message.get(1..).unwrap_or_default());
+                events.push(0);
+                events.extend_from_slice(&u32::try_from(event.len())?.to_be_bytes());
+                events.extend_from_slice(&event);
+            }
+
+            Ok(http::Response::builder()
+                .status(StatusCode::OK)
+                .body(events)?
+                .into())
+        }
+    }
+
+    /// Streams raw responses over HTTP/2 with prior knowledge, like the gRPC
+    /// client of the CLI runtime.
+    struct StreamingHttp {
+        client: reqwest::Client,
+    }
+
+    #[async_trait]
+    impl HttpIO for StreamingHttp {
+        async fn execute(&self, _request: Request) -> Result<Response<Bytes>> {
+            bail!("Only streaming calls are supported")
+        }
+
+        async fn execute_raw(&self, request: Request) -> Result<reqwest::Response> {
+            Ok(self.client.execute(request).await?)
+        }
+    }
+
+    /// Starts an HTTP/2 server answering a single connection with a message
+    /// followed by `trailers`, and returns its address.
+    async fn serve_message_with_trailers(trailers: HeaderMap) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let service = service_fn(move |_| {
+                let frames = vec![
+                    Ok::<_, Infallible>(Frame::data(Bytes::from_static(
+                        b"\0\0\0\0\x0e\n\x0ctest message",
+                    ))),
+                    Ok(Frame::trailers(trailers.clone())),
+                ];
+                async move {
+                    Ok::<_, Infallible>(http::Response::new(StreamBody::new(
+                        futures_util::stream::iter(frames),
+                    )))
+                }
+            });
+            let _ = Builder::new(TokioExecutor::new())
+                .serve_connection(TokioIo::new(stream), service)
+                .await;
+        });
+
+        format!("http://{addr}")
+    }
+
     fn prepare_args(test_http: TestHttp) -> Result<(TargetRuntime, ProtobufOperation, Request)> {
         let mut runtime = crate::core::runtime::test::init(&None);
         runtime.http2_only = Arc::new(test_http);
@@ -225,6 +374,7 @@ mod tests {
                     grpc_description,
                     grpc_status_message,
                     grpc_status_details,
+                    grpc_extensions,
                 }) => {
                     let code = Code::InvalidArgument;
                     assert_eq!(*grpc_code, code as i32);
@@ -240,6 +390,7 @@ mod tests {
                             }]
                         })
                     );
+                    assert!(grpc_extensions.is_empty());
                 }
                 _ => panic!("Expected GRPCError"),
             }
@@ -259,4 +410,71 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_grpc_streaming_request_with_error_in_trailers() -> Result<()> {
+        let mut trailers = HeaderMap::new();
+        Status::new(Code::Unavailable, "upstream went away").add_header(&mut trailers)?;
+        let url = serve_message_with_trailers(trailers).await;
+
+        let (mut runtime, operation, _) =
+            prepare_args(TestHttp { scenario: TestScenario::SuccessWithoutGrpcStatus })?;
+        runtime.http2_only = Arc::new(StreamingHttp {
+            client: reqwest::Client::builder().http2_prior_knowledge().build()?,
+        });
+        let request = Request::new(Method::POST, url.parse()?);
+
+        let results = execute_grpc_streaming_request(&runtime, &operation, request)
+            .await?
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(
+            serde_json::to_value(results[0].as_ref().unwrap())?,
+            json!({ "message": "test message" })
+        );
+        match &results[1] {
+            Err(Error::GRPC { grpc_code, grpc_status_message, .. }) => {
+                assert_eq!(*grpc_code, Code::Unavailable as i32);
+                assert_eq!(grpc_status_message, "upstream went away");
+            }
+            result => panic!("Expected a gRPC error, got {result:?}"),
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_grpc_bidi_streaming_request() -> Result<()> {
+        let mut runtime = crate::core::runtime::test::init(&None);
+        runtime.http2_only = Arc::new(ChatHttp);
+
+        let file_descriptor_set = protox::compile([protobuf::STREAMING], [protobuf::SELF]);
+        let grpc_method = GrpcMethod::try_from("streaming.EventService.Chat").unwrap();
+        let file = ProtobufSet::from_proto_file(file_descriptor_set.unwrap())?;
+        let service = file.find_service(&grpc_method)?;
+        let operation = service.find_operation(&grpc_method)?;
+
+        let mut request = Request::new(Method::POST, "http://example.com".parse().unwrap());
+        *request.body_mut() = Some(
+            operation
+                .convert_input(r#"[{ "topic": "a" }, { "topic": "bc" }]"#)?
+                .into(),
+        );
+
+        let events = execute_grpc_streaming_request(&runtime, &operation, request)
+            .await?
+            .map(|event| serde_json::to_value(event.unwrap()).unwrap())
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(
+            events,
+            vec![
+                json!({ "id": 1, "data": "a" }),
+                json!({ "id": 2, "data": "bc" })
+            ]
+        );
+        Ok(())
+    }
 }
@@ -3,11 +3,15 @@ use std::hash::{Hash, Hasher};
 use anyhow::Result;
 use derive_setters::Setters;
 use gqlforge_hasher::GqlforgeHasher;
-use http::header::{CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue};
+use http::header::{
+    ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue,
+};
 use url::Url;
 
+use super::compression::{ACCEPTED_ENCODINGS, GRPC_ACCEPT_ENCODING, GRPC_ENCODING};
+use super::protocol::{CONNECT_PROTOCOL_VERSION, X_GRPC_WEB, content_type};
 use super::request::create_grpc_request;
-use crate::core::config::GraphQLOperationType;
+use crate::core::config::{GraphQLOperationType, GrpcProtocol};
 use crate::core::grpc::protobuf::ProtobufOperation;
 use crate::core::has_headers::HasHeaders;
 use crate::core::helpers::headers::MustacheHeaders;
@@ -15,8 +19,6 @@ use crate::core::ir::model::{CacheKey, IoId};
 use crate::core::mustache::Mustache;
 use crate::core::path::PathString;
 
-static GRPC_MIME_TYPE: HeaderValue = HeaderValue::from_static("application/grpc");
-
 #[derive(Setters, Debug, Clone)]
 pub struct RequestTemplate {
     pub url: Mustache,
@@ -67,11 +69,43 @@ impl RequestTemplate {
     fn create_headers<C: PathString>(&self, ctx: &C) -> HeaderMap {
         let mut header_map = HeaderMap::new();
 
-        header_map.insert(CONTENT_TYPE, GRPC_MIME_TYPE.clone());
-        header_map.insert(
-            HeaderName::from_static("te"),
-            HeaderValue::from_static("trailers"),
-        );
+        let protocol = self.operation.protocol;
+        header_map.insert(CONTENT_TYPE, content_type(protocol));
+        match protocol {
+            GrpcProtocol::Grpc => {
+                header_map.insert(
+                    HeaderName::from_static("te"),
+                    HeaderValue::from_static("trailers"),
+                );
+            }
+            GrpcProtocol::GrpcWeb => {
+                header_map.insert(
+                    HeaderName::from_static(X_GRPC_WEB),
+                    HeaderValue::from_static("1"),
+                );
+            }
+            GrpcProtocol::Connect => {
+                header_map.insert(
+                    HeaderName::from_static(CONNECT_PROTOCOL_VERSION),
+                    HeaderValue::from_static("1"),
+                );
+            }
+        }
+
+        // Connect unary calls compress whole bodies with HTTP content coding
+        let (accept_encoding, encoding) = match protocol {
+            GrpcProtocol::Grpc | GrpcProtocol::GrpcWeb => (
+                HeaderName::from_static(GRPC_ACCEPT_ENCODING),
+                HeaderName::from_static(GRPC_ENCODING),
+            ),
+            GrpcProtocol::Connect => (ACCEPT_ENCODING, CONTENT_ENCODING),
+        };
+        header_map.insert(accept_encoding, ACCEPTED_ENCODINGS.clone());
+        if let Some(compression) = self.operation.compression
+            && let Ok(value) = HeaderValue::from_str(&compression.to_string())
+        {
+            header_map.insert(encoding, value);
+        }
 
         for (k, v) in &self.headers {
             if let Ok(header_value) = HeaderValue::from_str(&v.render(ctx)) {
@@ -154,7 +188,8 @@ mod tests {
     use crate::core::blueprint::GrpcMethod;
     use crate::core::config::reader::ConfigReader;
     use crate::core::config::{
-        Config, Field, GraphQLOperationType, Grpc, Link, LinkType, Resolver, Type,
+        Config, Field, GraphQLOperationType, Grpc, GrpcCompression, GrpcProtocol, Link, LinkType,
+        Resolver, Type,
     };
     use crate::core::grpc::protobuf::{ProtobufOperation, ProtobufSet};
     use crate::core::ir::model::CacheKey;
@@ -261,6 +296,10 @@ mod tests {
                 (
                     HeaderName::from_static("te"),
                     HeaderValue::from_static("trailers")
+                ),
+                (
+                    HeaderName::from_static("grpc-accept-encoding"),
+                    HeaderValue::from_static("gzip")
                 )
             ])
         );
@@ -291,6 +330,61 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn request_with_compression() {
+        let mut operation = get_protobuf_op().await;
+        operation.compression = Some(GrpcCompression::Gzip);
+        let tmpl = RequestTemplate {
+            url: Mustache::parse("http://localhost:3000/"),
+            headers: vec![],
+            operation,
+            body: Some(RequestBody {
+                mustache: Some(Mustache::parse(r#"{ "name": "test" }"#)),
+                value: String::new(),
+            }),
+            operation_type: GraphQLOperationType::Query,
+        };
+        let ctx = Context::default();
+        let rendered = tmpl.render(&ctx).unwrap();
+        let req = rendered.to_request().unwrap();
+
+        assert_eq!(req.headers().get("grpc-encoding").unwrap(), "gzip");
+        if let Some(body) = req.body() {
+            assert_eq!(body.as_bytes().unwrap()[0], 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn request_with_connect_protocol() {
+        let mut operation = get_protobuf_op().await;
+        operation.protocol = GrpcProtocol::Connect;
+        let tmpl = RequestTemplate {
+            url: Mustache::parse("http://localhost:3000/"),
+            headers: vec![],
+            operation,
+            body: Some(RequestBody {
+                mustache: Some(Mustache::parse(r#"{ "name": "test" }"#)),
+                value: String::new(),
+            }),
+            operation_type: GraphQLOperationType::Query,
+        };
+        let ctx = Context::default();
+        let rendered = tmpl.render(&ctx).unwrap();
+        let req = rendered.to_request().unwrap();
+
+        assert_eq!(
+            req.headers().get("content-type").unwrap(),
+            "application/proto"
+        );
+        assert_eq!(req.headers().get("connect-protocol-version").unwrap(), "1");
+        assert_eq!(req.headers().get("accept-encoding").unwrap(), "gzip");
+        assert!(req.headers().get("te").is_none());
+        if let Some(body) = req.body() {
+            // unframed message
+            assert_eq!(body.as_bytes().unwrap(), b"\n\x04test");
+        }
+    }
+
     async fn request_template_with_body(body_str: &str) -> RequestTemplate {
         RequestTemplate {
             url: Mustache::parse("http://localhost:3000/"),
@@ -0,0 +1,301 @@
+use std::collections::BTreeMap;
+
+use async_graphql_value::{ConstValue, Name};
+use http::StatusCode;
+use indexmap::IndexMap;
+use prost_reflect::prost_types::Any;
+use serde_json::json;
+use tonic::Code;
+use tonic_types::{ErrorDetail, RpcStatusExt};
+
+use super::protobuf::ProtobufOperation;
+
+/// Decodes a standard `google.rpc` error detail, given by the full name of
+/// its type. Returns `None` for other types.
+fn decode_standard_detail(type_name: &str, value: &[u8]) -> Option<ErrorDetail> {
+    let status = tonic_types::Status {
+        code: Code::Unknown as i32,
+        message: String::new(),
+        details: vec![Any {
+            type_url: format!("type.googleapis.com/{type_name}"),
+            value: value.to_vec(),
+        }],
+    };
+
+    status.get_error_details_vec().into_iter().next()
+}
+
+/// The JSON form of a standard error detail, along with the typed error
+/// extension it adds: `reason` for `ErrorInfo`, `fieldViolations` for
+/// `BadRequest`, `quotaViolations` for `QuotaFailure` and `retryAfter`, in
+/// seconds, for `RetryInfo`.
+fn standard_detail(
+    detail: &ErrorDetail,
+) -> (serde_json::Value, Option<(&'static str, serde_json::Value)>) {
+    match detail {
+        ErrorDetail::ErrorInfo(info) => (
+            json!({ "reason": info.reason, "domain": info.domain, "metadata": info.metadata }),
+            Some(("reason", json!(info.reason))),
+        ),
+        ErrorDetail::BadRequest(bad_request) => {
+            let violations = bad_request
+                .field_violations
+                .iter()
+                .map(|v| json!({ "field": v.field, "description": v.description }))
+                .collect::<Vec<_>>();
+            (
+                json!({ "fieldViolations": violations }),
+                Some(("fieldViolations", json!(violations))),
+            )
+        }
+        ErrorDetail::QuotaFailure(quota_failure) => {
+            let violations = quota_failure
+                .violations
+                .iter()
+                .map(|v| json!({ "subject": v.subject, "description": v.description }))
+                .collect::<Vec<_>>();
+            (
+                json!({ "violations": violations }),
+                Some(("quotaViolations", json!(violations))),
+            )
+        }
+        ErrorDetail::RetryInfo(retry_info) => {
+            let seconds = retry_info.retry_delay.map(|delay| delay.as_secs_f64());
+            (
+                json!({ "retryDelay": seconds.map(|seconds| format!("{seconds}s")) }),
+                seconds.map(|seconds| ("retryAfter", json!(seconds))),
+            )
+        }
+        ErrorDetail::PreconditionFailure(precondition_failure) => {
+            let violations = precondition_failure
+                .violations
+                .iter()
+                .map(|v| json!({ "type": v.r#type, "subject": v.subject, "description": v.description }))
+                .collect::<Vec<_>>();
+            (json!({ "violations": violations }), None)
+        }
+        ErrorDetail::DebugInfo(debug_info) => (
+            json!({ "stackEntries": debug_info.stack_entries, "detail": debug_info.detail }),
+            None,
+        ),
+        ErrorDetail::RequestInfo(request_info) => (
+            json!({ "requestId": request_info.request_id, "servingData": request_info.serving_data }),
+            None,
+        ),
+        ErrorDetail::ResourceInfo(resource_info) => (
+            json!({
+                "resourceType": resource_info.resource_type,
+                "resourceName": resource_info.resource_name,
+                "owner": resource_info.owner,
+                "description": resource_info.description,
+            }),
+            None,
+        ),
+        ErrorDetail::Help(help) => {
+            let links = help
+                .links
+                .iter()
+                .map(|link| json!({ "description": link.description, "url": link.url }))
+                .collect::<Vec<_>>();
+            (json!({ "links": links }), None)
+        }
+        ErrorDetail::LocalizedMessage(localized_message) => (
+            json!({ "locale": localized_message.locale, "message": localized_message.message }),
+            None,
+        ),
+    }
+}
+
+/// Decodes the details of a failed call, given by the full name of their type
+/// and their encoded value, along with the typed error extensions they add.
+/// Standard `google.rpc` details are decoded even when their types aren't
+/// part of the linked protobuf files.
+pub fn decode_error_details<'a>(
+    operation: &ProtobufOperation,
+    details: impl IntoIterator<Item = (&'a str, &'a [u8])>,
+) -> (Vec<ConstValue>, IndexMap<Name, ConstValue>) {
+    let mut status_details = Vec::new();
+    let mut extensions = IndexMap::new();
+
+    for (type_name, value) in details {
+        if let Some(detail) = decode_standard_detail(type_name, value) {
+            let (decoded, extension) = standard_detail(&detail);
+            if let Some((name, extension)) = extension
+                && let Ok(extension) = ConstValue::from_json(extension)
+            {
+                extensions.entry(Name::new(name)).or_insert(extension);
+            }
+            status_details.push(ConstValue::from_json(decoded).unwrap_or_default());
+            continue;
+        }
+
+        match operation.find_message(type_name) {
+            Some(message) => match message.decode(value) {
+                Ok(decoded) => status_details.push(decoded),
+                _ => {
+                    tracing::error!("Error while decoding message: {type_name}");
+                }
+            },
+            _ => {
+                tracing::error!("Error while searching descriptor for message: {type_name}");
+            }
+        }
+    }
+
+    (status_details, extensions)
+}
+
+/// The canonical name of a gRPC status code, e.g. `INVALID_ARGUMENT`.
+#[must_use]
+pub fn code_name(code: i32) -> &'static str {
+    match Code::from_i32(code) {
+        Code::Ok => "OK",
+        Code::Cancelled => "CANCELLED",
+        Code::Unknown => "UNKNOWN",
+        Code::InvalidArgument => "INVALID_ARGUMENT",
+        Code::DeadlineExceeded => "DEADLINE_EXCEEDED",
+        Code::NotFound => "NOT_FOUND",
+        Code::AlreadyExists => "ALREADY_EXISTS",
+        Code::PermissionDenied => "PERMISSION_DENIED",
+        Code::ResourceExhausted => "RESOURCE_EXHAUSTED",
+        Code::FailedPrecondition => "FAILED_PRECONDITION",
+        Code::Aborted => "ABORTED",
+        Code::OutOfRange => "OUT_OF_RANGE",
+        Code::Unimplemented => "UNIMPLEMENTED",
+        Code::Internal => "INTERNAL",
+        Code::Unavailable => "UNAVAILABLE",
+        Code::DataLoss => "DATA_LOSS",
+        Code::Unauthenticated => "UNAUTHENTICATED",
+    }
+}
+
+/// The gRPC status code with the given canonical name.
+#[must_use]
+pub fn code_from_name(name: &str) -> Option<i32> {
+    (0..=16).find(|code| code_name(*code) == name)
+}
+
+/// The HTTP status `@rest` endpoints respond with when a gRPC call fails,
+/// following the mapping of `google.rpc.Code`.
+fn default_http_status(code: i32) -> StatusCode {
+    match Code::from_i32(code) {
+        Code::Ok => StatusCode::OK,
+        // Client Closed Request
+        Code::Cancelled => StatusCode::from_u16(499).unwrap_or(StatusCode::BAD_REQUEST),
+        Code::InvalidArgument | Code::FailedPrecondition | Code::OutOfRange => {
+            StatusCode::BAD_REQUEST
+        }
+        Code::DeadlineExceeded => StatusCode::GATEWAY_TIMEOUT,
+        Code::NotFound => StatusCode::NOT_FOUND,
+        Code::AlreadyExists | Code::Aborted => StatusCode::CONFLICT,
+        Code::PermissionDenied => StatusCode::FORBIDDEN,
+        Code::ResourceExhausted => StatusCode::TOO_MANY_REQUESTS,
+        Code::Unimplemented => StatusCode::NOT_IMPLEMENTED,
+        Code::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+        Code::Unauthenticated => StatusCode::UNAUTHORIZED,
+        Code::Unknown | Code::Internal | Code::DataLoss => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+/// Maps gRPC status codes to the HTTP status of `@rest` responses, with the
+/// overrides configured in `@server(grpcStatusMapping:)`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GrpcStatusMap {
+    overrides: BTreeMap<i32, StatusCode>,
+}
+
+impl GrpcStatusMap {
+    #[must_use]
+    pub fn new(overrides: BTreeMap<i32, StatusCode>) -> Self {
+        Self { overrides }
+    }
+
+    #[must_use]
+    pub fn http_status(&self, code: i32) -> StatusCode {
+        self.overrides
+            .get(&code)
+            .copied()
+            .unwrap_or_else(|| default_http_status(code))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![expect(clippy::unwrap_used, reason = "test code")]
+    use prost::Message;
+    use tonic_types::{ErrorDetails, StatusExt};
+
+    use super::*;
+
+    /// The details of a status with `details`, as decoded by
+    /// `decode_error_details`.
+    fn standard_details(details: ErrorDetails) -> Vec<ErrorDetail> {
+        let status = tonic::Status::with_error_details(Code::InvalidArgument, "invalid", details);
+        let status = tonic_types::Status::decode(status.details()).unwrap();
+
+        status
+            .details
+            .iter()
+            .filter_map(|detail| {
+                let type_name = detail.type_url.split('/').next_back().unwrap();
+                decode_standard_detail(type_name, &detail.value)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn decodes_standard_error_details() {
+        let details = standard_details(ErrorDetails::with_bad_request_violation(
+            "email",
+            "is invalid",
+        ));
+        let (decoded, extension) = standard_detail(&details[0]);
+
+        assert_eq!(
+            decoded,
+            json!({ "fieldViolations": [{ "field": "email", "description": "is invalid" }] })
+        );
+        assert_eq!(
+            extension,
+            Some((
+                "fieldViolations",
+                json!([{ "field": "email", "description": "is invalid" }])
+            ))
+        );
+    }
+
+    #[test]
+    fn reads_retry_delay_in_seconds() {
+        let details = standard_details(ErrorDetails::with_retry_info(Some(
+            std::time::Duration::from_millis(1500),
+        )));
+
+        assert_eq!(
+            standard_detail(&details[0]).1,
+            Some(("retryAfter", json!(1.5)))
+        );
+    }
+
+    #[test]
+    fn skips_other_detail_types() {
+        assert!(decode_standard_detail("news.NewsError", b"").is_none());
+    }
+
+    #[test]
+    fn names_codes() {
+        assert_eq!(code_name(3), "INVALID_ARGUMENT");
+        assert_eq!(code_from_name("NOT_FOUND"), Some(5));
+        assert_eq!(code_from_name("MISSING"), None);
+    }
+
+    #[test]
+    fn maps_codes_to_http_status() {
+        let map = GrpcStatusMap::default();
+        assert_eq!(map.http_status(5), StatusCode::NOT_FOUND);
+        assert_eq!(map.http_status(14), StatusCode::SERVICE_UNAVAILABLE);
+
+        let map = GrpcStatusMap::new(BTreeMap::from([(5, StatusCode::GONE)]));
+        assert_eq!(map.http_status(5), StatusCode::GONE);
+        assert_eq!(map.http_status(16), StatusCode::UNAUTHORIZED);
+    }
+}
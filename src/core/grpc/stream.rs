@@ -1,14 +1,20 @@
+use anyhow::Result;
 use bytes::{Buf, Bytes, BytesMut};
 
+use super::compression::decode_message;
+use crate::core::config::GrpcCompression;
+
 /// Decodes gRPC-framed messages from a byte stream.
 ///
 /// Each gRPC frame consists of a 5-byte header:
-///   - 1 byte: compressed flag (0 = uncompressed)
+///   - 1 byte: compressed flag (0 = uncompressed, 1 = compressed with the
+///     `grpc-encoding` of the response)
 ///   - 4 bytes: big-endian message length
 ///
 /// followed by the protobuf-encoded message payload.
 pub struct GrpcFrameDecoder {
     buffer: BytesMut,
+    encoding: Option<GrpcCompression>,
 }
 
 impl Default for GrpcFrameDecoder {
@@ -20,11 +26,21 @@ impl Default for GrpcFrameDecoder {
 impl GrpcFrameDecoder {
     #[must_use]
     pub fn new() -> Self {
-        Self { buffer: BytesMut::new() }
+        Self { buffer: BytesMut::new(), encoding: None }
+    }
+
+    /// A decoder for a response whose compressed messages use `encoding`.
+    #[must_use]
+    pub fn with_encoding(encoding: Option<GrpcCompression>) -> Self {
+        Self { buffer: BytesMut::new(), encoding }
     }
 
     /// Feed a chunk of bytes and extract any complete gRPC frames.
-    pub fn decode(&mut self, chunk: &Bytes) -> Vec<Bytes> {
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a compressed frame can't be decompressed.
+    pub fn decode(&mut self, chunk: &Bytes) -> Result<Vec<Bytes>> {
         self.buffer.extend_from_slice(chunk);
         let mut frames = Vec::new();
 
@@ -33,7 +49,8 @@ impl GrpcFrameDecoder {
                 break;
             }
 
-            // Read the 4-byte message length (skip 1-byte compressed flag)
+            // Read the 4-byte message length after the 1-byte compressed flag
+            let compressed = self.buffer[0] == 1;
             let msg_len = u32::from_be_bytes([
                 self.buffer[1],
                 self.buffer[2],
@@ -49,18 +66,27 @@ impl GrpcFrameDecoder {
             // Skip the 5-byte header
             self.buffer.advance(5);
             let payload = self.buffer.split_to(msg_len).freeze();
-            frames.push(payload);
+            if compressed {
+                frames.push(Bytes::from(
+                    decode_message(true, &payload, self.encoding)?.into_owned(),
+                ));
+            } else {
+                frames.push(payload);
+            }
         }
 
-        frames
+        Ok(frames)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    #![expect(clippy::unwrap_used, reason = "test code")]
     use bytes::Bytes;
 
     use super::GrpcFrameDecoder;
+    use crate::core::config::GrpcCompression;
+    use crate::core::grpc::compression::compress;
 
     fn make_frame(payload: &[u8]) -> Vec<u8> {
         let len = u32::try_from(payload.len()).unwrap_or(u32::MAX);
@@ -75,7 +101,7 @@ mod tests {
     fn test_single_frame() {
         let mut decoder = GrpcFrameDecoder::new();
         let data = make_frame(b"hello");
-        let frames = decoder.decode(&Bytes::from(data));
+        let frames = decoder.decode(&Bytes::from(data)).unwrap();
         assert_eq!(frames.len(), 1);
         assert_eq!(frames[0].as_ref(), b"hello");
     }
@@ -85,7 +111,7 @@ mod tests {
         let mut decoder = GrpcFrameDecoder::new();
         let mut data = make_frame(b"first");
         data.extend_from_slice(&make_frame(b"second"));
-        let frames = decoder.decode(&Bytes::from(data));
+        let frames = decoder.decode(&Bytes::from(data)).unwrap();
         assert_eq!(frames.len(), 2);
         assert_eq!(frames[0].as_ref(), b"first");
         assert_eq!(frames[1].as_ref(), b"second");
@@ -97,11 +123,11 @@ mod tests {
         let data = make_frame(b"split-test");
 
         // Feed first 3 bytes (partial header)
-        let frames = decoder.decode(&Bytes::from(data[..3].to_vec()));
+        let frames = decoder.decode(&Bytes::from(data[..3].to_vec())).unwrap();
         assert_eq!(frames.len(), 0);
 
         // Feed the rest
-        let frames = decoder.decode(&Bytes::from(data[3..].to_vec()));
+        let frames = decoder.decode(&Bytes::from(data[3..].to_vec())).unwrap();
         assert_eq!(frames.len(), 1);
         assert_eq!(frames[0].as_ref(), b"split-test");
     }
@@ -110,7 +136,7 @@ mod tests {
     fn test_empty_payload() {
         let mut decoder = GrpcFrameDecoder::new();
         let data = make_frame(b"");
-        let frames = decoder.decode(&Bytes::from(data));
+        let frames = decoder.decode(&Bytes::from(data)).unwrap();
         assert_eq!(frames.len(), 1);
         assert_eq!(frames[0].as_ref(), b"");
     }
@@ -118,7 +144,25 @@ mod tests {
     #[test]
     fn test_empty_chunk() {
         let mut decoder = GrpcFrameDecoder::new();
-        let frames = decoder.decode(&Bytes::new());
+        let frames = decoder.decode(&Bytes::new()).unwrap();
         assert_eq!(frames.len(), 0);
     }
+
+    #[test]
+    fn test_compressed_frame() {
+        let payload = compress(GrpcCompression::Gzip, b"compressed").unwrap();
+        let mut data = make_frame(&payload);
+        data[0] = 1;
+        data.extend_from_slice(&make_frame(b"plain"));
+
+        let mut decoder = GrpcFrameDecoder::with_encoding(Some(GrpcCompression::Gzip));
+        let frames = decoder.decode(&Bytes::from(data.clone())).unwrap();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].as_ref(), b"compressed");
+        assert_eq!(frames[1].as_ref(), b"plain");
+
+        // A compressed frame can't be read without the response encoding
+        let mut decoder = GrpcFrameDecoder::new();
+        assert!(decoder.decode(&Bytes::from(data)).is_err());
+    }
 }
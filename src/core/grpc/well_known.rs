@@ -0,0 +1,185 @@
+//! Well-known types are carried in their canonical JSON mapping, which
+//! matches the GraphQL types they're generated as, except for `FieldMask`:
+//! its paths are joined in a single string, but generated as a string list.
+
+use std::collections::HashSet;
+
+use prost_reflect::{FieldDescriptor, Kind, MessageDescriptor};
+use serde_json::Value;
+
+/// Full name of the `FieldMask` well-known type.
+pub const FIELD_MASK: &str = "google.protobuf.FieldMask";
+
+/// The GraphQL type a well-known message is generated as, if it isn't
+/// generated as an object type.
+#[must_use]
+pub fn graphql_type(message: &str) -> Option<&'static str> {
+    let name = match message.strip_prefix('.').unwrap_or(message) {
+        "google.protobuf.Timestamp" => "DateTime",
+        "google.protobuf.Duration" | "google.protobuf.StringValue" => "String",
+        "google.protobuf.Int32Value" => "Int",
+        "google.protobuf.Int64Value" => "Int64",
+        "google.protobuf.UInt32Value" => "UInt32",
+        "google.protobuf.UInt64Value" => "UInt64",
+        "google.protobuf.FloatValue" | "google.protobuf.DoubleValue" => "Float",
+        "google.protobuf.BoolValue" => "Boolean",
+        "google.protobuf.BytesValue" => "Bytes",
+        "google.protobuf.Struct" | "google.protobuf.Value" | "google.protobuf.ListValue" => "JSON",
+        // Paths of the mask, generated as `[String!]`.
+        FIELD_MASK => "String",
+        _ => return None,
+    };
+
+    Some(name)
+}
+
+/// Whether a message, or one nested in it, has a `FieldMask` field.
+#[must_use]
+pub fn has_field_mask(descriptor: &MessageDescriptor) -> bool {
+    fn visit(descriptor: &MessageDescriptor, visited: &mut HashSet<String>) -> bool {
+        if !visited.insert(descriptor.full_name().to_string()) {
+            return false;
+        }
+
+        descriptor.fields().any(|field| match field.kind() {
+            Kind::Message(message) => message.full_name() == FIELD_MASK || visit(&message, visited),
+            _ => false,
+        })
+    }
+
+    visit(descriptor, &mut HashSet::new())
+}
+
+/// Splits the paths of the field masks of a message serialized to JSON into
+/// lists.
+pub fn field_masks_to_lists(descriptor: &MessageDescriptor, value: &mut Value) {
+    visit_field_masks(descriptor, value, &|mask| {
+        if let Value::String(paths) = mask {
+            *mask = paths
+                .split(',')
+                .filter(|path| !path.is_empty())
+                .map(|path| Value::String(path.to_string()))
+                .collect();
+        }
+    });
+}
+
+/// Joins the path lists of the field masks of a JSON input, so that it can
+/// be deserialized as a message.
+pub fn field_masks_to_strings(descriptor: &MessageDescriptor, value: &mut Value) {
+    visit_field_masks(descriptor, value, &|mask| {
+        if let Value::Array(paths) = mask {
+            *mask = Value::String(
+                paths
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .collect::<Vec<_>>()
+                    .join(","),
+            );
+        }
+    });
+}
+
+/// Calls `f` with the value of every `FieldMask` of a message in JSON.
+fn visit_field_masks(descriptor: &MessageDescriptor, value: &mut Value, f: &impl Fn(&mut Value)) {
+    let Value::Object(object) = value else {
+        return;
+    };
+
+    for field in descriptor.fields() {
+        let Kind::Message(message) = field.kind() else {
+            continue;
+        };
+        // Output uses the JSON name of fields, while input may use either.
+        let key = if object.contains_key(field.json_name()) {
+            field.json_name()
+        } else {
+            field.name()
+        };
+        let Some(value) = object.get_mut(key) else {
+            continue;
+        };
+        let values: Vec<&mut Value> = match value {
+            Value::Array(values) if field.is_list() => values.iter_mut().collect(),
+            Value::Object(entries) if field.is_map() => entries.values_mut().collect(),
+            value => vec![value],
+        };
+
+        for value in values {
+            visit_message(&field, &message, value, f);
+        }
+    }
+}
+
+fn visit_message(
+    field: &FieldDescriptor,
+    message: &MessageDescriptor,
+    value: &mut Value,
+    f: &impl Fn(&mut Value),
+) {
+    if field.is_map() {
+        let value_field = message.map_entry_value_field();
+        if let Kind::Message(value_message) = value_field.kind() {
+            visit_message(&value_field, &value_message, value, f);
+        }
+    } else if message.full_name() == FIELD_MASK {
+        f(value);
+    } else {
+        visit_field_masks(message, value, f);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![expect(clippy::unwrap_used, reason = "test code")]
+    use gqlforge_fixtures::protobuf;
+    use prost_reflect::DescriptorPool;
+    use serde_json::json;
+
+    use super::*;
+
+    fn descriptor() -> MessageDescriptor {
+        let set = protox::compile([protobuf::WELL_KNOWN], [protobuf::SELF]).unwrap();
+        let pool = DescriptorPool::from_file_descriptor_set(set).unwrap();
+        pool.get_message_by_name("posts.UpdateRequest").unwrap()
+    }
+
+    #[test]
+    fn finds_nested_field_masks() {
+        let descriptor = descriptor();
+        assert!(has_field_mask(&descriptor));
+
+        let timestamp = descriptor.parent_pool().get_message_by_name("google.protobuf.Timestamp");
+        assert!(!has_field_mask(&timestamp.unwrap()));
+    }
+
+    #[test]
+    fn converts_field_masks() {
+        let descriptor = descriptor();
+        let mut value = json!({
+            "updateMask": "title,body",
+            "updates": [{ "mask": "title" }],
+            "title": "Hello"
+        });
+
+        field_masks_to_lists(&descriptor, &mut value);
+        assert_eq!(
+            value,
+            json!({
+                "updateMask": ["title", "body"],
+                "updates": [{ "mask": ["title"] }],
+                "title": "Hello"
+            })
+        );
+
+        field_masks_to_strings(&descriptor, &mut value);
+        assert_eq!(
+            value,
+            json!({
+                "updateMask": "title,body",
+                "updates": [{ "mask": "title" }],
+                "title": "Hello"
+            })
+        );
+    }
+}
@@ -52,6 +52,7 @@ impl HttpDataLoader {
         DataLoader::new(self)
             .delay(Duration::from_millis(batch.delay as u64))
             .max_batch_size(batch.max_size.unwrap_or_default())
+            .name("http")
     }
 }
 
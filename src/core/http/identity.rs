@@ -0,0 +1,264 @@
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use http::header::{HeaderName, HeaderValue};
+use jsonwebtoken::jwk::{Jwk, JwkSet, PublicKeyUse};
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+
+use super::oauth2::matches_host;
+use crate::core::config::{Identity, IdentityAlgorithm};
+
+/// Mints the internal identity tokens of `@upstream(identity:)`. Clones share
+/// the signing key.
+#[derive(Clone)]
+pub struct IdentitySigner {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    identity: Identity,
+    key: EncodingKey,
+    header: Header,
+    header_name: HeaderName,
+    jwks: JwkSet,
+}
+
+impl IdentitySigner {
+    /// Loads the PEM-encoded private key of the `@link(type: Key)` that the
+    /// identity references.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key can't be used with the identity's
+    /// algorithm, or the header name is invalid.
+    pub fn try_new(identity: Identity, pem: &str) -> Result<Self> {
+        let algorithm = algorithm(identity.algorithm);
+        let key = match identity.algorithm {
+            IdentityAlgorithm::ES256 | IdentityAlgorithm::ES384 => {
+                EncodingKey::from_ec_pem(pem.as_bytes())
+            }
+            IdentityAlgorithm::EdDSA => EncodingKey::from_ed_pem(pem.as_bytes()),
+            _ => EncodingKey::from_rsa_pem(pem.as_bytes()),
+        }
+        .with_context(|| format!("Key can't be used with {:?}", identity.algorithm))?;
+
+        let mut jwk = Jwk::from_encoding_key(&key, algorithm)
+            .with_context(|| format!("Key can't be used with {:?}", identity.algorithm))?;
+        let kid = URL_SAFE_NO_PAD.encode(Sha256::digest(serde_json::to_vec(&jwk.algorithm)?));
+        jwk.common.key_id = Some(kid.clone());
+        jwk.common.public_key_use = Some(PublicKeyUse::Signature);
+
+        let mut header = Header::new(algorithm);
+        header.kid = Some(kid);
+
+        let header_name = HeaderName::try_from(identity.get_header())
+            .with_context(|| format!("Invalid header name: {}", identity.get_header()))?;
+
+        Ok(Self {
+            inner: Arc::new(Inner {
+                identity,
+                key,
+                header,
+                header_name,
+                jwks: JwkSet { keys: vec![jwk] },
+            }),
+        })
+    }
+
+    #[must_use]
+    pub fn identity(&self) -> &Identity {
+        &self.inner.identity
+    }
+
+    /// Header the tokens are sent in.
+    #[must_use]
+    pub fn header_name(&self) -> &HeaderName {
+        &self.inner.header_name
+    }
+
+    /// The public signing key, served on the identity's `jwksPath`.
+    #[must_use]
+    pub fn jwks(&self) -> &JwkSet {
+        &self.inner.jwks
+    }
+
+    /// Whether requests to `url` are sent with an identity token.
+    #[must_use]
+    pub fn matches(&self, url: &reqwest::Url) -> bool {
+        matches_host(&self.inner.identity.hosts, url)
+    }
+
+    /// How long a minted token stays valid.
+    #[must_use]
+    pub fn ttl(&self) -> Duration {
+        Duration::from_secs(self.inner.identity.get_ttl())
+    }
+
+    /// Mints a token carrying `sub` and the configured subset of `claims`,
+    /// and the name of the operation being executed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the token can't be signed.
+    pub fn sign(
+        &self,
+        claims: Option<&Value>,
+        operation_name: Option<&str>,
+    ) -> Result<HeaderValue> {
+        let identity = &self.inner.identity;
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        let mut payload = Map::new();
+
+        if let Some(claims) = claims.and_then(Value::as_object) {
+            for name in std::iter::once("sub").chain(identity.claims.iter().map(String::as_str)) {
+                if let Some(value) = claims.get(name) {
+                    payload.insert(name.to_string(), value.clone());
+                }
+            }
+        }
+        if let Some(issuer) = &identity.issuer {
+            payload.insert("iss".to_string(), issuer.clone().into());
+        }
+        if let Some(audience) = &identity.audience {
+            payload.insert("aud".to_string(), audience.clone().into());
+        }
+        if let Some(operation_name) = operation_name {
+            payload.insert("operation".to_string(), operation_name.into());
+        }
+        payload.insert("iat".to_string(), now.into());
+        payload.insert("exp".to_string(), (now + identity.get_ttl()).into());
+
+        let token = jsonwebtoken::encode(&self.inner.header, &payload, &self.inner.key)?;
+        Ok(HeaderValue::try_from(token)?)
+    }
+}
+
+impl PartialEq for IdentitySigner {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl Eq for IdentitySigner {}
+
+impl std::fmt::Debug for IdentitySigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IdentitySigner")
+            .field("identity", &self.inner.identity)
+            .finish_non_exhaustive()
+    }
+}
+
+fn algorithm(algorithm: IdentityAlgorithm) -> Algorithm {
+    match algorithm {
+        IdentityAlgorithm::RS256 => Algorithm::RS256,
+        IdentityAlgorithm::RS384 => Algorithm::RS384,
+        IdentityAlgorithm::RS512 => Algorithm::RS512,
+        IdentityAlgorithm::PS256 => Algorithm::PS256,
+        IdentityAlgorithm::PS384 => Algorithm::PS384,
+        IdentityAlgorithm::PS512 => Algorithm::PS512,
+        IdentityAlgorithm::ES256 => Algorithm::ES256,
+        IdentityAlgorithm::ES384 => Algorithm::ES384,
+        IdentityAlgorithm::EdDSA => Algorithm::EdDSA,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![expect(clippy::unwrap_used, reason = "test code")]
+    use jsonwebtoken::{DecodingKey, Validation, decode};
+    use serde_json::json;
+
+    use super::*;
+
+    const RSA_KEY: &str = include_str!("../../../tests/server/config/example-rsa.key");
+
+    fn identity() -> Identity {
+        Identity {
+            key: "identity".to_string(),
+            algorithm: IdentityAlgorithm::RS256,
+            hosts: vec!["orders.internal".to_string()],
+            claims: vec!["tenant".to_string()],
+            issuer: Some("gateway".to_string()),
+            audience: Some("internal".to_string()),
+            ttl: None,
+            header: None,
+            jwks_path: None,
+        }
+    }
+
+    #[test]
+    fn signs_tokens_verifiable_with_the_jwks() {
+        let signer = IdentitySigner::try_new(identity(), RSA_KEY).unwrap();
+        let claims = json!({"sub": "user-1", "tenant": "acme", "email": "a@acme.test"});
+
+        let token = signer.sign(Some(&claims), Some("GetOrders")).unwrap();
+
+        let jwk = &signer.jwks().keys[0];
+        let header = jsonwebtoken::decode_header(token.to_str().unwrap()).unwrap();
+        assert_eq!(header.kid, jwk.common.key_id);
+
+        let mut validation = Validation::new(Algorithm::RS256);
+        validation.set_audience(&["internal"]);
+        validation.set_issuer(&["gateway"]);
+        let decoded = decode::<Value>(
+            token.to_str().unwrap(),
+            &DecodingKey::from_jwk(jwk).unwrap(),
+            &validation,
+        )
+        .unwrap()
+        .claims;
+
+        assert_eq!(decoded["sub"], "user-1");
+        assert_eq!(decoded["tenant"], "acme");
+        assert_eq!(decoded["operation"], "GetOrders");
+        assert_eq!(decoded.get("email"), None);
+        assert_eq!(
+            decoded["exp"].as_u64().unwrap() - decoded["iat"].as_u64().unwrap(),
+            60
+        );
+    }
+
+    #[test]
+    fn signs_anonymous_requests() {
+        let signer = IdentitySigner::try_new(identity(), RSA_KEY).unwrap();
+        let token = signer.sign(None, None).unwrap();
+
+        let mut validation = Validation::new(Algorithm::RS256);
+        validation.set_audience(&["internal"]);
+        let decoded = decode::<Value>(
+            token.to_str().unwrap(),
+            &DecodingKey::from_jwk(&signer.jwks().keys[0]).unwrap(),
+            &validation,
+        )
+        .unwrap()
+        .claims;
+
+        assert_eq!(decoded.get("sub"), None);
+        assert_eq!(decoded.get("operation"), None);
+    }
+
+    #[test]
+    fn rejects_keys_of_another_algorithm() {
+        let identity = Identity { algorithm: IdentityAlgorithm::ES256, ..identity() };
+        assert!(IdentitySigner::try_new(identity, RSA_KEY).is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_header_names() {
+        let identity = Identity { header: Some("x identity".to_string()), ..identity() };
+        assert!(IdentitySigner::try_new(identity, RSA_KEY).is_err());
+    }
+
+    #[test]
+    fn matches_hosts() {
+        let signer = IdentitySigner::try_new(identity(), RSA_KEY).unwrap();
+        assert!(signer.matches(&"http://orders.internal/orders".parse().unwrap()));
+        assert!(!signer.matches(&"https://api.example.com/orders".parse().unwrap()));
+    }
+}
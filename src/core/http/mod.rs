@@ -2,7 +2,10 @@ pub use cache::*;
 pub use data_loader::*;
 pub use data_loader_request::*;
 use http::HeaderValue;
+pub use identity::IdentitySigner;
 pub use method::Method;
+pub use oauth2::{OAuth2Client, OAuth2Http};
+pub use peer_certificate::{PeerCertificate, SubjectAltNames};
 pub use query_encoder::QueryEncoder;
 pub use request_context::RequestContext;
 pub use request_handler::{API_URL_PREFIX, handle_request};
@@ -12,12 +15,16 @@ pub use response::*;
 mod cache;
 mod data_loader;
 mod data_loader_request;
+mod identity;
 mod method;
+mod oauth2;
+mod peer_certificate;
 mod query_encoder;
 mod request_context;
 mod request_handler;
 mod request_template;
 mod response;
+pub mod rpc;
 pub mod showcase;
 #[cfg(feature = "cli")]
 pub mod spa;
@@ -0,0 +1,618 @@
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, PoisonError, RwLock, Weak};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result, bail};
+use bytes::Bytes;
+use headers::authorization::Bearer;
+use headers::{Authorization, HeaderMapExt};
+use http::header::{CONTENT_TYPE, HeaderValue};
+use serde::Deserialize;
+
+use super::Response;
+use crate::core::HttpIO;
+use crate::core::config::{OAuth2GrantType, OAuth2Link};
+use crate::core::runtime::TargetRuntime;
+
+const TOKEN_EXCHANGE_GRANT: &str = "urn:ietf:params:oauth:grant-type:token-exchange";
+const ACCESS_TOKEN_TYPE: &str = "urn:ietf:params:oauth:token-type:access_token";
+
+/// Delay before fetching the token again after a background refresh failed.
+const RETRY_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Shortest time a token is used for, so that an endpoint issuing tokens with
+/// a tiny or zero `expires_in` isn't asked for a new one on every request.
+const MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Access tokens for the upstreams of a `@link(type: OAuth2)`. Clones share
+/// the same token, so every request to those upstreams reuses it until
+/// shortly before it expires.
+#[derive(Clone)]
+pub struct OAuth2Client {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    link: OAuth2Link,
+    token: RwLock<Option<Token>>,
+    /// Held while fetching so concurrent requests share a single fetch.
+    fetching: tokio::sync::Mutex<()>,
+    refreshing: AtomicBool,
+}
+
+#[derive(Clone)]
+struct Token {
+    access_token: String,
+    /// When the token must be replaced, shortly before its expiry.
+    /// `None` if the endpoint didn't say when the token expires.
+    refresh_at: Option<Instant>,
+}
+
+impl Token {
+    fn is_fresh(&self, now: Instant) -> bool {
+        self.refresh_at.is_none_or(|refresh_at| now < refresh_at)
+    }
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: Option<u64>,
+}
+
+/// Whether `url` is on one of `hosts`. A host matches `host`, `host:port` or
+/// a `*.domain` wildcard.
+pub(super) fn matches_host(hosts: &[String], url: &reqwest::Url) -> bool {
+    let Some(host) = url.host_str() else {
+        return false;
+    };
+
+    hosts.iter().any(|pattern| {
+        let (pattern, port) = match pattern.rsplit_once(':') {
+            Some((pattern, port)) => (pattern, port.parse::<u16>().ok()),
+            None => (pattern.as_str(), None),
+        };
+        let host_matches = match pattern.strip_prefix("*.") {
+            Some(domain) => host
+                .to_ascii_lowercase()
+                .ends_with(&format!(".{}", domain.to_ascii_lowercase())),
+            None => host.eq_ignore_ascii_case(pattern),
+        };
+
+        host_matches && port.is_none_or(|port| url.port_or_known_default() == Some(port))
+    })
+}
+
+impl OAuth2Client {
+    #[must_use]
+    pub fn new(link: OAuth2Link) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                link,
+                token: RwLock::new(None),
+                fetching: tokio::sync::Mutex::new(()),
+                refreshing: AtomicBool::new(false),
+            }),
+        }
+    }
+
+    #[must_use]
+    pub fn link(&self) -> &OAuth2Link {
+        &self.inner.link
+    }
+
+    /// Whether requests to `url` are sent with this client's token.
+    #[must_use]
+    pub fn matches(&self, url: &reqwest::Url) -> bool {
+        matches_host(&self.inner.link.hosts, url)
+    }
+
+    /// A token that isn't about to expire, fetched from the token endpoint
+    /// when the cached one is missing or stale.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the token endpoint can't be reached or doesn't
+    /// issue a token.
+    pub async fn token(&self, http: &Arc<dyn HttpIO>) -> Result<String> {
+        if let Some(token) = self.inner.cached(Instant::now()) {
+            return Ok(token);
+        }
+
+        let _guard = self.inner.fetching.lock().await;
+        // Another request fetched the token while this one was waiting.
+        if let Some(token) = self.inner.cached(Instant::now()) {
+            return Ok(token);
+        }
+
+        let token = self.inner.fetch(http).await?;
+        self.spawn_refresh(http);
+        Ok(token)
+    }
+
+    /// Drops the cached token, so the next request fetches a new one. Called
+    /// when an upstream rejects the token before it was due to expire.
+    pub fn invalidate(&self) {
+        *self
+            .inner
+            .token
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = None;
+    }
+
+    /// Starts replacing the token in the background before it expires, so
+    /// requests don't wait for the token endpoint. The task stops when the
+    /// last clone of the client is dropped.
+    fn spawn_refresh(&self, http: &Arc<dyn HttpIO>) {
+        if self.inner.refreshing.swap(true, Ordering::AcqRel) {
+            return;
+        }
+
+        let client = Arc::downgrade(&self.inner);
+        let http = http.clone();
+        tokio::spawn(async move {
+            loop {
+                let delay = {
+                    let Some(inner) = Weak::upgrade(&client) else {
+                        break;
+                    };
+                    let refresh_at = inner
+                        .token
+                        .read()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .as_ref()
+                        .and_then(|token| token.refresh_at);
+                    // Tokens without an expiry are only replaced once rejected.
+                    let Some(refresh_at) = refresh_at else {
+                        inner.refreshing.store(false, Ordering::Release);
+                        break;
+                    };
+                    refresh_at.saturating_duration_since(Instant::now())
+                };
+
+                tokio::time::sleep(delay).await;
+                let Some(inner) = Weak::upgrade(&client) else {
+                    break;
+                };
+
+                let guard = inner.fetching.lock().await;
+                if inner.cached(Instant::now()).is_some() {
+                    continue;
+                }
+                if let Err(err) = inner.fetch(&http).await {
+                    tracing::warn!(
+                        "Failed to refresh OAuth2 token from {}: {err}",
+                        inner.link.url
+                    );
+                    drop(guard);
+                    drop(inner);
+                    tokio::time::sleep(RETRY_INTERVAL).await;
+                }
+            }
+        });
+    }
+}
+
+impl Inner {
+    fn cached(&self, now: Instant) -> Option<String> {
+        self.token
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_ref()
+            .filter(|token| token.is_fresh(now))
+            .map(|token| token.access_token.clone())
+    }
+
+    /// Requests a token from the token endpoint and caches it.
+    async fn fetch(&self, http: &Arc<dyn HttpIO>) -> Result<String> {
+        let link = &self.link;
+        let mut body = url::form_urlencoded::Serializer::new(String::new());
+        match link.grant_type {
+            OAuth2GrantType::ClientCredentials => {
+                body.append_pair("grant_type", "client_credentials");
+            }
+            OAuth2GrantType::TokenExchange => {
+                body.append_pair("grant_type", TOKEN_EXCHANGE_GRANT)
+                    .append_pair(
+                        "subject_token",
+                        link.subject_token.as_deref().unwrap_or_default(),
+                    )
+                    .append_pair(
+                        "subject_token_type",
+                        link.subject_token_type
+                            .as_deref()
+                            .unwrap_or(ACCESS_TOKEN_TYPE),
+                    );
+            }
+        }
+        if !link.scopes.is_empty() {
+            body.append_pair("scope", &link.scopes.join(" "));
+        }
+        if let Some(audience) = &link.audience {
+            body.append_pair("audience", audience);
+        }
+
+        let mut request = reqwest::Request::new(reqwest::Method::POST, link.url.parse()?);
+        request.headers_mut().insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static("application/x-www-form-urlencoded"),
+        );
+        request.headers_mut().typed_insert(Authorization::basic(
+            &link.client_id,
+            link.client_secret.as_deref().unwrap_or_default(),
+        ));
+        *request.body_mut() = Some(body.finish().into());
+
+        let response = http.execute(request).await?;
+        if !response.status.is_success() {
+            bail!("Token endpoint responded with {}", response.status);
+        }
+
+        let response: TokenResponse = serde_json::from_slice(&response.body)
+            .context("Token endpoint returned an invalid response")?;
+        let refresh_at = response.expires_in.map(|expires_in| {
+            refresh_at(
+                Instant::now(),
+                Duration::from_secs(expires_in),
+                link.refresh_before(),
+            )
+        });
+
+        *self.token.write().unwrap_or_else(PoisonError::into_inner) =
+            Some(Token { access_token: response.access_token.clone(), refresh_at });
+
+        Ok(response.access_token)
+    }
+}
+
+/// When a token that expires in `expires_in` must be replaced. Short-lived
+/// tokens are replaced halfway through their lifetime rather than
+/// `refresh_before` ahead, which would be immediately, and never sooner than
+/// [`MIN_REFRESH_INTERVAL`].
+fn refresh_at(now: Instant, expires_in: Duration, refresh_before: Duration) -> Instant {
+    now + expires_in
+        .saturating_sub(refresh_before.min(expires_in / 2))
+        .max(MIN_REFRESH_INTERVAL)
+}
+
+impl PartialEq for OAuth2Client {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner) || self.inner.link == other.inner.link
+    }
+}
+
+impl Eq for OAuth2Client {}
+
+impl Debug for OAuth2Client {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OAuth2Client")
+            .field("url", &self.inner.link.url)
+            .field("hosts", &self.inner.link.hosts)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Sends requests to the hosts of an `OAuth2` link with its access token,
+/// replacing any `Authorization` header forwarded from the client.
+pub struct OAuth2Http {
+    inner: Arc<dyn HttpIO>,
+    clients: Vec<OAuth2Client>,
+}
+
+impl OAuth2Http {
+    /// Wraps the HTTP clients of `runtime` when any upstream has OAuth2
+    /// credentials.
+    #[must_use]
+    pub fn wrap(mut runtime: TargetRuntime, clients: &[OAuth2Client]) -> TargetRuntime {
+        if clients.is_empty() {
+            return runtime;
+        }
+
+        runtime.http = Arc::new(Self { inner: runtime.http, clients: clients.to_vec() });
+        runtime.http2_only =
+            Arc::new(Self { inner: runtime.http2_only, clients: clients.to_vec() });
+        runtime
+    }
+
+    async fn authorize(&self, request: &mut reqwest::Request) -> Result<Option<&OAuth2Client>> {
+        let Some(client) = self
+            .clients
+            .iter()
+            .find(|client| client.matches(request.url()))
+        else {
+            return Ok(None);
+        };
+
+        let token = client
+            .token(&self.inner)
+            .await
+            .with_context(|| format!("Failed to fetch OAuth2 token from {}", client.link().url))?;
+        request
+            .headers_mut()
+            .typed_insert(Authorization::<Bearer>::bearer(&token)?);
+
+        Ok(Some(client))
+    }
+}
+
+#[async_trait::async_trait]
+impl HttpIO for OAuth2Http {
+    async fn execute(&self, mut request: reqwest::Request) -> Result<Response<Bytes>> {
+        let client = self.authorize(&mut request).await?;
+        let response = self.inner.execute(request).await?;
+        if let Some(client) = client
+            && response.status == reqwest::StatusCode::UNAUTHORIZED
+        {
+            client.invalidate();
+        }
+        Ok(response)
+    }
+
+    async fn execute_raw(&self, mut request: reqwest::Request) -> Result<reqwest::Response> {
+        let client = self.authorize(&mut request).await?;
+        let response = self.inner.execute_raw(request).await?;
+        if let Some(client) = client
+            && response.status() == reqwest::StatusCode::UNAUTHORIZED
+        {
+            client.invalidate();
+        }
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![expect(clippy::unwrap_used, reason = "test code")]
+    use std::sync::Mutex;
+
+    use http::HeaderMap;
+
+    use super::*;
+
+    /// Issues a new token for each request to the token endpoint and records
+    /// the `Authorization` header of requests to the upstream, which rejects
+    /// requests to `/reject`.
+    #[derive(Default)]
+    struct TestHttp {
+        issued: Mutex<u32>,
+        token_requests: Mutex<Vec<String>>,
+        upstream_auth: Mutex<Vec<Option<String>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl HttpIO for TestHttp {
+        async fn execute(&self, request: reqwest::Request) -> Result<Response<Bytes>> {
+            if request.url().path() == "/oauth2/token" {
+                let body = request.body().and_then(|body| body.as_bytes()).unwrap();
+                self.token_requests
+                    .lock()
+                    .unwrap()
+                    .push(String::from_utf8(body.to_vec()).unwrap());
+
+                let mut issued = self.issued.lock().unwrap();
+                *issued += 1;
+                let body = serde_json::json!({
+                    "access_token": format!("token-{issued}"),
+                    "token_type": "Bearer",
+                    "expires_in": 3600,
+                });
+                return Ok(Response {
+                    status: reqwest::StatusCode::OK,
+                    headers: HeaderMap::new(),
+                    body: Bytes::from(body.to_string()),
+                });
+            }
+
+            let auth = request
+                .headers()
+                .get(http::header::AUTHORIZATION)
+                .map(|value| value.to_str().unwrap().to_owned());
+            let status = if request.url().path() == "/reject" {
+                reqwest::StatusCode::UNAUTHORIZED
+            } else {
+                reqwest::StatusCode::OK
+            };
+            self.upstream_auth.lock().unwrap().push(auth);
+
+            Ok(Response { status, headers: HeaderMap::new(), body: Bytes::new() })
+        }
+    }
+
+    fn link() -> OAuth2Link {
+        OAuth2Link {
+            url: "https://auth.example.com/oauth2/token".to_owned(),
+            client_id: "gateway".to_owned(),
+            client_secret: Some("secret".to_owned()),
+            scopes: vec!["orders:read".to_owned(), "orders:write".to_owned()],
+            hosts: vec![
+                "orders.internal".to_owned(),
+                "*.billing.internal:8443".to_owned(),
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn request(url: &str) -> reqwest::Request {
+        reqwest::Request::new(reqwest::Method::GET, url.parse().unwrap())
+    }
+
+    #[test]
+    fn matches_hosts() {
+        let client = OAuth2Client::new(link());
+        let matches = |url: &str| client.matches(&url.parse().unwrap());
+
+        assert!(matches("https://orders.internal/orders"));
+        assert!(matches("http://ORDERS.internal:8080/orders"));
+        assert!(matches("https://eu.billing.internal:8443/invoices"));
+        assert!(!matches("https://eu.billing.internal/invoices"));
+        assert!(!matches("https://billing.internal:8443/invoices"));
+        assert!(!matches("https://users.internal/users"));
+    }
+
+    #[tokio::test]
+    async fn injects_and_caches_tokens() {
+        let test_http = Arc::new(TestHttp::default());
+        let http = OAuth2Http {
+            inner: test_http.clone(),
+            clients: vec![OAuth2Client::new(link())],
+        };
+
+        http.execute(request("https://orders.internal/orders"))
+            .await
+            .unwrap();
+        http.execute(request("https://orders.internal/orders"))
+            .await
+            .unwrap();
+        http.execute(request("https://users.internal/users"))
+            .await
+            .unwrap();
+
+        let mut authorized = request("https://orders.internal/orders");
+        authorized
+            .headers_mut()
+            .typed_insert(Authorization::<Bearer>::bearer("own-token").unwrap());
+        http.execute(authorized).await.unwrap();
+
+        assert_eq!(
+            *test_http.upstream_auth.lock().unwrap(),
+            vec![
+                Some("Bearer token-1".to_owned()),
+                Some("Bearer token-1".to_owned()),
+                None,
+                Some("Bearer token-1".to_owned())
+            ]
+        );
+        assert_eq!(
+            *test_http.token_requests.lock().unwrap(),
+            vec!["grant_type=client_credentials&scope=orders%3Aread+orders%3Awrite".to_owned()]
+        );
+    }
+
+    #[tokio::test]
+    async fn fetches_a_new_token_after_a_rejection() {
+        let test_http = Arc::new(TestHttp::default());
+        let http = OAuth2Http {
+            inner: test_http.clone(),
+            clients: vec![OAuth2Client::new(link())],
+        };
+
+        let response = http
+            .execute(request("https://orders.internal/reject"))
+            .await;
+        assert_eq!(response.unwrap().status, reqwest::StatusCode::UNAUTHORIZED);
+        http.execute(request("https://orders.internal/orders"))
+            .await
+            .unwrap();
+        http.execute(request("https://orders.internal/orders"))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            *test_http.upstream_auth.lock().unwrap(),
+            vec![
+                Some("Bearer token-1".to_owned()),
+                Some("Bearer token-2".to_owned()),
+                Some("Bearer token-2".to_owned())
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn exchanges_subject_tokens() {
+        let test_http = Arc::new(TestHttp::default());
+        let client = OAuth2Client::new(OAuth2Link {
+            grant_type: OAuth2GrantType::TokenExchange,
+            subject_token: Some("workload-token".to_owned()),
+            scopes: vec![],
+            audience: Some("orders-api".to_owned()),
+            ..link()
+        });
+
+        let inner: Arc<dyn HttpIO> = test_http.clone();
+        assert_eq!(client.token(&inner).await.unwrap(), "token-1");
+        assert_eq!(
+            *test_http.token_requests.lock().unwrap(),
+            vec![
+                "grant_type=urn%3Aietf%3Aparams%3Aoauth%3Agrant-type%3Atoken-exchange\
+                 &subject_token=workload-token\
+                 &subject_token_type=urn%3Aietf%3Aparams%3Aoauth%3Atoken-type%3Aaccess_token\
+                 &audience=orders-api"
+                    .to_owned()
+            ]
+        );
+    }
+
+    #[test]
+    fn refreshes_before_expiry() {
+        let now = Instant::now();
+        let token = Token {
+            access_token: "token".to_owned(),
+            refresh_at: Some(now + Duration::from_secs(60)),
+        };
+
+        assert!(token.is_fresh(now));
+        assert!(!token.is_fresh(now + Duration::from_secs(60)));
+        assert!(Token { refresh_at: None, ..token }.is_fresh(now));
+    }
+
+    #[test]
+    fn refreshes_short_lived_tokens_halfway() {
+        let now = Instant::now();
+        let refresh_before = link().refresh_before();
+
+        assert_eq!(
+            refresh_at(now, Duration::from_secs(3600), refresh_before),
+            now + Duration::from_secs(3600) - refresh_before
+        );
+        assert_eq!(
+            refresh_at(now, Duration::from_secs(30), refresh_before),
+            now + Duration::from_secs(15)
+        );
+        assert_eq!(
+            refresh_at(now, Duration::ZERO, refresh_before),
+            now + MIN_REFRESH_INTERVAL
+        );
+        assert_eq!(
+            refresh_at(now, Duration::from_secs(1), refresh_before),
+            now + MIN_REFRESH_INTERVAL
+        );
+    }
+
+    #[tokio::test]
+    async fn replaces_the_client_authorization() {
+        let server = httpmock::MockServer::start();
+        let token_mock = server.mock(|when, then| {
+            when.method(httpmock::Method::POST).path("/oauth2/token");
+            then.status(200).json_body(serde_json::json!({
+                "access_token": "upstream-token",
+                "token_type": "Bearer",
+                "expires_in": 3600,
+            }));
+        });
+        let upstream_mock = server.mock(|when, then| {
+            when.method(httpmock::Method::GET)
+                .path("/orders")
+                .header("authorization", "Bearer upstream-token");
+            then.status(200).body("[]");
+        });
+
+        let runtime = crate::core::runtime::test::init(&None);
+        let client = OAuth2Client::new(OAuth2Link {
+            url: server.url("/oauth2/token"),
+            hosts: vec![format!("{}:{}", server.host(), server.port())],
+            ..link()
+        });
+        let runtime = OAuth2Http::wrap(runtime, &[client]);
+
+        let mut request = request(&server.url("/orders"));
+        request
+            .headers_mut()
+            .typed_insert(Authorization::<Bearer>::bearer("client-token").unwrap());
+        let response = runtime.http.execute(request).await.unwrap();
+
+        assert_eq!(response.status, reqwest::StatusCode::OK);
+        token_mock.assert();
+        upstream_mock.assert();
+    }
+}
@@ -0,0 +1,209 @@
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use x509_cert::Certificate;
+use x509_cert::der::asn1::{Ia5StringRef, PrintableStringRef, Utf8StringRef};
+use x509_cert::der::{Any, Decode};
+use x509_cert::ext::pkix::SubjectAltName;
+use x509_cert::ext::pkix::name::GeneralName;
+
+/// The certificate a client authenticated with over mutual TLS, exposed as
+/// `tls` to access expressions and templates.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct PeerCertificate {
+    /// Subject attributes keyed by their short name, such as `CN` or `O`.
+    /// When an attribute repeats, the last value is kept.
+    pub subject: BTreeMap<String, String>,
+    pub sans: SubjectAltNames,
+    /// Hex-encoded SHA-256 digest of the DER-encoded certificate.
+    pub fingerprint: String,
+}
+
+/// Subject alternative names of a certificate, grouped by kind.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct SubjectAltNames {
+    pub dns: Vec<String>,
+    pub email: Vec<String>,
+    pub uri: Vec<String>,
+    pub ip: Vec<String>,
+}
+
+impl PeerCertificate {
+    /// Parses a DER-encoded X.509 certificate.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the certificate can't be decoded.
+    pub fn from_der(der: &[u8]) -> anyhow::Result<Self> {
+        let cert = Certificate::from_der(der)?;
+        let tbs = &cert.tbs_certificate;
+
+        let subject = tbs
+            .subject
+            .0
+            .iter()
+            .flat_map(|rdn| rdn.0.iter())
+            .filter_map(|attr| {
+                let value = attribute_value(&attr.value)?;
+                Some((short_name(&attr.oid.to_string()), value))
+            })
+            .collect();
+
+        let mut sans = SubjectAltNames::default();
+        if let Some((_, SubjectAltName(names))) = tbs.get::<SubjectAltName>()? {
+            for name in names {
+                match name {
+                    GeneralName::DnsName(dns) => sans.dns.push(dns.to_string()),
+                    GeneralName::Rfc822Name(email) => sans.email.push(email.to_string()),
+                    GeneralName::UniformResourceIdentifier(uri) => sans.uri.push(uri.to_string()),
+                    GeneralName::IpAddress(ip) => {
+                        if let Some(ip) = ip_address(ip.as_bytes()) {
+                            sans.ip.push(ip.to_string());
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let fingerprint = Sha256::digest(der)
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+
+        Ok(Self { subject, sans, fingerprint })
+    }
+
+    #[must_use]
+    pub fn to_value(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+
+    /// The value at `path` within [`Self::to_value`], such as `subject.CN` or
+    /// `sans.dns.0`. Lists are indexed by position.
+    #[must_use]
+    pub fn get<T: AsRef<str>>(&self, path: &[T]) -> Option<serde_json::Value> {
+        let mut current = self.to_value();
+        for segment in path {
+            current = match current {
+                serde_json::Value::Array(mut items) => items
+                    .get_mut(segment.as_ref().parse::<usize>().ok()?)?
+                    .take(),
+                mut other => other.get_mut(segment.as_ref())?.take(),
+            };
+        }
+        Some(current)
+    }
+}
+
+/// Short names of the usual distinguished name attributes, the dotted OID
+/// for any other attribute.
+fn short_name(oid: &str) -> String {
+    match oid {
+        "2.5.4.3" => "CN",
+        "2.5.4.5" => "serialNumber",
+        "2.5.4.6" => "C",
+        "2.5.4.7" => "L",
+        "2.5.4.8" => "ST",
+        "2.5.4.10" => "O",
+        "2.5.4.11" => "OU",
+        "0.9.2342.19200300.100.1.1" => "UID",
+        "0.9.2342.19200300.100.1.25" => "DC",
+        "1.2.840.113549.1.9.1" => "emailAddress",
+        other => other,
+    }
+    .to_owned()
+}
+
+fn attribute_value(value: &Any) -> Option<String> {
+    value
+        .decode_as::<Utf8StringRef<'_>>()
+        .map(|s| s.to_string())
+        .or_else(|_| {
+            value
+                .decode_as::<PrintableStringRef<'_>>()
+                .map(|s| s.to_string())
+        })
+        .or_else(|_| value.decode_as::<Ia5StringRef<'_>>().map(|s| s.to_string()))
+        .ok()
+}
+
+fn ip_address(bytes: &[u8]) -> Option<IpAddr> {
+    match bytes.len() {
+        4 => <[u8; 4]>::try_from(bytes).ok().map(IpAddr::from),
+        16 => <[u8; 16]>::try_from(bytes).ok().map(IpAddr::from),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![expect(clippy::unwrap_used, reason = "test code")]
+    use rustls_pki_types::CertificateDer;
+
+    use super::*;
+
+    fn client_certificate() -> CertificateDer<'static> {
+        let pem = include_str!("fixtures/client.crt");
+        rustls_pemfile::certs(&mut pem.as_bytes())
+            .next()
+            .unwrap()
+            .unwrap()
+    }
+
+    #[test]
+    fn reads_subject_sans_and_fingerprint() {
+        let cert = PeerCertificate::from_der(&client_certificate()).unwrap();
+
+        assert_eq!(
+            cert.subject,
+            BTreeMap::from([
+                ("C".to_owned(), "US".to_owned()),
+                ("CN".to_owned(), "orders-service".to_owned()),
+                ("O".to_owned(), "Acme".to_owned()),
+                ("OU".to_owned(), "Platform".to_owned()),
+            ])
+        );
+        assert_eq!(
+            cert.sans,
+            SubjectAltNames {
+                dns: vec!["orders.internal".to_owned()],
+                email: vec!["orders@acme.internal".to_owned()],
+                uri: vec!["spiffe://acme.internal/orders".to_owned()],
+                ip: vec!["10.0.0.12".to_owned()],
+            }
+        );
+        assert_eq!(
+            cert.fingerprint,
+            "f9b5b45d9575da661d36ce39666ab4b73f0b93e2595aa3375826b749e9ef1c72"
+        );
+    }
+
+    #[test]
+    fn reads_values_by_path() {
+        let cert = PeerCertificate::from_der(&client_certificate()).unwrap();
+
+        assert_eq!(
+            cert.get(&["subject", "CN"]),
+            Some(serde_json::json!("orders-service"))
+        );
+        assert_eq!(
+            cert.get(&["sans", "dns", "0"]),
+            Some(serde_json::json!("orders.internal"))
+        );
+        assert_eq!(
+            cert.get(&["sans", "dns"]),
+            Some(serde_json::json!(["orders.internal"]))
+        );
+        assert_eq!(cert.get(&["sans", "dns", "1"]), None);
+        assert_eq!(cert.get(&["subject", "L"]), None);
+        assert_eq!(cert.get::<&str>(&[]), Some(cert.to_value()));
+    }
+
+    #[test]
+    fn rejects_invalid_certificates() {
+        assert!(PeerCertificate::from_der(b"not a certificate").is_err());
+    }
+}
@@ -1,6 +1,8 @@
+use std::collections::HashMap;
 use std::num::NonZeroU64;
 use std::str::FromStr;
 use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
 
 use async_graphql_value::ConstValue;
 use cache_control::{Cachability, CacheControl};
@@ -12,9 +14,9 @@ use crate::core::blueprint::{Server, Upstream};
 use crate::core::data_loader::{DataLoader, DedupeResult};
 use crate::core::graphql::GraphqlDataLoader;
 use crate::core::grpc::data_loader::GrpcDataLoader;
-use crate::core::http::{DataLoaderRequest, HttpDataLoader};
-use crate::core::ir::Error;
+use crate::core::http::{DataLoaderRequest, HttpDataLoader, IdentitySigner, PeerCertificate};
 use crate::core::ir::model::IoId;
+use crate::core::ir::{Error, IoMetrics};
 use crate::core::runtime::TargetRuntime;
 use crate::core::{cache, grpc};
 
@@ -27,6 +29,17 @@ pub struct RequestContext {
     // A subset of all the headers received in the GraphQL Request that will be sent to the
     // upstream.
     pub allowed_headers: HeaderMap,
+    /// Headers of the incoming request, used by auth providers that read
+    /// credentials from a custom header. Unlike `allowed_headers`, they are
+    /// never forwarded upstream.
+    pub request_headers: HeaderMap,
+    /// Query string of the incoming request, used by auth providers that
+    /// read credentials from query parameters.
+    pub request_query: Option<String>,
+    /// `Cookie` header of the incoming request, used by auth providers that
+    /// read credentials from cookies. Kept apart from `allowed_headers` so
+    /// cookies are never forwarded upstream.
+    pub request_cookies: Option<String>,
     pub http_data_loaders: Arc<Vec<DataLoader<DataLoaderRequest, HttpDataLoader>>>,
     pub gql_data_loaders: Arc<Vec<DataLoader<DataLoaderRequest, GraphqlDataLoader>>>,
     pub grpc_data_loaders: Arc<Vec<DataLoader<grpc::DataLoaderRequest, GrpcDataLoader>>>,
@@ -36,6 +49,22 @@ pub struct RequestContext {
     pub cache: DedupeResult<IoId, ConstValue, Error>,
     pub dedupe_handler: Arc<DedupeResult<IoId, ConstValue, Error>>,
     pub auth_claims: Arc<Mutex<Option<serde_json::Value>>>,
+    /// Whether the auth providers accepted the request, once a `@mask` needed
+    /// its claims. Set at most once, so masked values share one verification.
+    pub mask_verification: Arc<tokio::sync::OnceCell<bool>>,
+    /// Certificate the client presented over mutual TLS, if any.
+    pub peer_certificate: Option<Arc<PeerCertificate>>,
+    /// Identity tokens minted for the request by operation name, with the
+    /// time they were minted.
+    pub identity_tokens: Arc<Mutex<HashMap<Option<String>, (HeaderValue, Instant)>>>,
+    /// When the `globalResponseTimeout` of the request elapses, if one is set.
+    pub deadline: Option<Instant>,
+    /// Records upstream requests, when telemetry is exported.
+    pub io_metrics: Option<Arc<IoMetrics>>,
+    /// Name of the client, from the `apollographql-client-name` header.
+    pub client_name: Option<String>,
+    /// Version of the client, from the `apollographql-client-version` header.
+    pub client_version: Option<String>,
 }
 
 impl RequestContext {
@@ -55,7 +84,17 @@ impl RequestContext {
             cache: DedupeResult::new(true),
             dedupe_handler: Arc::new(DedupeResult::new(false)),
             allowed_headers: HeaderMap::new(),
+            request_headers: HeaderMap::new(),
+            request_query: None,
+            request_cookies: None,
             auth_claims: Arc::new(Mutex::new(None)),
+            mask_verification: Arc::new(tokio::sync::OnceCell::new()),
+            peer_certificate: None,
+            identity_tokens: Arc::new(Mutex::new(HashMap::new())),
+            deadline: None,
+            io_metrics: None,
+            client_name: None,
+            client_version: None,
         }
     }
     fn set_min_max_age_conc(&self, min_max_age: i32) {
@@ -213,6 +252,85 @@ impl RequestContext {
             .clone()
     }
 
+    /// Time left before the `globalResponseTimeout` of the request elapses,
+    /// if one is set.
+    #[must_use]
+    pub fn remaining_time(&self) -> Option<Duration> {
+        self.deadline
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()))
+    }
+
+    /// The `@upstream(identity:)` token for a request to `url`, if its host
+    /// receives one. Tokens are minted once per operation and reused until
+    /// half their lifetime has passed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the token can't be signed.
+    pub fn identity_header(
+        &self,
+        url: &reqwest::Url,
+        operation_name: Option<&str>,
+    ) -> anyhow::Result<Option<(HeaderName, HeaderValue)>> {
+        let Some(signer) = self.identity_signer(url) else {
+            return Ok(None);
+        };
+
+        let now = Instant::now();
+        let mut tokens = self
+            .identity_tokens
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let key = operation_name.map(ToOwned::to_owned);
+
+        let token = match tokens.get(&key) {
+            Some((token, minted_at)) if now.duration_since(*minted_at) < signer.ttl() / 2 => {
+                token.clone()
+            }
+            _ => {
+                let token = signer.sign(self.get_auth_claims().as_ref(), operation_name)?;
+                tokens.insert(key, (token.clone(), now));
+                token
+            }
+        };
+
+        Ok(Some((signer.header_name().clone(), token)))
+    }
+
+    /// Whether requests to `url` carry the caller's identity token. Data
+    /// loaders are shared between requests, so such requests are neither
+    /// batched nor deduplicated with those of other callers.
+    #[must_use]
+    pub fn signs_identity(&self, url: &reqwest::Url) -> bool {
+        self.identity_signer(url).is_some()
+    }
+
+    fn identity_signer(&self, url: &reqwest::Url) -> Option<&IdentitySigner> {
+        self.upstream
+            .identity
+            .as_ref()
+            .filter(|signer| signer.matches(url))
+    }
+
+    /// Value of the `name` parameter of the request query string.
+    #[must_use]
+    pub fn query_param(&self, name: &str) -> Option<String> {
+        url::form_urlencoded::parse(self.request_query.as_deref()?.as_bytes())
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+    }
+
+    /// Value of the `name` cookie sent with the request.
+    #[must_use]
+    pub fn cookie(&self, name: &str) -> Option<String> {
+        self.request_cookies
+            .as_deref()?
+            .split(';')
+            .filter_map(|cookie| cookie.trim().split_once('='))
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value.trim_matches('"').to_owned())
+    }
+
     pub fn is_batching_enabled(&self) -> bool {
         self.upstream.is_batching_enabled()
     }
@@ -272,15 +390,28 @@ impl From<&AppContext> for RequestContext {
             x_response_headers: Arc::new(Mutex::new(HeaderMap::new())),
             cookie_headers,
             allowed_headers: HeaderMap::new(),
+            request_headers: HeaderMap::new(),
+            request_query: None,
+            request_cookies: None,
             http_data_loaders: app_ctx.http_data_loaders.clone(),
             gql_data_loaders: app_ctx.gql_data_loaders.clone(),
             grpc_data_loaders: app_ctx.grpc_data_loaders.clone(),
             min_max_age: Arc::new(Mutex::new(None)),
             cache_public: Arc::new(Mutex::new(None)),
             runtime: app_ctx.runtime.clone(),
-            cache: DedupeResult::new(true),
+            cache: DedupeResult::new(true).name("request"),
             dedupe_handler: app_ctx.dedupe_handler.clone(),
             auth_claims: Arc::new(Mutex::new(None)),
+            mask_verification: Arc::new(tokio::sync::OnceCell::new()),
+            peer_certificate: None,
+            identity_tokens: Arc::new(Mutex::new(HashMap::new())),
+            deadline: u64::try_from(app_ctx.blueprint.server.global_response_timeout)
+                .ok()
+                .filter(|timeout| *timeout > 0)
+                .map(|timeout| Instant::now() + Duration::from_millis(timeout)),
+            io_metrics: app_ctx.io_metrics.clone(),
+            client_name: None,
+            client_version: None,
         }
     }
 }
@@ -288,11 +419,13 @@ impl From<&AppContext> for RequestContext {
 #[cfg(test)]
 mod test {
     #![expect(clippy::unwrap_used, reason = "test code")]
+    use std::time::{Duration, Instant};
+
     use cache_control::Cachability;
 
     use crate::core::blueprint::{Server, Upstream};
     use crate::core::config::{self, Batch};
-    use crate::core::http::RequestContext;
+    use crate::core::http::{IdentitySigner, RequestContext};
 
     impl Default for RequestContext {
         fn default() -> Self {
@@ -375,4 +508,67 @@ mod test {
             create_req_ctx_with_batch(Batch { delay: 1, max_size: Some(1), ..Default::default() });
         assert!(req_ctx.is_batching_enabled());
     }
+
+    #[test]
+    fn test_identity_header_per_operation() {
+        let identity = config::Identity {
+            key: "identity".to_string(),
+            algorithm: config::IdentityAlgorithm::RS256,
+            hosts: vec!["orders.internal".to_string()],
+            claims: vec![],
+            issuer: None,
+            audience: None,
+            ttl: None,
+            header: None,
+            jwks_path: None,
+        };
+        let signer = IdentitySigner::try_new(
+            identity,
+            include_str!("../../../tests/server/config/example-rsa.key"),
+        )
+        .unwrap();
+        let req_ctx =
+            RequestContext::default().upstream(Upstream::default().identity(Some(signer)));
+        let orders = "http://orders.internal/orders".parse().unwrap();
+
+        let (name, first) = req_ctx
+            .identity_header(&orders, Some("GetOrders"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(name, "x-gqlforge-identity");
+
+        let (_, again) = req_ctx
+            .identity_header(&orders, Some("GetOrders"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(first, again);
+
+        let (_, other) = req_ctx
+            .identity_header(&orders, Some("GetOrder"))
+            .unwrap()
+            .unwrap();
+        assert_ne!(first, other);
+
+        let external = "https://api.example.com/orders".parse().unwrap();
+        assert!(
+            req_ctx
+                .identity_header(&external, Some("GetOrders"))
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_remaining_time() {
+        let req_ctx = RequestContext::default();
+        assert_eq!(req_ctx.remaining_time(), None);
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let req_ctx = req_ctx.deadline(Some(deadline));
+        let remaining = req_ctx.remaining_time().unwrap();
+        assert!(remaining > Duration::ZERO && remaining <= Duration::from_secs(10));
+
+        let req_ctx = req_ctx.deadline(Some(Instant::now()));
+        assert_eq!(req_ctx.remaining_time(), Some(Duration::ZERO));
+    }
 }
@@ -17,8 +17,13 @@ use tracing::Instrument;
 use tracing_opentelemetry::OpenTelemetrySpanExt;
 
 use super::request_context::RequestContext;
-use super::telemetry::{RequestCounter, get_response_status_code};
-use super::{GQLFORGE_HTTP_ORIGIN, GQLFORGE_HTTPS_ORIGIN, showcase, telemetry};
+use super::telemetry::{
+    CLIENT_NAME_HEADER, CLIENT_VERSION_HEADER, RequestCounter, get_response_status_code,
+};
+use super::{
+    GQLFORGE_HTTP_ORIGIN, GQLFORGE_HTTPS_ORIGIN, IdentitySigner, PeerCertificate, showcase,
+    telemetry,
+};
 use crate::core::app_context::AppContext;
 use crate::core::async_graphql_hyper::{GraphQLRequestLike, GraphQLResponse};
 use crate::core::blueprint::telemetry::TelemetryExporter;
@@ -49,16 +54,38 @@ fn prometheus_metrics(prometheus_exporter: &PrometheusExporter) -> Result<Respon
         .body(Full::new(Bytes::from(buffer)))?)
 }
 
+/// Publishes the key that signs `@upstream(identity:)` tokens, so upstreams
+/// can verify them.
+fn identity_jwks(signer: &IdentitySigner) -> Result<Response<Full<Bytes>>> {
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header(CONTENT_TYPE, "application/json")
+        .body(Full::new(Bytes::from(serde_json::to_vec(signer.jwks())?)))?)
+}
+
 fn not_found() -> Result<Response<Full<Bytes>>> {
     Ok(Response::builder()
         .status(StatusCode::NOT_FOUND)
         .body(Full::default())?)
 }
 
-fn create_request_context(req: &Request<Full<Bytes>>, app_ctx: &AppContext) -> RequestContext {
+pub(super) fn create_request_context(
+    req: &Request<Full<Bytes>>,
+    app_ctx: &AppContext,
+) -> RequestContext {
     let allowed_headers =
         create_allowed_headers(req.headers(), &app_ctx.blueprint.upstream.allowed_headers);
-    RequestContext::from(app_ctx).allowed_headers(allowed_headers)
+    RequestContext::from(app_ctx)
+        .allowed_headers(allowed_headers)
+        .request_headers(req.headers().clone())
+        .request_query(req.uri().query().map(ToOwned::to_owned))
+        .request_cookies(request_cookies(req.headers()))
+        .peer_certificate(req.extensions().get::<Arc<PeerCertificate>>().cloned())
+        .client_name(telemetry::client_header(req.headers(), CLIENT_NAME_HEADER))
+        .client_version(telemetry::client_header(
+            req.headers(),
+            CLIENT_VERSION_HEADER,
+        ))
 }
 
 pub fn update_response_headers(
@@ -150,6 +177,18 @@ async fn execute_query<T: DeserializeOwned + GraphQLRequestLike>(
     Ok(response)
 }
 
+/// Joins the `Cookie` headers of a request, which HTTP/2 clients may split
+/// into one header per cookie.
+fn request_cookies(headers: &HeaderMap) -> Option<String> {
+    let cookies = headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .collect::<Vec<_>>();
+
+    (!cookies.is_empty()).then(|| cookies.join("; "))
+}
+
 fn create_allowed_headers(headers: &HeaderMap, allowed: &BTreeSet<String>) -> HeaderMap {
     let mut new_headers = HeaderMap::with_capacity(allowed.len());
     for (k, v) in headers {
@@ -249,13 +288,16 @@ async fn handle_request_with_cors<T: DeserializeOwned + GraphQLRequestLike>(
     }
 }
 
+/// Serves the `@rest` endpoints of `app_ctx`. They are executed with
+/// `schema_ctx`, which is the contract on the GraphQL route when there is one.
 async fn handle_rest_apis(
     mut request: Request<Full<Bytes>>,
-    app_ctx: Arc<AppContext>,
+    app_ctx: &AppContext,
+    schema_ctx: Arc<AppContext>,
     req_counter: &mut RequestCounter,
 ) -> Result<Response<Full<Bytes>>> {
     *request.uri_mut() = request.uri().path().replace(API_URL_PREFIX, "").parse()?;
-    let req_ctx = Arc::new(create_request_context(&request, app_ctx.as_ref()));
+    let req_ctx = Arc::new(create_request_context(&request, schema_ctx.as_ref()));
     if let Some(p_request) = app_ctx.endpoints.matches(&request) {
         let http_route = format!("{API_URL_PREFIX}{}", p_request.path.as_str());
         req_counter.set_http_route(&http_route);
@@ -270,15 +312,15 @@ async fn handle_rest_apis(
             let graphql_request = p_request.into_request(request).await?;
             let mut response = graphql_request
                 .data(req_ctx.clone())
-                .execute(&app_ctx.schema)
+                .execute(&schema_ctx.schema)
                 .await
                 .set_cache_control(
-                    app_ctx.blueprint.server.enable_cache_control_header,
+                    schema_ctx.blueprint.server.enable_cache_control_header,
                     req_ctx.get_min_max_age().unwrap_or(0),
                     req_ctx.is_cache_public().unwrap_or(true),
                 )
-                .into_rest_response()?;
-            update_response_headers(&mut response, &req_ctx, &app_ctx);
+                .into_rest_response(&schema_ctx.blueprint.server.grpc_status_map)?;
+            update_response_headers(&mut response, &req_ctx, &schema_ctx);
             Ok(response)
         }
         .instrument(span)
@@ -293,8 +335,15 @@ async fn handle_request_inner<T: DeserializeOwned + GraphQLRequestLike>(
     app_ctx: Arc<AppContext>,
     req_counter: &mut RequestCounter,
 ) -> Result<Response<Full<Bytes>>> {
+    let schema_ctx = app_ctx.schema_context(req.uri().path());
     if req.uri().path().starts_with(API_URL_PREFIX) {
-        return handle_rest_apis(req, app_ctx, req_counter).await;
+        return handle_rest_apis(req, &app_ctx, schema_ctx, req_counter).await;
+    }
+
+    // Contracts are matched first, as one may replace the full schema on the
+    // GraphQL route
+    if req.method() == Method::POST && app_ctx.contracts.contains_key(req.uri().path()) {
+        return graphql_request::<T>(req, &schema_ctx, req_counter).await;
     }
 
     let health_check_endpoint = app_ctx.blueprint.server.routes.status();
@@ -327,6 +376,12 @@ async fn handle_request_inner<T: DeserializeOwned + GraphQLRequestLike>(
             Ok(status_response)
         }
         Method::GET => {
+            if let Some(signer) = &app_ctx.blueprint.upstream.identity
+                && req.uri().path() == signer.identity().get_jwks_path()
+            {
+                return identity_jwks(signer);
+            }
+
             if let Some(TelemetryExporter::Prometheus(prometheus)) =
                 app_ctx.blueprint.telemetry.export.as_ref()
                 && req.uri().path() == prometheus.path
@@ -458,6 +513,234 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_identity_jwks_endpoint() -> anyhow::Result<()> {
+        use crate::core::config::{Identity, IdentityAlgorithm};
+
+        let sdl = tokio::fs::read_to_string(gqlforge_fixtures::configs::JSONPLACEHOLDER).await?;
+        let config = Config::from_sdl(&sdl).to_result()?;
+        let mut blueprint = Blueprint::try_from(&ConfigModule::from(config))?;
+        let identity = Identity {
+            key: "identity".to_string(),
+            algorithm: IdentityAlgorithm::RS256,
+            hosts: vec!["orders.internal".to_string()],
+            claims: vec![],
+            issuer: None,
+            audience: None,
+            ttl: None,
+            header: None,
+            jwks_path: None,
+        };
+        blueprint.upstream.identity = Some(IdentitySigner::try_new(
+            identity,
+            include_str!("../../../tests/server/config/example-rsa.key"),
+        )?);
+        let app_ctx = Arc::new(AppContext::new(
+            blueprint,
+            init(&None),
+            EndpointSet::default(),
+        ));
+
+        let req = Request::builder()
+            .method(Method::GET)
+            .uri("http://localhost:8000/.well-known/jwks.json".to_string())
+            .body(Full::default())?;
+
+        let resp = handle_request::<GraphQLRequest>(req, app_ctx).await?;
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = resp.into_body().collect().await?.to_bytes();
+        let jwks: serde_json::Value = serde_json::from_slice(&body)?;
+        assert_eq!(jwks["keys"][0]["kty"], "RSA");
+        assert_eq!(jwks["keys"][0]["use"], "sig");
+        assert!(jwks["keys"][0].get("d").is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_identity_requests_are_not_batched() -> anyhow::Result<()> {
+        use crate::core::config::{Identity, IdentityAlgorithm};
+
+        let server = httpmock::MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(httpmock::Method::GET)
+                .path("/users/1")
+                .header_exists("x-gqlforge-identity");
+            then.status(200).json_body(serde_json::json!({ "id": 1 }));
+        });
+
+        let sdl = format!(
+            r#"
+            schema @server @upstream(batch: {{delay: 50, maxSize: 10}}) {{
+              query: Query
+            }}
+
+            type Query {{
+              user: User @http(url: "{}")
+            }}
+
+            type User {{
+              id: Int
+            }}
+            "#,
+            server.url("/users/1")
+        );
+        let config = Config::from_sdl(&sdl).to_result()?;
+        let mut blueprint = Blueprint::try_from(&ConfigModule::from(config))?;
+        let identity = Identity {
+            key: "identity".to_string(),
+            algorithm: IdentityAlgorithm::RS256,
+            hosts: vec![format!("{}:{}", server.host(), server.port())],
+            claims: vec![],
+            issuer: None,
+            audience: None,
+            ttl: None,
+            header: None,
+            jwks_path: None,
+        };
+        blueprint.upstream.identity = Some(IdentitySigner::try_new(
+            identity,
+            include_str!("../../../tests/server/config/example-rsa.key"),
+        )?);
+        let app_ctx = Arc::new(AppContext::new(
+            blueprint,
+            init(&None),
+            EndpointSet::default(),
+        ));
+
+        // Each request has its own context and identity token, but both share
+        // the app's data loaders.
+        let request = |operation: &str| {
+            let query = format!(
+                r#"{{"query": "query {operation} {{ user {{ id }} }}", "operationName": "{operation}"}}"#
+            );
+            Request::builder()
+                .method(Method::POST)
+                .uri("http://localhost:8000/graphql".to_string())
+                .header("Content-Type", "application/json")
+                .body(Full::new(Bytes::from(query)))
+        };
+        let (first, second) = tokio::join!(
+            handle_request::<GraphQLRequest>(request("GetUser")?, app_ctx.clone()),
+            handle_request::<GraphQLRequest>(request("GetMe")?, app_ctx.clone()),
+        );
+
+        for resp in [first?, second?] {
+            let body = resp.into_body().collect().await?.to_bytes();
+            let body: serde_json::Value = serde_json::from_slice(&body)?;
+            assert_eq!(body["data"]["user"]["id"], 1);
+        }
+        mock.assert_calls(2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_contract_endpoint() -> anyhow::Result<()> {
+        use crate::core::config::Contract;
+
+        let sdl = r#"
+            schema @server @upstream {
+              query: Query
+            }
+
+            type Query {
+              users: [User] @http(url: "http://jsonplaceholder.typicode.com/users")
+              audit: [String] @http(url: "http://jsonplaceholder.typicode.com/audit") @tag(name: "internal")
+            }
+
+            type User {
+              id: Int!
+              name: String
+            }
+        "#;
+        let config = Config::from_sdl(sdl).to_result()?;
+        let mut blueprint = Blueprint::try_from(&ConfigModule::from(config))?;
+        blueprint.server.contracts = vec![
+            Contract {
+                route: "/graphql".to_string(),
+                exclude_tags: vec!["internal".to_string()],
+            },
+            Contract { route: "/internal/graphql".to_string(), exclude_tags: vec![] },
+        ];
+        let app_ctx = Arc::new(AppContext::new(
+            blueprint,
+            init(&None),
+            EndpointSet::default(),
+        ));
+
+        let query_fields = |route: &str| {
+            let query = r#"{"query": "{ __type(name: \"Query\") { fields { name } } }"}"#;
+            let req = Request::builder()
+                .method(Method::POST)
+                .uri(format!("http://localhost:8000{route}"))
+                .header("Content-Type", "application/json")
+                .body(Full::new(Bytes::from(query)))
+                .unwrap();
+            let app_ctx = app_ctx.clone();
+            async move {
+                let resp = handle_request::<GraphQLRequest>(req, app_ctx)
+                    .await
+                    .unwrap();
+                let body = resp.into_body().collect().await.unwrap().to_bytes();
+                serde_json::from_slice::<serde_json::Value>(&body).unwrap()["data"]["__type"]
+                    ["fields"]
+                    .clone()
+            }
+        };
+
+        assert_eq!(
+            query_fields("/graphql").await,
+            serde_json::json!([{"name": "users"}])
+        );
+        assert_eq!(
+            query_fields("/internal/graphql").await,
+            serde_json::json!([{"name": "users"}, {"name": "audit"}])
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_api_key_header_not_forwarded() -> anyhow::Result<()> {
+        use http::header::HeaderValue;
+
+        use crate::core::config::{ApiKeyLink, ApiKeySource, Content, Extensions};
+
+        let sdl = tokio::fs::read_to_string(gqlforge_fixtures::configs::JSONPLACEHOLDER).await?;
+        let config = Config::from_sdl(&sdl).to_result()?;
+        let extensions = Extensions {
+            api_keys: vec![Content {
+                id: None,
+                content: ApiKeyLink {
+                    source: ApiKeySource::Header("X-Api-Key".to_string()),
+                    keys: vec![],
+                },
+            }],
+            ..Default::default()
+        };
+        let blueprint =
+            Blueprint::try_from(&ConfigModule::from(config).set_extensions(extensions))?;
+        let app_ctx = AppContext::new(blueprint, init(&None), EndpointSet::default());
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("http://localhost:8000/graphql".to_string())
+            .header("X-Api-Key", HeaderValue::from_static("secret-key"))
+            .body(Full::default())?;
+        let req_ctx = create_request_context(&req, &app_ctx);
+
+        // The verifier reads the key, but it isn't sent to upstreams
+        assert!(!req_ctx.allowed_headers.contains_key("x-api-key"));
+        assert_eq!(
+            req_ctx.request_headers.get("x-api-key").unwrap(),
+            "secret-key"
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_create_allowed_headers() {
         use std::collections::BTreeSet;
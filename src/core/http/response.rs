@@ -8,7 +8,10 @@ use prost::Message;
 use tonic::Status;
 use tonic_types::Status as GrpcStatus;
 
+use crate::core::config::GrpcProtocol;
+use crate::core::grpc::compression::{content_encoding, message_encoding};
 use crate::core::grpc::protobuf::ProtobufOperation;
+use crate::core::grpc::status::decode_error_details;
 use crate::core::ir::Error;
 
 #[derive(Clone, Debug, Default, Setters)]
@@ -135,62 +138,57 @@ impl Response<Bytes> {
         operation: &ProtobufOperation,
     ) -> Result<Response<async_graphql::Value>> {
         let mut resp = Response::default();
-        let body = operation.convert_output::<async_graphql::Value>(&self.body)?;
+        let body = match operation.protocol {
+            GrpcProtocol::Grpc | GrpcProtocol::GrpcWeb => {
+                let encoding = message_encoding(&self.headers)?;
+                operation.convert_encoded_output::<async_graphql::Value>(&self.body, encoding)?
+            }
+            GrpcProtocol::Connect => {
+                let encoding = content_encoding(&self.headers)?;
+                operation.convert_unframed_output::<async_graphql::Value>(&self.body, encoding)?
+            }
+        };
         resp.body = body;
         resp.status = self.status;
         resp.headers = self.headers;
         Ok(resp)
     }
 
-    pub fn to_grpc_error(&self, operation: &ProtobufOperation) -> anyhow::Error {
+    /// The error of a failed gRPC call, from its `grpc-status` headers. Error
+    /// details are decoded with the linked protobuf files, or as standard
+    /// `google.rpc` details.
+    #[must_use]
+    pub fn to_grpc_error(&self, operation: &ProtobufOperation) -> Error {
         let Some(grpc_status) = Status::from_header_map(&self.headers) else {
-            return Error::IO("Error while parsing upstream headers".to_owned()).into();
+            return Error::IO("Error while parsing upstream headers".to_owned());
         };
 
         let mut obj: IndexMap<Name, async_graphql::Value> = IndexMap::new();
         let mut status_details = Vec::new();
+        let mut grpc_extensions = IndexMap::new();
         if !grpc_status.details().is_empty() {
             if let Ok(status) = GrpcStatus::decode(grpc_status.details()) {
                 obj.insert(Name::new("code"), status.code.into());
                 obj.insert(Name::new("message"), status.message.clone().into());
 
-                for detail in status.details {
-                    let type_url = &detail.type_url;
-                    let type_name = type_url.split('/').next_back().unwrap_or("");
-
-                    match operation.find_message(type_name) {
-                        Some(message) => match message.decode(detail.value.as_slice()) {
-                            Ok(decoded) => {
-                                status_details.push(decoded);
-                            }
-                            _ => {
-                                tracing::error!("Error while decoding message: {type_name}");
-                            }
-                        },
-                        _ => {
-                            tracing::error!(
-                                "Error while searching descriptor for message: {type_name}"
-                            );
-                        }
-                    }
-                }
+                let details = status.details.iter().map(|detail| {
+                    let type_name = detail.type_url.split('/').next_back().unwrap_or("");
+                    (type_name, detail.value.as_slice())
+                });
+                (status_details, grpc_extensions) = decode_error_details(operation, details);
             } else {
                 tracing::error!("Error while decoding gRPC status details");
             }
         }
         obj.insert(Name::new("details"), ConstValue::List(status_details));
 
-        let error = Error::GRPC {
+        Error::GRPC {
             grpc_code: grpc_status.code() as i32,
             grpc_description: grpc_status.code().description().to_owned(),
             grpc_status_message: grpc_status.message().to_owned(),
             grpc_status_details: ConstValue::Object(obj),
-        };
-
-        // TODO: because of this conversion to anyhow::Error
-        // we lose additional details that could be added
-        // through async_graphql::ErrorExtensions
-        anyhow::Error::new(error)
+            grpc_extensions,
+        }
     }
 
     ///
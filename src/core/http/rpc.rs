@@ -0,0 +1,414 @@
+use std::borrow::Cow;
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail};
+use async_graphql_value::ConstValue;
+use bytes::Bytes;
+use futures_util::StreamExt;
+use futures_util::stream::BoxStream;
+use http::header::{CONTENT_TYPE, HeaderValue};
+use http::{HeaderMap, Response, StatusCode};
+use http_body_util::{BodyExt, Full, StreamBody};
+use hyper::body::Frame;
+use indexmap::IndexMap;
+use serde::Deserialize;
+use tonic::Code;
+
+use super::RequestContext;
+use super::request_handler::create_request_context;
+use crate::core::app_context::AppContext;
+use crate::core::config::GrpcCompression;
+use crate::core::grpc::compression::{MessageTooLarge, decode_message, message_encoding};
+use crate::core::jit::{self, AnyResponse, ConstValueExecutor};
+use crate::core::rest::Checked;
+use crate::core::rpc::{MethodSet, Route, codec};
+
+pub type RpcBody = StreamBody<BoxStream<'static, Result<Frame<Bytes>, Infallible>>>;
+
+static GRPC_STATUS: &str = "grpc-status";
+static GRPC_MESSAGE: &str = "grpc-message";
+
+/// The parts of a GraphQL response a call is answered with.
+#[derive(Deserialize)]
+struct GraphQLResult {
+    #[serde(default)]
+    data: ConstValue,
+    #[serde(default)]
+    errors: Vec<GraphQLResultError>,
+}
+
+#[derive(Deserialize)]
+struct GraphQLResultError {
+    message: String,
+    #[serde(default)]
+    extensions: IndexMap<String, ConstValue>,
+}
+
+impl GraphQLResultError {
+    /// The status code of the error, kept from the gRPC upstream it came
+    /// from.
+    fn code(&self) -> Code {
+        match self.extensions.get("grpcCode") {
+            Some(ConstValue::Number(code)) => code
+                .as_i64()
+                .and_then(|code| i32::try_from(code).ok())
+                .map_or(Code::Unknown, Code::from_i32),
+            _ => Code::Unknown,
+        }
+    }
+}
+
+/// Whether a request is a gRPC call, sent with an `application/grpc`
+/// content type.
+#[must_use]
+pub fn is_rpc_request(headers: &HeaderMap) -> bool {
+    headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| matches!(value, "application/grpc" | "application/grpc+proto"))
+}
+
+/// Answers a call with a message, or with the status of its failure.
+fn grpc_response(
+    message: Option<Vec<u8>>,
+    code: Code,
+    status_message: &str,
+) -> anyhow::Result<Response<RpcBody>> {
+    let mut trailers = HeaderMap::new();
+    trailers.insert(GRPC_STATUS, HeaderValue::from(code as i32));
+    if !status_message.is_empty() {
+        trailers.insert(
+            GRPC_MESSAGE,
+            HeaderValue::from_str(&urlencoding::encode(status_message))?,
+        );
+    }
+
+    let mut frames = Vec::with_capacity(2);
+    if let Some(message) = message {
+        let mut frame = Vec::with_capacity(message.len() + 5);
+        frame.push(0);
+        frame.extend_from_slice(
+            &u32::try_from(message.len())
+                .unwrap_or(u32::MAX)
+                .to_be_bytes(),
+        );
+        frame.extend_from_slice(&message);
+        frames.push(Ok(Frame::data(Bytes::from(frame))));
+    }
+    frames.push(Ok(Frame::trailers(trailers)));
+
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header(CONTENT_TYPE, "application/grpc")
+        .body(StreamBody::new(futures_util::stream::iter(frames).boxed()))?)
+}
+
+fn status_response(code: Code, status_message: &str) -> anyhow::Result<Response<RpcBody>> {
+    grpc_response(None, code, status_message)
+}
+
+/// The payload of the single message of a unary call.
+fn read_message(body: &Bytes, encoding: Option<GrpcCompression>) -> anyhow::Result<Cow<'_, [u8]>> {
+    if body.len() < 5 {
+        bail!("Missing request message");
+    }
+
+    let len = u32::from_be_bytes([body[1], body[2], body[3], body[4]]) as usize;
+    let payload = body
+        .get(5..5 + len)
+        .ok_or_else(|| anyhow!("Incomplete request message"))?;
+    if body.len() > 5 + len {
+        bail!("Unexpected data after the request message");
+    }
+
+    decode_message(body[0] == 1, payload, encoding)
+}
+
+/// Executes the operation of a method through the JIT, planning it on its
+/// first call.
+async fn execute(
+    app_ctx: &Arc<AppContext>,
+    req_ctx: &RequestContext,
+    route: &Route,
+    variables: ConstValue,
+) -> jit::Result<AnyResponse<Vec<u8>>> {
+    let request = route.request(variables);
+    let plan = if let Some(plan) = app_ctx.operation_plans.get(route.plan_hash()) {
+        plan.value().clone()
+    } else {
+        let plan = request.create_plan(&app_ctx.blueprint)?;
+        app_ctx
+            .operation_plans
+            .insert(route.plan_hash().clone(), plan.clone());
+        plan
+    };
+
+    Ok(ConstValueExecutor::from(plan)
+        .execute(app_ctx, req_ctx, request)
+        .await)
+}
+
+/// Handle a unary gRPC call to a method served from an `@rpc` operation.
+///
+/// Decodes the variables of the operation from the request message, executes
+/// it, and encodes its data as the response message. GraphQL errors fail the
+/// call with the status of the first one. The method is looked up in
+/// `methods` and executed with `app_ctx`, which is the contract on the
+/// GraphQL route when there is one.
+///
+/// # Errors
+///
+/// Returns an error if the request body can't be read or the response can't
+/// be built.
+pub async fn handle_rpc_request(
+    req: http::Request<Full<Bytes>>,
+    methods: &MethodSet<Checked>,
+    app_ctx: Arc<AppContext>,
+) -> anyhow::Result<Response<RpcBody>> {
+    let path = req.uri().path().to_string();
+    let Some(route) = methods.find(&path) else {
+        return status_response(Code::Unimplemented, &format!("Unknown method {path}"));
+    };
+
+    let req_ctx = create_request_context(&req, app_ctx.as_ref());
+    let encoding = match message_encoding(req.headers()) {
+        Ok(encoding) => encoding,
+        Err(e) => return status_response(Code::Unimplemented, &e.to_string()),
+    };
+
+    let body = req.into_body().collect().await?.to_bytes();
+    let variables = match read_message(&body, encoding)
+        .and_then(|payload| Ok(codec::decode(route.input(), &payload)?))
+    {
+        Ok(variables) => variables,
+        Err(e) if e.is::<MessageTooLarge>() => {
+            return status_response(Code::ResourceExhausted, &e.to_string());
+        }
+        Err(e) => return status_response(Code::InvalidArgument, &e.to_string()),
+    };
+
+    let response = match execute(&app_ctx, &req_ctx, route, variables).await {
+        Ok(response) => response,
+        Err(e) => return status_response(Code::Internal, &e.to_string()),
+    };
+
+    let result: GraphQLResult = serde_json::from_slice(response.body.as_ref())?;
+    if let Some(error) = result.errors.first() {
+        return status_response(error.code(), &error.message);
+    }
+
+    match codec::encode(route.output(), &result.data) {
+        Ok(message) => grpc_response(Some(message), Code::Ok, ""),
+        Err(e) => status_response(Code::Internal, &e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![expect(clippy::unwrap_used, reason = "test code")]
+    use gqlforge_valid::Validator;
+    use httpmock::MockServer;
+    use serde_json::json;
+
+    use super::*;
+    use crate::core::blueprint::Blueprint;
+    use crate::core::config::{Config, ConfigModule};
+    use crate::core::grpc::compression::{GRPC_ENCODING, MAX_MESSAGE_SIZE, compress};
+    use crate::core::rest::EndpointSet;
+    use crate::core::runtime::test::init;
+
+    const OPERATIONS: &str = r#"
+        query getUser($id: Int!) @rpc {
+            user(id: $id) { id name }
+        }
+    "#;
+
+    /// The context and methods of a schema fetching users from `server`.
+    fn rpc_context(server: &MockServer) -> (Arc<AppContext>, MethodSet<Checked>) {
+        let sdl = format!(
+            r#"
+            schema {{ query: Query }}
+            type Query {{
+                user(id: Int!): User @http(url: "{}/users/{{{{.args.id}}}}")
+            }}
+            type User {{ id: Int! name: String }}
+            "#,
+            server.base_url()
+        );
+        let config = Config::from_sdl(&sdl).to_result().unwrap();
+        let blueprint = Blueprint::try_from(&ConfigModule::from(config)).unwrap();
+        let methods = MethodSet::try_new(OPERATIONS)
+            .unwrap()
+            .into_checked(&blueprint)
+            .unwrap();
+        let app_ctx = AppContext::new(blueprint, init(&None), EndpointSet::default());
+
+        (Arc::new(app_ctx), methods)
+    }
+
+    /// A unary call to `path` with a single framed `message`.
+    fn rpc_request(path: &str, message: &[u8]) -> http::Request<Full<Bytes>> {
+        let mut body = vec![0];
+        body.extend_from_slice(&u32::try_from(message.len()).unwrap().to_be_bytes());
+        body.extend_from_slice(message);
+
+        http::Request::builder()
+            .method(http::Method::POST)
+            .version(http::Version::HTTP_2)
+            .uri(format!("http://localhost:8000{path}"))
+            .header(CONTENT_TYPE, "application/grpc")
+            .body(Full::new(Bytes::from(body)))
+            .unwrap()
+    }
+
+    /// Calls a method, returning the payload of the response message and its
+    /// trailers.
+    async fn call(
+        request: http::Request<Full<Bytes>>,
+        app_ctx: Arc<AppContext>,
+        methods: &MethodSet<Checked>,
+    ) -> (Bytes, HeaderMap) {
+        let response = handle_rpc_request(request, methods, app_ctx).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let collected = response.into_body().collect().await.unwrap();
+        let trailers = collected.trailers().cloned().unwrap();
+        let body = collected.to_bytes();
+        (body.slice(body.len().min(5)..), trailers)
+    }
+
+    #[test]
+    fn test_is_rpc_request() {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/grpc"));
+        assert!(is_rpc_request(&headers));
+
+        headers.insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static("application/grpc-web+proto"),
+        );
+        assert!(!is_rpc_request(&headers));
+    }
+
+    #[test]
+    fn test_read_message() {
+        let body = Bytes::from_static(b"\0\0\0\0\x02\x08\x01");
+        assert_eq!(read_message(&body, None).unwrap().as_ref(), b"\x08\x01");
+
+        let body = Bytes::from_static(b"\0\0\0\0\x05\x08");
+        assert!(read_message(&body, None).is_err());
+
+        let body = Bytes::from_static(b"\0\0\0\0\x02\x08\x01\0\0\0\0\x02\x08\x02");
+        assert!(read_message(&body, None).is_err());
+    }
+
+    #[test]
+    fn test_error_code() {
+        let error: GraphQLResultError =
+            serde_json::from_str(r#"{"message": "Not Found", "extensions": {"grpcCode": 5}}"#)
+                .unwrap();
+        assert_eq!(error.code(), Code::NotFound);
+
+        let error: GraphQLResultError = serde_json::from_str(r#"{"message": "Oops"}"#).unwrap();
+        assert_eq!(error.code(), Code::Unknown);
+    }
+
+    #[tokio::test]
+    async fn test_handle_rpc_request() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(httpmock::Method::GET).path("/users/1");
+            then.status(200)
+                .json_body(json!({ "id": 1, "name": "Leanne Graham" }));
+        });
+        let (app_ctx, methods) = rpc_context(&server);
+        let route = methods.find("/gqlforge.Gateway/GetUser").unwrap();
+        let input = codec::encode(
+            route.input(),
+            &ConstValue::from_json(json!({ "id": 1 })).unwrap(),
+        )
+        .unwrap();
+
+        let request = rpc_request("/gqlforge.Gateway/GetUser", &input);
+        let (message, trailers) = call(request, app_ctx, &methods).await;
+
+        mock.assert();
+        assert_eq!(trailers.get(GRPC_STATUS).unwrap(), "0");
+        assert_eq!(
+            codec::decode(route.output(), &message)
+                .unwrap()
+                .into_json()
+                .unwrap(),
+            json!({ "user": { "id": 1, "name": "Leanne Graham" } })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_handle_rpc_request_upstream_error() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(httpmock::Method::GET).path("/users/2");
+            then.status(500);
+        });
+        let (app_ctx, methods) = rpc_context(&server);
+        let route = methods.find("/gqlforge.Gateway/GetUser").unwrap();
+        let input = codec::encode(
+            route.input(),
+            &ConstValue::from_json(json!({ "id": 2 })).unwrap(),
+        )
+        .unwrap();
+
+        let request = rpc_request("/gqlforge.Gateway/GetUser", &input);
+        let (message, trailers) = call(request, app_ctx, &methods).await;
+
+        mock.assert();
+        assert!(message.is_empty());
+        assert_eq!(
+            trailers.get(GRPC_STATUS).unwrap(),
+            &(Code::Unknown as i32).to_string()
+        );
+        assert!(trailers.contains_key(GRPC_MESSAGE));
+    }
+
+    #[tokio::test]
+    async fn test_handle_rpc_request_message_too_large() {
+        let server = MockServer::start();
+        let (app_ctx, methods) = rpc_context(&server);
+        let message = vec![0; usize::try_from(MAX_MESSAGE_SIZE).unwrap() + 1];
+        let compressed = compress(GrpcCompression::Gzip, &message).unwrap();
+
+        // A single message with the compressed flag set
+        let mut body = vec![1];
+        body.extend_from_slice(&u32::try_from(compressed.len()).unwrap().to_be_bytes());
+        body.extend_from_slice(&compressed);
+
+        let mut request = rpc_request("/gqlforge.Gateway/GetUser", b"");
+        *request.body_mut() = Full::new(Bytes::from(body));
+        request
+            .headers_mut()
+            .insert(GRPC_ENCODING, HeaderValue::from_static("gzip"));
+        let (message, trailers) = call(request, app_ctx, &methods).await;
+
+        assert!(message.is_empty());
+        assert_eq!(
+            trailers.get(GRPC_STATUS).unwrap(),
+            &(Code::ResourceExhausted as i32).to_string()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_handle_rpc_request_unknown_method() {
+        let server = MockServer::start();
+        let (app_ctx, methods) = rpc_context(&server);
+
+        let request = rpc_request("/gqlforge.Gateway/Unknown", b"");
+        let (message, trailers) = call(request, app_ctx, &methods).await;
+
+        assert!(message.is_empty());
+        assert_eq!(
+            trailers.get(GRPC_STATUS).unwrap(),
+            &(Code::Unimplemented as i32).to_string()
+        );
+    }
+}
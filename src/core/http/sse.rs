@@ -6,8 +6,8 @@ use http::{Response, StatusCode, header};
 use http_body_util::{BodyExt, Full, StreamBody};
 use hyper::body::Frame;
 
+use super::request_handler::create_request_context;
 use crate::core::app_context::AppContext;
-use crate::core::http::RequestContext;
 
 pub type SseBody = StreamBody<
     futures_util::stream::BoxStream<'static, Result<Frame<Bytes>, std::convert::Infallible>>,
@@ -25,11 +25,14 @@ pub async fn handle_sse_request(
     req: http::Request<Full<Bytes>>,
     app_ctx: Arc<AppContext>,
 ) -> anyhow::Result<Response<SseBody>> {
+    // Built before the body is consumed, so protected subscriptions see the
+    // credentials of the request.
+    let req_ctx = Arc::new(create_request_context(&req, app_ctx.as_ref()));
+
     let body_bytes = req.into_body().collect().await?.to_bytes();
     let graphql_req: async_graphql::Request = serde_json::from_slice(&body_bytes)
         .map_err(|e| anyhow::anyhow!("Failed to parse GraphQL request: {e}"))?;
 
-    let req_ctx = Arc::new(RequestContext::from(app_ctx.as_ref()));
     let graphql_req = graphql_req.data(req_ctx);
 
     let stream = app_ctx.schema.execute_stream(graphql_req);
@@ -1,6 +1,6 @@
 use anyhow::Result;
 use bytes::Bytes;
-use http::{Request, Response};
+use http::{HeaderMap, Request, Response};
 use http_body_util::Full;
 use opentelemetry::KeyValue;
 use opentelemetry::metrics::Counter;
@@ -12,6 +12,10 @@ use tracing_opentelemetry::OpenTelemetrySpanExt;
 
 use crate::core::blueprint::telemetry::Telemetry;
 
+/// Headers clients identify themselves with, recorded with their operations.
+pub const CLIENT_NAME_HEADER: &str = "apollographql-client-name";
+pub const CLIENT_VERSION_HEADER: &str = "apollographql-client-version";
+
 static HTTP_SERVER_REQUEST_COUNT: std::sync::LazyLock<Counter<u64>> =
     std::sync::LazyLock::new(|| {
         let meter = opentelemetry::global::meter("http_request");
@@ -75,6 +79,15 @@ pub fn get_response_status_code(response: &Response<Full<Bytes>>) -> KeyValue {
     )
 }
 
+/// Value of a client identification header, if it's valid UTF-8.
+pub fn client_header(headers: &HeaderMap, name: &str) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| !value.is_empty())
+        .map(ToOwned::to_owned)
+}
+
 pub fn propagate_context(req: &Request<Full<Bytes>>) {
     let context = opentelemetry::global::get_text_map_propagator(|propagator| {
         propagator.extract(&HeaderExtractor(req.headers()))
@@ -1,12 +1,14 @@
+use std::cmp::Ordering;
 use std::sync::PoisonError;
 
 use nom::branch::alt;
 use nom::bytes::complete::{tag, take_while1};
-use nom::character::complete::{char, multispace0};
-use nom::combinator::{map, value};
-use nom::multi::separated_list1;
-use nom::sequence::{delimited, preceded};
+use nom::character::complete::{char, multispace0, satisfy};
+use nom::combinator::{map, not, value};
+use nom::multi::{separated_list0, separated_list1};
+use nom::sequence::{delimited, preceded, terminated};
 use nom::{IResult, Parser};
+use serde_json::Value;
 
 use super::{EvalContext, ResolverContextLike};
 use crate::core::path::PathString;
@@ -18,6 +20,18 @@ pub enum AccessExpr {
     Not(Box<AccessExpr>),
     Eq(Operand, Operand),
     Neq(Operand, Operand),
+    Lt(Operand, Operand),
+    Lte(Operand, Operand),
+    Gt(Operand, Operand),
+    Gte(Operand, Operand),
+    /// The left operand is an element of the right list.
+    In(Operand, Operand),
+    /// The left list contains the right operand.
+    Contains(Operand, Operand),
+    StartsWith(Operand, Operand),
+    /// Scopes granted to the token, given as alternatives of scopes that must
+    /// all be granted, as in `@requiresScopes(scopes: [["a", "b"], ["c"]])`.
+    Scopes(Vec<Vec<String>>),
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -26,6 +40,7 @@ pub enum Operand {
     StringLiteral(String),
     NumberLiteral(i64),
     BoolLiteral(bool),
+    ListLiteral(Vec<Operand>),
 }
 
 impl AccessExpr {
@@ -56,29 +71,152 @@ impl AccessExpr {
             AccessExpr::Or(left, right) => Ok(left.evaluate(ctx)? || right.evaluate(ctx)?),
             AccessExpr::Not(expr) => Ok(!expr.evaluate(ctx)?),
             AccessExpr::Eq(left, right) => {
-                let l = resolve_operand(left, ctx);
-                let r = resolve_operand(right, ctx);
+                let (l, r) = resolve_equality(left, right, ctx);
                 Ok(l == r)
             }
             AccessExpr::Neq(left, right) => {
+                let (l, r) = resolve_equality(left, right, ctx);
+                Ok(l != r)
+            }
+            AccessExpr::Lt(left, right) => {
+                Ok(compare(left, right, ctx).is_some_and(Ordering::is_lt))
+            }
+            AccessExpr::Lte(left, right) => {
+                Ok(compare(left, right, ctx).is_some_and(Ordering::is_le))
+            }
+            AccessExpr::Gt(left, right) => {
+                Ok(compare(left, right, ctx).is_some_and(Ordering::is_gt))
+            }
+            AccessExpr::Gte(left, right) => {
+                Ok(compare(left, right, ctx).is_some_and(Ordering::is_ge))
+            }
+            AccessExpr::In(left, right) => Ok(contains(right, left, ctx)),
+            AccessExpr::Contains(left, right) => Ok(contains(left, right, ctx)),
+            AccessExpr::StartsWith(left, right) => {
                 let l = resolve_operand(left, ctx);
                 let r = resolve_operand(right, ctx);
-                Ok(l != r)
+                match (l, r) {
+                    (Some(Value::String(l)), Some(Value::String(r))) => Ok(l.starts_with(&r)),
+                    _ => Ok(false),
+                }
+            }
+            AccessExpr::Scopes(alternatives) => {
+                let granted = granted_scopes(ctx);
+                Ok(alternatives
+                    .iter()
+                    .any(|scopes| scopes.iter().all(|scope| granted.contains(scope))))
             }
         }
     }
+
+    /// Combines expressions so that all of them must hold, `None` if there are
+    /// none.
+    #[must_use]
+    pub fn all(exprs: impl IntoIterator<Item = AccessExpr>) -> Option<AccessExpr> {
+        exprs
+            .into_iter()
+            .reduce(|acc, expr| AccessExpr::And(Box::new(acc), Box::new(expr)))
+    }
+
+    /// Combines expressions so that any of them must hold, `None` if there are
+    /// none.
+    #[must_use]
+    pub fn any(exprs: impl IntoIterator<Item = AccessExpr>) -> Option<AccessExpr> {
+        exprs
+            .into_iter()
+            .reduce(|acc, expr| AccessExpr::Or(Box::new(acc), Box::new(expr)))
+    }
+}
+
+/// Resolves the operands of `==` and `!=`. An `args` or `value` path
+/// compared with a string resolves to its string form, as it did before these
+/// paths kept their GraphQL types, so `args.id == '1'` and
+/// `claims.sub == args.id` still hold for `id: 1`.
+fn resolve_equality<Ctx: ResolverContextLike + Sync>(
+    left: &Operand,
+    right: &Operand,
+    ctx: &EvalContext<'_, Ctx>,
+) -> (Option<Value>, Option<Value>) {
+    let l = resolve_operand(left, ctx);
+    let r = resolve_operand(right, ctx);
+    let is_string = |value: Option<&Value>| matches!(value, Some(Value::String(_)));
+    let as_string = |operand: &Operand, value: Option<Value>, other_is_string: bool| match operand {
+        Operand::Path(segments)
+            if other_is_string
+                && segments
+                    .first()
+                    .is_some_and(|head| head == "args" || head == "value") =>
+        {
+            ctx.path_string(segments)
+                .map(|s| Value::String(s.into_owned()))
+        }
+        _ => value,
+    };
+    let (l_is_string, r_is_string) = (is_string(l.as_ref()), is_string(r.as_ref()));
+    (
+        as_string(left, l, r_is_string),
+        as_string(right, r, l_is_string),
+    )
+}
+
+/// Orders two numbers, `None` if either operand isn't a number.
+fn compare<Ctx: ResolverContextLike + Sync>(
+    left: &Operand,
+    right: &Operand,
+    ctx: &EvalContext<'_, Ctx>,
+) -> Option<Ordering> {
+    let l = resolve_operand(left, ctx)?.as_f64()?;
+    let r = resolve_operand(right, ctx)?.as_f64()?;
+    l.partial_cmp(&r)
+}
+
+/// Whether `list` resolves to an array holding the value of `item`.
+fn contains<Ctx: ResolverContextLike + Sync>(
+    list: &Operand,
+    item: &Operand,
+    ctx: &EvalContext<'_, Ctx>,
+) -> bool {
+    let Some(Value::Array(list)) = resolve_operand(list, ctx) else {
+        return false;
+    };
+    resolve_operand(item, ctx).is_some_and(|item| list.contains(&item))
+}
+
+/// Scopes granted to the token, read from the space-separated `scope` claim
+/// (RFC 8693) or from the `scp` and `scopes` claims.
+fn granted_scopes<Ctx: ResolverContextLike + Sync>(ctx: &EvalContext<'_, Ctx>) -> Vec<String> {
+    let guard = ctx
+        .request_ctx
+        .auth_claims
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    let Some(claims) = guard.as_ref() else {
+        return vec![];
+    };
+
+    ["scope", "scp", "scopes"]
+        .iter()
+        .filter_map(|claim| claims.get(claim))
+        .flat_map(|value| match value {
+            Value::String(scopes) => scopes.split_whitespace().map(String::from).collect(),
+            Value::Array(scopes) => scopes
+                .iter()
+                .filter_map(Value::as_str)
+                .map(String::from)
+                .collect(),
+            _ => vec![],
+        })
+        .collect()
 }
 
 fn resolve_operand<Ctx: ResolverContextLike + Sync>(
     operand: &Operand,
     ctx: &EvalContext<'_, Ctx>,
-) -> Option<serde_json::Value> {
+) -> Option<Value> {
     match operand {
-        Operand::Path(segments) => {
+        Operand::Path(segments) => match segments.split_first() {
             // For claims paths, resolve directly from serde_json::Value to preserve types
-            if segments.first().map(std::string::String::as_str) == Some("claims")
-                && segments.len() > 1
-            {
+            Some((head, tail)) if head == "claims" && !tail.is_empty() => {
                 let guard = ctx
                     .request_ctx
                     .auth_claims
@@ -86,18 +224,39 @@ fn resolve_operand<Ctx: ResolverContextLike + Sync>(
                     .unwrap_or_else(PoisonError::into_inner);
                 let claims = guard.as_ref()?;
                 let mut current = claims;
-                for segment in &segments[1..] {
+                for segment in tail {
                     current = current.get(segment.as_str())?;
                 }
                 Some(current.clone())
-            } else {
-                ctx.path_string(segments)
-                    .map(|s| serde_json::Value::String(s.into_owned()))
             }
-        }
-        Operand::StringLiteral(s) => Some(serde_json::Value::String(s.clone())),
+            // The client certificate keeps its lists, so SANs can be matched with
+            // `in` and `contains`
+            Some((head, tail)) if head == "tls" => {
+                ctx.request_ctx.peer_certificate.as_ref()?.get(tail)
+            }
+            // `scopes` is the list of granted scopes, as in `scopes contains 'a'`
+            Some((head, tail)) if head == "scopes" && tail.is_empty() => Some(Value::Array(
+                granted_scopes(ctx).into_iter().map(Value::String).collect(),
+            )),
+            // Arguments and the parent value keep their GraphQL types as well
+            Some((head, tail)) if head == "args" => {
+                ctx.path_arg(tail)?.into_owned().into_json().ok()
+            }
+            Some((head, tail)) if head == "value" => {
+                ctx.path_value(tail)?.into_owned().into_json().ok()
+            }
+            _ => ctx
+                .path_string(segments)
+                .map(|s| Value::String(s.into_owned())),
+        },
+        Operand::StringLiteral(s) => Some(Value::String(s.clone())),
         Operand::NumberLiteral(n) => Some(serde_json::json!(*n)),
-        Operand::BoolLiteral(b) => Some(serde_json::Value::Bool(*b)),
+        Operand::BoolLiteral(b) => Some(Value::Bool(*b)),
+        Operand::ListLiteral(items) => items
+            .iter()
+            .map(|item| resolve_operand(item, ctx))
+            .collect::<Option<Vec<_>>>()
+            .map(Value::Array),
     }
 }
 
@@ -174,10 +333,23 @@ fn parse_bool_literal(input: &str) -> IResult<&str, Operand> {
     Ok((remaining, op))
 }
 
+fn parse_list_literal(input: &str) -> IResult<&str, Operand> {
+    map(
+        delimited(
+            char('['),
+            separated_list0(preceded(multispace0, char(',')), parse_operand),
+            preceded(multispace0, char(']')),
+        ),
+        Operand::ListLiteral,
+    )
+    .parse(input)
+}
+
 fn parse_operand(input: &str) -> IResult<&str, Operand> {
     let (input, _) = multispace0(input)?;
 
     alt((
+        parse_list_literal,
         parse_string_literal,
         parse_bool_literal,
         parse_number_literal,
@@ -186,16 +358,42 @@ fn parse_operand(input: &str) -> IResult<&str, Operand> {
     .parse(input)
 }
 
+/// A word operator, which must not be followed by an identifier character so
+/// that e.g. `index` isn't read as `in`.
+fn keyword<'a>(
+    word: &'static str,
+) -> impl Parser<&'a str, Output = &'a str, Error = nom::error::Error<&'a str>> {
+    terminated(tag(word), not(satisfy(is_ident_char)))
+}
+
 fn parse_comparison(input: &str) -> IResult<&str, AccessExpr> {
     let (input, left) = parse_operand(input)?;
     let (input, _) = multispace0(input)?;
-    let (input, op) = alt((tag("!="), tag("=="))).parse(input)?;
+    let (input, op) = alt((
+        tag("!="),
+        tag("=="),
+        tag("<="),
+        tag(">="),
+        tag("<"),
+        tag(">"),
+        keyword("in"),
+        keyword("contains"),
+        keyword("startsWith"),
+    ))
+    .parse(input)?;
     let (input, _) = multispace0(input)?;
     let (input, right) = parse_operand(input)?;
     let (input, _) = multispace0(input)?;
     match op {
         "==" => Ok((input, AccessExpr::Eq(left, right))),
         "!=" => Ok((input, AccessExpr::Neq(left, right))),
+        "<=" => Ok((input, AccessExpr::Lte(left, right))),
+        ">=" => Ok((input, AccessExpr::Gte(left, right))),
+        "<" => Ok((input, AccessExpr::Lt(left, right))),
+        ">" => Ok((input, AccessExpr::Gt(left, right))),
+        "in" => Ok((input, AccessExpr::In(left, right))),
+        "contains" => Ok((input, AccessExpr::Contains(left, right))),
+        "startsWith" => Ok((input, AccessExpr::StartsWith(left, right))),
         _ => unreachable!(),
     }
 }
@@ -498,4 +696,223 @@ mod tests {
         let expr = AccessExpr::parse("claims.level == '42'").unwrap();
         assert!(!expr.evaluate(&eval_ctx).unwrap());
     }
+
+    #[test]
+    fn test_parse_operators() {
+        let path = |p: &str| Operand::Path(p.split('.').map(String::from).collect());
+
+        assert_eq!(
+            AccessExpr::parse("claims.level >= 3").unwrap(),
+            AccessExpr::Gte(path("claims.level"), Operand::NumberLiteral(3))
+        );
+        assert_eq!(
+            AccessExpr::parse("args.limit<100").unwrap(),
+            AccessExpr::Lt(path("args.limit"), Operand::NumberLiteral(100))
+        );
+        assert_eq!(
+            AccessExpr::parse("claims.role in ['admin', 'mod']").unwrap(),
+            AccessExpr::In(
+                path("claims.role"),
+                Operand::ListLiteral(vec![
+                    Operand::StringLiteral("admin".into()),
+                    Operand::StringLiteral("mod".into()),
+                ])
+            )
+        );
+        assert_eq!(
+            AccessExpr::parse("claims.groups contains value.team").unwrap(),
+            AccessExpr::Contains(path("claims.groups"), path("value.team"))
+        );
+        assert_eq!(
+            AccessExpr::parse("args.path startsWith 'public/'").unwrap(),
+            AccessExpr::StartsWith(path("args.path"), Operand::StringLiteral("public/".into()))
+        );
+    }
+
+    #[test]
+    fn test_parse_keyword_boundary() {
+        // "index" must not be read as the `in` operator followed by "dex"
+        assert!(AccessExpr::parse("claims.role index ['admin']").is_err());
+        assert!(AccessExpr::parse("claims.role in[]").is_ok());
+    }
+
+    #[test]
+    fn test_evaluate_operators() {
+        use crate::core::http::RequestContext;
+        use crate::core::ir::EmptyResolverContext;
+
+        let runtime = crate::core::runtime::test::init(&None);
+        let req_ctx = RequestContext::new(runtime);
+        req_ctx.set_auth_claims(serde_json::json!({
+            "level": 3,
+            "role": "editor",
+            "groups": ["eng", "ops"],
+            "tenant": "acme",
+        }));
+
+        let res_ctx = EmptyResolverContext {};
+        let eval_ctx = EvalContext::new(&req_ctx, &res_ctx).with_args(
+            async_graphql::Value::from_json(
+                serde_json::json!({ "limit": 50, "path": "acme/reports", "team": "eng" }),
+            )
+            .unwrap(),
+        );
+
+        let cases = [
+            ("claims.level > 2", true),
+            ("claims.level <= 2", false),
+            ("args.limit < 100", true),
+            ("args.limit >= claims.level", true),
+            ("claims.role > 2", false),
+            ("claims.role in ['admin', 'editor']", true),
+            ("claims.role in ['admin']", false),
+            ("claims.groups contains args.team", true),
+            ("claims.groups contains 'sales'", false),
+            ("claims.role contains 'edit'", false),
+            ("args.path startsWith 'acme/'", true),
+            ("args.path startsWith claims.role", false),
+            ("args.limit == 50", true),
+            ("args.limit == '50'", true),
+        ];
+
+        for (expr, expected) in cases {
+            let result = AccessExpr::parse(expr)
+                .unwrap()
+                .evaluate(&eval_ctx)
+                .unwrap();
+            assert_eq!(result, expected, "{expr}");
+        }
+    }
+
+    #[test]
+    fn test_evaluate_args_compared_with_string_literals() {
+        use crate::core::http::RequestContext;
+        use crate::core::ir::EmptyResolverContext;
+
+        let runtime = crate::core::runtime::test::init(&None);
+        let req_ctx = RequestContext::new(runtime);
+        let res_ctx = EmptyResolverContext {};
+        req_ctx.set_auth_claims(serde_json::json!({ "sub": "1", "level": 1 }));
+        let eval_ctx = EvalContext::new(&req_ctx, &res_ctx).with_args(
+            async_graphql::Value::from_json(serde_json::json!({ "id": 1, "draft": false }))
+                .unwrap(),
+        );
+
+        // Strings compare with the string form of typed arguments, as they did
+        // before arguments kept their types.
+        let cases = [
+            ("args.id == '1'", true),
+            ("claims.sub == args.id", true),
+            ("claims.level == args.id", true),
+            ("'1' == args.id", true),
+            ("args.id != '1'", false),
+            ("args.id == '2'", false),
+            ("args.draft == 'false'", true),
+            ("args.id == 1", true),
+            ("args.draft == false", true),
+        ];
+
+        for (expr, expected) in cases {
+            let result = AccessExpr::parse(expr)
+                .unwrap()
+                .evaluate(&eval_ctx)
+                .unwrap();
+            assert_eq!(result, expected, "{expr}");
+        }
+    }
+
+    #[test]
+    fn test_evaluate_scopes() {
+        use crate::core::http::RequestContext;
+        use crate::core::ir::EmptyResolverContext;
+
+        let scopes = |alternatives: &[&[&str]]| {
+            AccessExpr::Scopes(
+                alternatives
+                    .iter()
+                    .map(|scopes| scopes.iter().map(|scope| (*scope).to_owned()).collect())
+                    .collect(),
+            )
+        };
+
+        let runtime = crate::core::runtime::test::init(&None);
+        let req_ctx = RequestContext::new(runtime);
+        req_ctx.set_auth_claims(serde_json::json!({
+            "scope": "read:users write:users",
+            "scp": ["read:orders"],
+        }));
+
+        let res_ctx = EmptyResolverContext {};
+        let eval_ctx = EvalContext::new(&req_ctx, &res_ctx);
+
+        assert!(
+            scopes(&[&["read:users", "read:orders"]])
+                .evaluate(&eval_ctx)
+                .unwrap()
+        );
+        assert!(
+            scopes(&[&["admin"], &["write:users"]])
+                .evaluate(&eval_ctx)
+                .unwrap()
+        );
+        assert!(
+            !scopes(&[&["admin"], &["read:users", "admin"]])
+                .evaluate(&eval_ctx)
+                .unwrap()
+        );
+
+        let contains = |expr: &str| {
+            AccessExpr::parse(expr)
+                .unwrap()
+                .evaluate(&eval_ctx)
+                .unwrap()
+        };
+        assert!(contains("scopes contains 'read:orders'"));
+        assert!(!contains("scopes contains 'admin'"));
+    }
+
+    #[test]
+    fn test_evaluate_tls() {
+        use std::collections::BTreeMap;
+        use std::sync::Arc;
+
+        use crate::core::http::{PeerCertificate, RequestContext, SubjectAltNames};
+        use crate::core::ir::EmptyResolverContext;
+
+        let runtime = crate::core::runtime::test::init(&None);
+        let req_ctx =
+            RequestContext::new(runtime).peer_certificate(Some(Arc::new(PeerCertificate {
+                subject: BTreeMap::from([("CN".to_owned(), "orders-service".to_owned())]),
+                sans: SubjectAltNames {
+                    uri: vec!["spiffe://acme.internal/orders".to_owned()],
+                    ..Default::default()
+                },
+                fingerprint: "f9b5".to_owned(),
+            })));
+
+        let res_ctx = EmptyResolverContext {};
+        let eval_ctx = EvalContext::new(&req_ctx, &res_ctx);
+
+        let evaluate = |expr: &str| {
+            AccessExpr::parse(expr)
+                .unwrap()
+                .evaluate(&eval_ctx)
+                .unwrap()
+        };
+
+        assert!(evaluate(r#"tls.subject.CN == "orders-service""#));
+        assert!(evaluate(
+            r#"tls.sans.uri contains "spiffe://acme.internal/orders""#
+        ));
+        assert!(!evaluate(r#"tls.subject.CN == "billing-service""#));
+
+        let req_ctx = RequestContext::new(crate::core::runtime::test::init(&None));
+        let eval_ctx = EvalContext::new(&req_ctx, &res_ctx);
+        assert!(
+            !AccessExpr::parse(r#"tls.subject.CN == "orders-service""#)
+                .unwrap()
+                .evaluate(&eval_ctx)
+                .unwrap()
+        );
+    }
 }
@@ -2,11 +2,13 @@ use std::fmt::Display;
 use std::sync::Arc;
 
 use async_graphql::Value as ConstValue;
+use async_graphql_value::Name;
 use derive_more::From;
+use indexmap::IndexMap;
 use thiserror::Error;
 
 use crate::core::jit::graphql_error::{Error as ExtensionError, ErrorExtensions};
-use crate::core::{Errata, auth, cache, worker};
+use crate::core::{Errata, auth, cache, grpc, worker};
 
 #[derive(From, Debug, Error, Clone)]
 pub enum Error {
@@ -20,6 +22,8 @@ pub enum Error {
         grpc_description: String,
         grpc_status_message: String,
         grpc_status_details: ConstValue,
+        /// Extensions read from the standard `google.rpc` error details.
+        grpc_extensions: IndexMap<Name, ConstValue>,
     },
 
     APIValidation(Vec<String>),
@@ -32,6 +36,9 @@ pub enum Error {
 
     Auth(auth::error::Error),
 
+    #[from(ignore)]
+    Forbidden(String),
+
     Worker(worker::Error),
 
     Cache(cache::Error),
@@ -56,7 +63,7 @@ impl From<Error> for Errata {
                 grpc_code,
                 grpc_description,
                 grpc_status_message,
-                grpc_status_details: _,
+                ..
             } => Errata::new("gRPC Error")
                 .description(format!("status: {grpc_code}, description: `{grpc_description}`, message: `{grpc_status_message}`")),
             Error::APIValidation(errors) => Errata::new("API Validation Error")
@@ -70,6 +77,7 @@ impl From<Error> for Errata {
             Error::Auth(err) => {
                 Errata::new("Authentication Failure").description(err.to_string())
             }
+            Error::Forbidden(message) => Errata::new("Forbidden").description(message),
             Error::Worker(err) => Errata::new("Worker Error").description(err.to_string()),
             Error::Cache(err) => Errata::new("Cache Error").description(err.to_string()),
             Error::Entity(message) => Errata::new("Entity Resolver Error").description(message)
@@ -85,12 +93,19 @@ impl ErrorExtensions for Error {
                 grpc_description,
                 grpc_status_message,
                 grpc_status_details,
+                grpc_extensions,
             } = self
             {
+                e.set("code", grpc::status::code_name(*grpc_code));
                 e.set("grpcCode", *grpc_code);
                 e.set("grpcDescription", grpc_description);
                 e.set("grpcStatusMessage", grpc_status_message);
                 e.set("grpcStatusDetails", grpc_status_details.clone());
+                e.extend(grpc_extensions.clone());
+            }
+
+            if let Error::Forbidden(_) = self {
+                e.set("code", "FORBIDDEN");
             }
 
             if let Error::HTTP { message: _, body } = self {
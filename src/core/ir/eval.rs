@@ -7,7 +7,7 @@ use indexmap::IndexMap;
 
 use super::eval_io::eval_io;
 use super::model::{Cache, CacheKey, IR, Map};
-use super::{Error, EvalContext, ResolverContextLike, TypedValue};
+use super::{Error, EvalContext, ResolverContextLike, TypedValue, mask_value};
 use crate::core::auth::verify::{AuthVerifier, Verify};
 use crate::core::json::{JsonLike, JsonObjectLike};
 use crate::core::serde_value_ext::ValueExt;
@@ -49,10 +49,18 @@ impl IR {
                     if let Some(access_expr) = access_expr
                         && !access_expr.evaluate(ctx).map_err(Error::ExprEval)?
                     {
-                        return Err(Error::ExprEval("Access denied".into()));
+                        return Err(Error::Forbidden("Access denied".into()));
                     }
                     expr.eval(ctx).await
                 }
+                IR::Mask(mask, expr) => {
+                    let value = expr.eval(ctx).await?;
+                    if mask.allows(ctx).await? {
+                        Ok(value)
+                    } else {
+                        Ok(mask_value(mask.strategy, value))
+                    }
+                }
                 IR::IO(io) => eval_io(io, ctx).await,
                 IR::Cache(Cache { max_age, io }) => {
                     let io = &**io;
@@ -3,9 +3,9 @@ use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use async_graphql::{ServerError, Value};
-use http::header::HeaderMap;
+use http::header::{HeaderMap, HeaderName, HeaderValue};
 
-use super::{GraphQLOperationContext, RelatedFields, ResolverContextLike, SelectionField};
+use super::{Error, GraphQLOperationContext, RelatedFields, ResolverContextLike, SelectionField};
 use crate::core::document::print_directives;
 use crate::core::http::RequestContext;
 
@@ -24,6 +24,12 @@ pub struct EvalContext<'a, Ctx: ResolverContextLike> {
 
     // Overridden Arguments for Async GraphQL Context
     graphql_ctx_args: Option<Arc<Value>>,
+
+    // Name of the operation being executed, if the request gave one
+    operation_name: Option<&'a str>,
+
+    // Parent type and name of the field being resolved
+    schema_coordinate: Option<(&'a str, &'a str)>,
 }
 
 impl<'a, Ctx: ResolverContextLike> EvalContext<'a, Ctx> {
@@ -52,9 +58,29 @@ impl<'a, Ctx: ResolverContextLike> EvalContext<'a, Ctx> {
             graphql_ctx,
             graphql_ctx_value: None,
             graphql_ctx_args: None,
+            operation_name: None,
+            schema_coordinate: None,
         }
     }
 
+    #[must_use]
+    pub fn with_operation_name(mut self, operation_name: Option<&'a str>) -> Self {
+        self.operation_name = operation_name;
+        self
+    }
+
+    #[must_use]
+    pub fn with_schema_coordinate(mut self, parent_type: &'a str, field: &'a str) -> Self {
+        self.schema_coordinate = Some((parent_type, field));
+        self
+    }
+
+    /// Parent type and name of the field being resolved, when known.
+    #[must_use]
+    pub fn schema_coordinate(&self) -> Option<(&'a str, &'a str)> {
+        self.schema_coordinate
+    }
+
     #[must_use]
     pub fn value(&self) -> Option<&Value> {
         self.graphql_ctx.value()
@@ -95,6 +121,21 @@ impl<'a, Ctx: ResolverContextLike> EvalContext<'a, Ctx> {
         value.to_str().ok()
     }
 
+    /// The `@upstream(identity:)` token for a request to `url`, if its host
+    /// receives one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the token can't be signed.
+    pub fn identity_header(
+        &self,
+        url: &reqwest::Url,
+    ) -> Result<Option<(HeaderName, HeaderValue)>, Error> {
+        self.request_ctx
+            .identity_header(url, self.operation_name)
+            .map_err(|err| Error::IO(format!("Failed to sign identity token: {err}")))
+    }
+
     #[must_use]
     pub fn env_var(&self, key: &str) -> Option<Cow<'_, str>> {
         self.request_ctx.runtime.env.get(key)
@@ -70,7 +70,10 @@ impl<'a, 'ctx, Context: ResolverContextLike + Sync> EvalHttp<'a, 'ctx, Context>
     }
 
     pub fn init_request(&self) -> Result<DynamicRequest<String>, Error> {
-        let inner = self.request_template.to_request(self.evaluation_ctx)?;
+        let mut inner = self.request_template.to_request(self.evaluation_ctx)?;
+        if let Some((name, value)) = self.evaluation_ctx.identity_header(inner.request().url())? {
+            inner.request_mut().headers_mut().insert(name, value);
+        }
         Ok(inner)
     }
 
@@ -80,7 +83,7 @@ impl<'a, 'ctx, Context: ResolverContextLike + Sync> EvalHttp<'a, 'ctx, Context>
     ) -> Result<Response<async_graphql::Value>, Error> {
         let ctx = &self.evaluation_ctx;
         let dl = &self.data_loader;
-        let response = if dl.is_some() {
+        let response = if dl.is_some() && !ctx.request_ctx.signs_identity(req.request().url()) {
             execute_request_with_dl(ctx, req, self.data_loader).await?
         } else {
             execute_raw_request(ctx, req).await?
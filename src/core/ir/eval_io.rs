@@ -1,3 +1,5 @@
+use std::time::Instant;
+
 use async_graphql_value::ConstValue;
 
 use super::eval_http::{
@@ -11,6 +13,7 @@ use crate::core::data_loader::DataLoader;
 use crate::core::graphql::GraphqlDataLoader;
 use crate::core::grpc;
 use crate::core::grpc::data_loader::GrpcDataLoader;
+use crate::core::grpc::protocol::timeout_header;
 use crate::core::http::DataLoaderRequest;
 use crate::core::ir::Error;
 
@@ -40,8 +43,27 @@ where
     }
 }
 
-#[expect(clippy::too_many_lines, reason = "dispatches all IO resolver variants")]
+/// Evaluates the IO, recording its duration and outcome when upstream
+/// metrics are enabled.
 async fn eval_io_inner<Ctx>(io: &IO, ctx: &mut EvalContext<'_, Ctx>) -> Result<ConstValue, Error>
+where
+    Ctx: ResolverContextLike + Sync,
+{
+    let request_ctx = ctx.request_ctx;
+    let Some(metrics) = request_ctx.io_metrics.as_deref() else {
+        return execute_io(io, ctx).await;
+    };
+
+    let coordinate = ctx.schema_coordinate();
+    let start = Instant::now();
+    let result = execute_io(io, ctx).await;
+    metrics.record(io, coordinate, start.elapsed(), result.is_ok());
+
+    result
+}
+
+#[expect(clippy::too_many_lines, reason = "dispatches all IO resolver variants")]
+async fn execute_io<Ctx>(io: &IO, ctx: &mut EvalContext<'_, Ctx>) -> Result<ConstValue, Error>
 where
     Ctx: ResolverContextLike + Sync,
 {
@@ -62,9 +84,14 @@ where
             Ok(response.body)
         }
         IO::GraphQL { req_template, field_name, dl_id, .. } => {
-            let req = req_template.to_request(ctx)?;
+            let mut req = req_template.to_request(ctx)?;
+            if let Some((name, value)) = ctx.identity_header(req.url())? {
+                req.headers_mut().insert(name, value);
+            }
+            let signed = ctx.request_ctx.signs_identity(req.url());
             let request = DynamicRequest::new(req);
-            let res = if ctx.request_ctx.upstream.batch.is_some()
+            let res = if !signed
+                && ctx.request_ctx.upstream.batch.is_some()
                 && matches!(req_template.operation_type, GraphQLOperationType::Query)
             {
                 let data_loader: Option<&DataLoader<DataLoaderRequest, GraphqlDataLoader>> =
@@ -78,10 +105,19 @@ where
             parse_graphql_response(ctx, res, field_name)
         }
         IO::Grpc { req_template, dl_id, hook, .. } => {
-            let rendered = req_template.render(ctx)?;
+            let mut rendered = req_template.render(ctx)?;
+            if let Some((name, value)) = ctx.identity_header(&rendered.url)? {
+                rendered.headers.insert(name, value);
+            }
+            // Lets the upstream cancel work the response would be too late for
+            if let Some(remaining) = ctx.request_ctx.remaining_time() {
+                let (name, value) = timeout_header(req_template.operation.protocol, remaining);
+                rendered.headers.insert(name, value);
+            }
             let worker = &ctx.request_ctx.runtime.worker;
 
-            let res = if ctx.request_ctx.upstream.batch.is_some() &&
+            let res = if !ctx.request_ctx.signs_identity(&rendered.url) &&
+                    ctx.request_ctx.upstream.batch.is_some() &&
                     // TODO: share check for operation_type for resolvers
                     matches!(req_template.operation_type, GraphQLOperationType::Query)
             {
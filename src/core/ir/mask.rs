@@ -0,0 +1,194 @@
+use async_graphql_value::ConstValue;
+use sha2::{Digest, Sha256};
+
+use super::model::Mask;
+use super::{Error, EvalContext, ResolverContextLike};
+use crate::core::auth::verify::{AuthVerifier, Verify};
+use crate::core::config::MaskStrategy;
+
+impl Mask {
+    /// Whether the value is returned unmasked. The auth providers are verified
+    /// first when no protected field has populated the claims yet, once per
+    /// request, and a failed verification leaves the value masked.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `unless` expression can't be evaluated.
+    pub async fn allows<Ctx: ResolverContextLike + Sync>(
+        &self,
+        ctx: &EvalContext<'_, Ctx>,
+    ) -> Result<bool, Error> {
+        let Some(unless) = &self.unless else {
+            return Ok(false);
+        };
+
+        if let Some(auth) = &self.auth
+            && ctx.request_ctx.get_auth_claims().is_none()
+        {
+            let verified = ctx
+                .request_ctx
+                .mask_verification
+                .get_or_init(|| async {
+                    match AuthVerifier::from(auth.clone())
+                        .verify(ctx.request_ctx)
+                        .await
+                        .to_result_with_claims()
+                    {
+                        Ok(claims) => {
+                            if let Some(claims) = claims {
+                                ctx.request_ctx.set_auth_claims(claims);
+                            }
+                            true
+                        }
+                        Err(_) => false,
+                    }
+                })
+                .await;
+            if !verified {
+                return Ok(false);
+            }
+        }
+
+        unless.evaluate(ctx).map_err(Error::ExprEval)
+    }
+}
+
+/// Masks a resolved value, and each element of a list. `null` stays `null`.
+#[must_use]
+pub fn mask_value(strategy: MaskStrategy, value: ConstValue) -> ConstValue {
+    let text = match value {
+        ConstValue::Null => return ConstValue::Null,
+        ConstValue::List(values) => {
+            return ConstValue::List(
+                values
+                    .into_iter()
+                    .map(|value| mask_value(strategy, value))
+                    .collect(),
+            );
+        }
+        ConstValue::String(text) => text,
+        ConstValue::Enum(name) => name.to_string(),
+        value => value.to_string(),
+    };
+
+    match strategy {
+        MaskStrategy::Null => ConstValue::Null,
+        MaskStrategy::Hash => ConstValue::String(
+            Sha256::digest(text.as_bytes())
+                .iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect(),
+        ),
+        MaskStrategy::Partial => ConstValue::String(mask_partial(&text)),
+    }
+}
+
+/// Keeps the first character and the domain of an email address, and the last
+/// four characters of values long enough that they don't reveal most of it.
+fn mask_partial(text: &str) -> String {
+    if let Some((local, domain)) = text.split_once('@')
+        && let Some(first) = local.chars().next()
+    {
+        return format!("{first}***@{domain}");
+    }
+
+    let len = text.chars().count();
+    if len < 8 {
+        return "*".repeat(len);
+    }
+    let visible = text.chars().skip(len - 4).collect::<String>();
+    format!("{}{visible}", "*".repeat(len - 4))
+}
+
+#[cfg(test)]
+mod tests {
+    #![expect(clippy::unwrap_used, reason = "test code")]
+    use async_graphql::{Name, Value};
+    use serde_json::json;
+
+    use super::*;
+    use crate::core::http::RequestContext;
+    use crate::core::ir::EmptyResolverContext;
+    use crate::core::ir::access_expr::AccessExpr;
+
+    fn masked(strategy: MaskStrategy, value: &str) -> Value {
+        mask_value(strategy, Value::String(value.to_string()))
+    }
+
+    #[test]
+    fn masks_partially() {
+        assert_eq!(
+            masked(MaskStrategy::Partial, "jane@example.com"),
+            Value::String("j***@example.com".to_string())
+        );
+        assert_eq!(
+            masked(MaskStrategy::Partial, "+1 555 0134567"),
+            Value::String("**********4567".to_string())
+        );
+        assert_eq!(
+            masked(MaskStrategy::Partial, "1234"),
+            Value::String("****".to_string())
+        );
+    }
+
+    #[test]
+    fn masks_with_a_hash() {
+        assert_eq!(
+            masked(MaskStrategy::Hash, "abc"),
+            Value::String(
+                "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn masks_with_null() {
+        assert_eq!(masked(MaskStrategy::Null, "secret"), Value::Null);
+    }
+
+    #[test]
+    fn masks_lists_and_keeps_null() {
+        let value = Value::List(vec![
+            Value::String("12345678".to_string()),
+            Value::Null,
+            Value::Enum(Name::new("INTERNAL")),
+        ]);
+        assert_eq!(
+            mask_value(MaskStrategy::Partial, value),
+            Value::List(vec![
+                Value::String("****5678".to_string()),
+                Value::Null,
+                Value::String("****RNAL".to_string()),
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn allows_when_the_expression_holds() {
+        let runtime = crate::core::runtime::test::init(&None);
+        let req_ctx = RequestContext::new(runtime);
+        req_ctx.set_auth_claims(json!({"scope": "pii:read"}));
+        let res_ctx = EmptyResolverContext {};
+        let eval_ctx = EvalContext::new(&req_ctx, &res_ctx);
+
+        let mask = |unless: Option<&str>| Mask {
+            strategy: MaskStrategy::Partial,
+            unless: unless.map(|unless| AccessExpr::parse(unless).unwrap()),
+            auth: None,
+        };
+
+        assert!(
+            mask(Some("scopes contains 'pii:read'"))
+                .allows(&eval_ctx)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !mask(Some("scopes contains 'pii:write'"))
+                .allows(&eval_ctx)
+                .await
+                .unwrap()
+        );
+        assert!(!mask(None).allows(&eval_ctx).await.unwrap());
+    }
+}
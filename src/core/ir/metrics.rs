@@ -0,0 +1,261 @@
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+use std::sync::{LazyLock, Mutex, PoisonError};
+use std::time::Duration;
+
+use gqlforge_hasher::GqlforgeHasher;
+use opentelemetry::KeyValue;
+use opentelemetry::metrics::{Counter, Histogram};
+use url::Url;
+
+use super::model::IO;
+use crate::core::config::TelemetryMetrics;
+use crate::core::mustache::{Mustache, Segment};
+
+static UPSTREAM_REQUEST_DURATION: LazyLock<Histogram<f64>> = LazyLock::new(|| {
+    let meter = opentelemetry::global::meter("upstream_request");
+
+    meter
+        .f64_histogram("upstream.request.duration")
+        .with_unit("s")
+        .with_description("Duration of the requests resolvers send to upstreams")
+        .build()
+});
+
+static UPSTREAM_REQUEST_ERRORS: LazyLock<Counter<u64>> = LazyLock::new(|| {
+    let meter = opentelemetry::global::meter("upstream_request");
+
+    meter
+        .u64_counter("upstream.request.errors")
+        .with_description("Number of requests to upstreams that failed")
+        .build()
+});
+
+/// Value of the field, upstream, operation name and client name attributes
+/// once `maxCardinality` is reached.
+const OTHER: &str = "other";
+
+/// Records the duration and errors of the requests sent to upstreams, with
+/// the attributes `@telemetry(metrics:)` enables.
+#[derive(Debug)]
+pub struct IoMetrics {
+    fields: bool,
+    upstreams: bool,
+    max_cardinality: usize,
+    /// Hashes of the attribute sets recorded so far.
+    series: Mutex<HashSet<u64>>,
+    /// Hashes of the operation attribute sets recorded so far.
+    operations: Mutex<HashSet<u64>>,
+}
+
+impl IoMetrics {
+    #[must_use]
+    pub fn new(options: &TelemetryMetrics) -> Self {
+        Self {
+            fields: options.fields(),
+            upstreams: options.upstreams(),
+            max_cardinality: options.max_cardinality(),
+            series: Mutex::new(HashSet::new()),
+            operations: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Records a request of `io`, resolving the field at `coordinate` (its
+    /// parent type and name).
+    pub fn record(
+        &self,
+        io: &IO,
+        coordinate: Option<(&str, &str)>,
+        duration: Duration,
+        is_ok: bool,
+    ) {
+        // Streams are resolved by subscriptions, outside of `eval_io`
+        let Some(kind) = kind(io) else {
+            return;
+        };
+        let mut upstream = upstream(io).filter(|_| self.upstreams);
+        let mut coordinate = coordinate.filter(|_| self.fields);
+
+        if !self.admit(kind, upstream.as_deref(), coordinate) {
+            upstream = upstream.map(|_| OTHER.to_string());
+            coordinate = coordinate.map(|_| (OTHER, OTHER));
+        }
+
+        let mut attributes = Vec::with_capacity(5);
+        attributes.push(KeyValue::new("io.kind", kind));
+        if let Some(upstream) = upstream {
+            attributes.push(KeyValue::new("upstream", upstream));
+        }
+        if let Some((parent_type, field)) = coordinate {
+            attributes.push(KeyValue::new(
+                "graphql.parent_type",
+                parent_type.to_string(),
+            ));
+            attributes.push(KeyValue::new("graphql.field.name", field.to_string()));
+        }
+
+        if !is_ok {
+            UPSTREAM_REQUEST_ERRORS.add(1, &attributes);
+        }
+        attributes.push(KeyValue::new("status", if is_ok { "ok" } else { "error" }));
+        UPSTREAM_REQUEST_DURATION.record(duration.as_secs_f64(), &attributes);
+    }
+
+    /// The operation name and client name attributes of the operation
+    /// metrics, sent by clients, so recorded as `other` past
+    /// `maxCardinality`.
+    #[must_use]
+    pub fn operation_labels<'a>(
+        &self,
+        name: Option<&'a str>,
+        client_name: Option<&'a str>,
+    ) -> (Option<&'a str>, Option<&'a str>) {
+        if self.admit_in(&self.operations, (name, client_name)) {
+            (name, client_name)
+        } else {
+            (name.map(|_| OTHER), client_name.map(|_| OTHER))
+        }
+    }
+
+    /// Whether an attribute set was recorded already, or still fits under
+    /// `maxCardinality`.
+    fn admit(&self, kind: &str, upstream: Option<&str>, coordinate: Option<(&str, &str)>) -> bool {
+        self.admit_in(&self.series, (kind, upstream, coordinate))
+    }
+
+    fn admit_in(&self, series: &Mutex<HashSet<u64>>, attributes: impl Hash) -> bool {
+        let mut hasher = GqlforgeHasher::default();
+        attributes.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        let mut series = series.lock().unwrap_or_else(PoisonError::into_inner);
+        if series.contains(&hash) {
+            true
+        } else if series.len() < self.max_cardinality {
+            series.insert(hash);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// The `io.kind` attribute of an IO.
+fn kind(io: &IO) -> Option<&'static str> {
+    match io {
+        IO::Http { .. } => Some("http"),
+        IO::GraphQL { .. } => Some("graphql"),
+        IO::Grpc { .. } => Some("grpc"),
+        IO::Js { .. } => Some("js"),
+        IO::Postgres { .. } => Some("postgres"),
+        IO::S3 { .. } => Some("s3"),
+        IO::GrpcStream { .. } | IO::GraphQLStream { .. } | IO::HttpStream { .. } => None,
+    }
+}
+
+/// The `upstream` attribute of an IO: the host it's sent to, the id of its
+/// database or bucket link, or the name of its JS function.
+fn upstream(io: &IO) -> Option<String> {
+    match io {
+        IO::Http { req_template, .. } => host(&req_template.root_url),
+        IO::GraphQL { req_template, .. } => Url::parse(&req_template.url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string)),
+        IO::Grpc { req_template, .. } => host(&req_template.url),
+        IO::Js { name } => Some(name.clone()),
+        IO::Postgres { connection_id, .. } => Some(connection_id.clone()),
+        IO::S3 { req_template, .. } => req_template.link_id.clone(),
+        IO::GrpcStream { .. } | IO::GraphQLStream { .. } | IO::HttpStream { .. } => None,
+    }
+}
+
+/// The host of a URL template, if it isn't rendered per request.
+fn host(url: &Mustache) -> Option<String> {
+    let (prefix, is_const) = match url.segments().as_slice() {
+        [Segment::Literal(url)] => (url, true),
+        [Segment::Literal(prefix), ..] => (prefix, false),
+        _ => return None,
+    };
+
+    // An expression right after the host may still be part of it
+    let (_, rest) = prefix.split_once("://")?;
+    if !is_const && !rest.contains('/') {
+        return None;
+    }
+
+    Url::parse(prefix).ok()?.host_str().map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn host_of_url_templates() {
+        let host_of = |url: &str| host(&Mustache::parse(url));
+
+        assert_eq!(
+            host_of("http://jsonplaceholder.typicode.com/users/{{.args.id}}"),
+            Some("jsonplaceholder.typicode.com".to_string())
+        );
+        assert_eq!(
+            host_of("https://api.example.com:8443"),
+            Some("api.example.com".to_string())
+        );
+        assert_eq!(host_of("http://{{.env.HOST}}/users"), None);
+        assert_eq!(
+            host_of("http://api-{{.env.REGION}}.example.com/users"),
+            None
+        );
+        assert_eq!(host_of("{{.env.URL}}/users"), None);
+    }
+
+    #[test]
+    fn caps_cardinality() {
+        let metrics =
+            IoMetrics::new(&TelemetryMetrics { max_cardinality: Some(2), ..Default::default() });
+
+        assert!(metrics.admit("http", Some("a.com"), Some(("Query", "users"))));
+        assert!(metrics.admit("http", Some("b.com"), Some(("Query", "posts"))));
+        assert!(!metrics.admit("http", Some("c.com"), Some(("Query", "todos"))));
+        assert!(metrics.admit("http", Some("a.com"), Some(("Query", "users"))));
+    }
+
+    #[test]
+    fn caps_cardinality_by_default() {
+        let metrics = IoMetrics::new(&TelemetryMetrics::default());
+
+        for id in 0..TelemetryMetrics::DEFAULT_MAX_CARDINALITY {
+            let name = format!("Operation{id}");
+            assert_eq!(
+                metrics.operation_labels(Some(&name), None),
+                (Some(name.as_str()), None)
+            );
+        }
+        assert_eq!(
+            metrics.operation_labels(Some("OneTooMany"), Some("web")),
+            (Some(OTHER), Some(OTHER))
+        );
+    }
+
+    #[test]
+    fn caps_operation_cardinality() {
+        let metrics =
+            IoMetrics::new(&TelemetryMetrics { max_cardinality: Some(1), ..Default::default() });
+
+        assert_eq!(
+            metrics.operation_labels(Some("GetUser"), Some("web")),
+            (Some("GetUser"), Some("web"))
+        );
+        assert_eq!(
+            metrics.operation_labels(Some("GetPost"), None),
+            (Some(OTHER), None)
+        );
+        assert_eq!(
+            metrics.operation_labels(Some("GetUser"), Some("web")),
+            (Some("GetUser"), Some("web"))
+        );
+        // Upstream requests are capped separately
+        assert!(metrics.admit("http", Some("a.com"), Some(("Query", "users"))));
+    }
+}
@@ -5,6 +5,8 @@ mod eval;
 mod eval_context;
 mod eval_http;
 mod eval_io;
+mod mask;
+mod metrics;
 mod request;
 mod resolver_context_like;
 
@@ -15,6 +17,8 @@ use std::ops::Deref;
 pub use discriminator::*;
 pub use error::*;
 pub use eval_context::EvalContext;
+pub use mask::mask_value;
+pub use metrics::IoMetrics;
 pub(crate) use request::DynamicRequest;
 pub use resolver_context_like::{
     EmptyResolverContext, ResolverContext, ResolverContextLike, SelectionField,
@@ -9,6 +9,7 @@ use super::access_expr::AccessExpr;
 use super::discriminator::Discriminator;
 use super::{EvalContext, ResolverContextLike};
 use crate::core::blueprint::{Auth, DynamicValue};
+use crate::core::config::MaskStrategy;
 use crate::core::config::group_by::GroupBy;
 use crate::core::graphql::{self};
 use crate::core::worker_hooks::WorkerHooks;
@@ -24,6 +25,8 @@ pub enum IR {
     Path(Box<IR>, Vec<String>),
     ContextPath(Vec<String>),
     Protect(Auth, Option<AccessExpr>, Box<IR>),
+    /// Masks the resolved value unless the access expression holds
+    Mask(Mask, Box<IR>),
     Map(Map),
     Pipe(Box<IR>, Box<IR>),
     /// Merges the result of multiple IRs together
@@ -35,6 +38,15 @@ pub enum IR {
     Service(String),
 }
 
+#[derive(Clone, Debug)]
+pub struct Mask {
+    pub strategy: MaskStrategy,
+    pub unless: Option<AccessExpr>,
+    /// Providers verified to read the claims `unless` refers to, when they
+    /// weren't verified for a protected field already.
+    pub auth: Option<Auth>,
+}
+
 #[derive(Clone, Debug)]
 pub struct Map {
     pub input: Box<IR>,
@@ -171,7 +183,7 @@ impl IR {
         match self {
             IR::IO(io) => io_modifier(io),
             IR::Cache(cache) => io_modifier(&mut cache.io),
-            IR::Discriminate(_, ir) | IR::Protect(_, _, ir) | IR::Path(ir, _) => {
+            IR::Discriminate(_, ir) | IR::Protect(_, _, ir) | IR::Mask(_, ir) | IR::Path(ir, _) => {
                 ir.modify_io(io_modifier);
             }
             IR::Pipe(ir1, ir2) => {
@@ -225,6 +237,7 @@ impl IR {
                 IR::Protect(auth, access_expr, expr) => {
                     IR::Protect(auth, access_expr, expr.modify_box(modifier))
                 }
+                IR::Mask(mask, expr) => IR::Mask(mask, expr.modify_box(modifier)),
                 IR::Map(Map { input, map }) => {
                     IR::Map(Map { input: input.modify_box(modifier), map })
                 }
@@ -365,16 +365,10 @@ impl<'a> Builder<'a> {
             .ok_or(BuildError::RootOperationTypeNotDefined { operation: operation.ty })?;
         let fields = self.collect_fields(None, &operation.selection_set.node, name, &fragments);
 
-        let is_introspection_query = operation.selection_set.node.items.iter().any(|f| {
-            if let Selection::Field(Positioned { node: gql_field, .. }) = &f.node {
-                let query = gql_field.name.node.as_str();
-                query.contains("__schema") || query.contains("__type")
-            } else {
-                false
-            }
-        });
+        let is_introspection_query =
+            selects_introspection(&operation.selection_set.node, &fragments);
 
-        let plan = OperationPlan::new(
+        let mut plan = OperationPlan::new(
             name,
             fields,
             operation.ty,
@@ -382,10 +376,40 @@ impl<'a> Builder<'a> {
             is_introspection_query,
             Some(self.index.get_interfaces()),
         );
+        // Anonymous operations are the only ones parsed as `Single`
+        plan.operation_name = match (operation_name, &self.document.operations) {
+            (Some(operation_name), _) => Some(operation_name.to_string()),
+            (None, DocumentOperations::Multiple(map)) => map.keys().next().map(ToString::to_string),
+            (None, DocumentOperations::Single(_)) => None,
+        };
         Ok(plan)
     }
 }
 
+/// Whether the root selection set requests `__schema` or `__type`, directly or
+/// through fragment spreads and inline fragments.
+fn selects_introspection(
+    selection_set: &SelectionSet,
+    fragments: &HashMap<&str, &FragmentDefinition>,
+) -> bool {
+    selection_set
+        .items
+        .iter()
+        .any(|selection| match &selection.node {
+            Selection::Field(Positioned { node: field, .. }) => {
+                matches!(field.name.node.as_str(), "__schema" | "__type")
+            }
+            Selection::FragmentSpread(Positioned { node: fragment_spread, .. }) => fragments
+                .get(fragment_spread.fragment_name.node.as_str())
+                .is_some_and(|fragment| {
+                    selects_introspection(&fragment.selection_set.node, fragments)
+                }),
+            Selection::InlineFragment(Positioned { node: fragment, .. }) => {
+                selects_introspection(&fragment.selection_set.node, fragments)
+            }
+        })
+}
+
 #[cfg(test)]
 mod tests {
     #![expect(clippy::unwrap_used, reason = "test code")]
@@ -419,6 +443,15 @@ mod tests {
         insta::assert_debug_snapshot!(plan.selection);
     }
 
+    #[tokio::test]
+    async fn test_operation_name() {
+        assert_eq!(plan("query { posts { id } }").operation_name, None);
+        assert_eq!(
+            plan("query Posts { posts { id } }").operation_name,
+            Some("Posts".to_string())
+        );
+    }
+
     #[tokio::test]
     async fn test_size() {
         let plan = plan(
@@ -517,6 +550,21 @@ mod tests {
         insta::assert_debug_snapshot!(plan.selection);
     }
 
+    #[test]
+    fn test_introspection_through_fragments() {
+        assert!(plan("{ __schema { queryType { name } } }").is_introspection_query);
+        assert!(
+            plan("{ ...Schema } fragment Schema on Query { __schema { queryType { name } } }")
+                .is_introspection_query
+        );
+        assert!(
+            plan("{ ... on Query { __type(name: \"User\") { name } } }").is_introspection_query
+        );
+        assert!(
+            !plan("{ ...Posts } fragment Posts on Query { posts { id } }").is_introspection_query
+        );
+    }
+
     #[test]
     fn test_multiple_operations() {
         let plan = plan(
@@ -6,7 +6,7 @@ use gqlforge_valid::Validator;
 
 use super::context::Context;
 use super::exec::{Executor, IRExecutor};
-use super::graphql_error::GraphQLError;
+use super::graphql_error::{ErrorExtensions, GraphQLError};
 use super::{AnyResponse, BuildError, Error, OperationPlan, Request, Response, Result, transform};
 use crate::core::Transform;
 use crate::core::app_context::AppContext;
@@ -60,6 +60,17 @@ impl ConstValueExecutor {
 
         let is_introspection_query =
             req_ctx.server.get_enable_introspection() && self.plan.is_introspection_query;
+
+        // Only authorized callers may introspect when `introspectionAccess` is set
+        if is_introspection_query && let Some(ir) = &req_ctx.server.introspection_protection {
+            let mut eval_context = EvalContext::new(req_ctx, &EmptyResolverContext {});
+            if let Err(err) = ir.eval(&mut eval_context).await {
+                let mut error = GraphQLError::new(err.to_string(), None);
+                error.extensions = Error::from(err).extend().extensions;
+                let resp: Response<ConstValue> = Response::default();
+                return resp.with_errors(vec![error]).into();
+            }
+        }
         let variables = &request.variables;
 
         // Attempt to skip unnecessary fields
@@ -93,7 +104,7 @@ impl ConstValueExecutor {
             }
         };
 
-        let exec = ConstValueExec::new(&plan, req_ctx);
+        let exec = ConstValueExec::new(&plan, req_ctx, request.operation_name.as_deref());
         // PERF: remove this particular clone?
         let vars = request.variables.clone();
         let exe = Executor::new(&plan, exec);
@@ -116,11 +127,16 @@ impl ConstValueExecutor {
 struct ConstValueExec<'a> {
     plan: &'a OperationPlan<ConstValue>,
     req_context: &'a RequestContext,
+    operation_name: Option<&'a str>,
 }
 
 impl<'a> ConstValueExec<'a> {
-    pub fn new(plan: &'a OperationPlan<ConstValue>, req_context: &'a RequestContext) -> Self {
-        Self { plan, req_context }
+    pub fn new(
+        plan: &'a OperationPlan<ConstValue>,
+        req_context: &'a RequestContext,
+        operation_name: Option<&'a str>,
+    ) -> Self {
+        Self { plan, req_context, operation_name }
     }
 
     async fn call(
@@ -138,7 +154,12 @@ impl<'a> ConstValueExec<'a> {
         }
 
         let req_context = &self.req_context;
-        let mut eval_ctx = EvalContext::new(req_context, ctx);
+        let field = ctx.field();
+        let mut eval_ctx =
+            EvalContext::new(req_context, ctx).with_operation_name(self.operation_name);
+        if let Some(parent_type) = &field.type_condition {
+            eval_ctx = eval_ctx.with_schema_coordinate(parent_type, &field.name);
+        }
 
         Ok(ir.eval(&mut eval_ctx).await?)
     }
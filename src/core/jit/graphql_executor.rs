@@ -2,13 +2,16 @@ use std::collections::BTreeMap;
 use std::future::Future;
 use std::hash::{Hash, Hasher};
 use std::sync::Arc;
+use std::time::Instant;
 
 use async_graphql::{BatchRequest, Value};
 use async_graphql_value::{ConstValue, Extensions};
 use futures_util::StreamExt;
 use futures_util::stream::FuturesOrdered;
 use gqlforge_hasher::GqlforgeHasher;
+use tracing::Instrument;
 
+use super::telemetry::Operation;
 use super::{AnyResponse, BatchResponse, Response};
 use crate::core::app_context::AppContext;
 use crate::core::async_graphql_hyper::OperationId;
@@ -78,48 +81,93 @@ impl JITExecutor {
         // TODO: hash considering only the query itself ignoring specified operation and
         // variables that could differ for the same query
         let hash = Self::req_hash(&request);
+        let is_name_recorded = request.operation_name.is_some();
+        let span = tracing::info_span!(
+            "graphql_operation",
+            otel.name = tracing::field::Empty,
+            otel.status_code = tracing::field::Empty,
+            graphql.operation.name = request.operation_name.as_deref(),
+        );
 
         async move {
-            if let Some(response) = self.app_ctx.const_execution_cache.get(&hash) {
-                return response.clone();
+            let start = Instant::now();
+            let (operation, response) = self.execute_operation(hash, request).await;
+
+            if let Some(operation) = operation {
+                operation.record(
+                    &self.req_ctx,
+                    start.elapsed(),
+                    response.is_ok,
+                    is_name_recorded,
+                    self.app_ctx.io_metrics.as_deref(),
+                );
             }
 
-            let jit_request = jit::Request::from(request);
-            let exec = if let Some(op) = self.app_ctx.operation_plans.get(&hash) {
-                ConstValueExecutor::from(op.value().clone())
-            } else {
-                let exec = match ConstValueExecutor::try_new(&jit_request, &self.app_ctx) {
-                    Ok(exec) => exec,
-                    Err(error) => {
-                        return Response::<async_graphql::Value>::default()
-                            .with_errors(vec![Positioned::new(error, Pos::default())])
-                            .into();
-                    }
-                };
-                self.app_ctx
-                    .operation_plans
-                    .insert(hash.clone(), exec.plan.clone());
-                exec
-            };
+            response
+        }
+        .instrument(span)
+    }
 
-            let is_const = exec.plan.is_const;
-            let is_protected = exec.plan.is_protected;
+    /// Executes the request, returning the operation it executed unless it
+    /// couldn't be planned.
+    async fn execute_operation(
+        &self,
+        hash: OPHash,
+        request: async_graphql::Request,
+    ) -> (Option<Operation>, AnyResponse<Vec<u8>>) {
+        if let Some(response) = self.app_ctx.const_execution_cache.get(&hash) {
+            let operation = self
+                .app_ctx
+                .operation_plans
+                .get(&hash)
+                .map(|plan| Operation::from(plan.value()));
+            return (operation, response.clone());
+        }
 
-            let response = if exec.plan.can_dedupe() {
-                self.dedupe_and_exec(exec, jit_request).await
-            } else {
-                self.exec(exec, jit_request).await
+        let jit_request = jit::Request::from(request);
+        let exec = if let Some(op) = self.app_ctx.operation_plans.get(&hash) {
+            ConstValueExecutor::from(op.value().clone())
+        } else {
+            let exec = match ConstValueExecutor::try_new(&jit_request, &self.app_ctx) {
+                Ok(exec) => exec,
+                Err(error) => {
+                    let response = Response::<async_graphql::Value>::default()
+                        .with_errors(vec![Positioned::new(error, Pos::default())]);
+                    return (None, response.into());
+                }
             };
-
-            // Cache the response if it's constant and not wrapped with protected.
-            if is_const && !is_protected {
-                self.app_ctx
-                    .const_execution_cache
-                    .insert(hash, response.clone());
-            }
-
-            response
+            self.app_ctx
+                .operation_plans
+                .insert(hash.clone(), exec.plan.clone());
+            exec
+        };
+
+        let operation = Operation::from(&exec.plan);
+        let is_const = exec.plan.is_const;
+        // Introspection results depend on the caller when `introspectionAccess` is set
+        let is_protected = exec.plan.is_protected
+            || (exec.plan.is_introspection_query
+                && self
+                    .app_ctx
+                    .blueprint
+                    .server
+                    .introspection_protection
+                    .is_some());
+
+        let response = if exec.plan.can_dedupe() {
+            self.dedupe_and_exec(exec, jit_request).await
+        } else {
+            self.exec(exec, jit_request).await
+        };
+
+        // Cache the response if it's constant and not wrapped with protected.
+        if is_const && !is_protected {
+            self.app_ctx
+                .const_execution_cache
+                .insert(hash, response.clone());
         }
+
+        (Some(operation), response)
     }
 
     /// Execute a GraphQL batch query.
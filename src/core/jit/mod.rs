@@ -3,6 +3,7 @@ pub mod graphql_error;
 mod model;
 mod store;
 mod synth;
+mod telemetry;
 mod transform;
 
 use builder::Builder;
@@ -25,3 +26,4 @@ pub use graphql_executor::*;
 pub use model::*;
 pub use request::*;
 pub use response::*;
+pub use telemetry::GRAPHQL_OPERATION_NAME;
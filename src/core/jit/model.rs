@@ -336,6 +336,11 @@ impl OPHash {
 pub struct OperationPlan<Input> {
     pub root_name: String,
     pub operation_type: OperationType,
+    /// Name of the operation, when it has one.
+    pub operation_name: Option<String>,
+    /// Hash of the normalized document, identifying the operation in
+    /// telemetry.
+    pub document_hash: String,
     // TODO: drop index from here. Embed all the necessary information in each field of the plan.
     pub index: Arc<Index>,
     pub is_introspection_query: bool,
@@ -367,6 +372,8 @@ impl<Input> OperationPlan<Input> {
             selection,
             root_name: self.root_name,
             operation_type: self.operation_type,
+            operation_name: self.operation_name,
+            document_hash: self.document_hash,
             index: self.index,
             is_introspection_query: self.is_introspection_query,
             is_dedupe: self.is_dedupe,
@@ -396,6 +403,8 @@ impl<Input> OperationPlan<Input> {
             root_name: root_name.to_string(),
             selection,
             operation_type,
+            operation_name: None,
+            document_hash: String::new(),
             index,
             is_introspection_query,
             is_dedupe: false,
@@ -4,7 +4,7 @@ use async_graphql_value::ConstValue;
 use gqlforge_valid::Validator;
 use serde::Deserialize;
 
-use super::{Builder, OperationPlan, Result, Variables, transform};
+use super::{Builder, OperationPlan, Result, Variables, telemetry, transform};
 use crate::core::Transform;
 use crate::core::blueprint::Blueprint;
 use crate::core::transform::TransformerOps;
@@ -49,7 +49,8 @@ impl Request<ConstValue> {
     ) -> Result<OperationPlan<async_graphql_value::Value>> {
         let doc = async_graphql::parser::parse_query(&self.query)?;
         let builder = Builder::new(blueprint, &doc);
-        let plan = builder.build(self.operation_name.as_deref())?;
+        let mut plan = builder.build(self.operation_name.as_deref())?;
+        plan.document_hash = telemetry::document_hash(&self.query);
 
         transform::CheckConst::new()
             .pipe(transform::CheckProtected::new())
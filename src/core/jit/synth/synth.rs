@@ -1,5 +1,6 @@
 use std::borrow::Cow;
 
+use crate::core::ir;
 use crate::core::jit::model::{Field, OperationPlan, Variables};
 use crate::core::jit::store::{DataPath, Store};
 use crate::core::jit::{Error, PathSegment, Positioned, ValidationError};
@@ -72,7 +73,9 @@ where
 
         let result = match self.store.get(node.id) {
             Some(value) => {
-                let mut value = value.as_ref().map_err(|e| Box::new(e.clone()))?;
+                let mut value = value
+                    .as_ref()
+                    .map_err(|e| Self::to_resolver_error(e, path))?;
 
                 for index in data_path.as_slice() {
                     if let Some(arr) = value.as_array() {
@@ -222,19 +225,39 @@ where
         node: &'a Field<Value>,
         path: &[PathSegment],
     ) -> Box<Positioned<Error>> {
-        Box::new(
-            Positioned::new(error, node.pos).with_path(
-                path.iter()
-                    .map(|x| match x {
-                        PathSegment::Field(cow) => {
-                            PathSegment::Field(Cow::Owned(cow.clone().into_owned()))
-                        }
-                        PathSegment::Index(i) => PathSegment::Index(*i),
-                    })
-                    .collect(),
-            ),
+        Box::new(Positioned::new(error, node.pos).with_path(Self::to_owned_path(path)))
+    }
+
+    /// Resolver errors are stored per field without a path, since the same
+    /// field can be resolved at several positions of the response. Auth
+    /// errors get the path of the first position the error is reached at, so
+    /// clients can tell which field was denied.
+    fn to_resolver_error(
+        error: &Positioned<Error>,
+        path: &[PathSegment],
+    ) -> Box<Positioned<Error>> {
+        let mut error = error.clone();
+        if error.path.is_empty() && Self::is_auth_error(&error.value) {
+            error.path = Self::to_owned_path(path);
+        }
+        Box::new(error)
+    }
+
+    fn is_auth_error(error: &Error) -> bool {
+        matches!(
+            error,
+            Error::IR(error) if matches!(**error, ir::Error::Auth(_) | ir::Error::Forbidden(_))
         )
     }
+
+    fn to_owned_path(path: &[PathSegment]) -> Vec<PathSegment<'static>> {
+        path.iter()
+            .map(|x| match x {
+                PathSegment::Field(cow) => PathSegment::Field(Cow::Owned(cow.clone().into_owned())),
+                PathSegment::Index(i) => PathSegment::Index(*i),
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
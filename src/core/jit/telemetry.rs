@@ -0,0 +1,255 @@
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use opentelemetry::KeyValue;
+use opentelemetry::metrics::{Counter, Histogram};
+use sha2::{Digest, Sha256};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+use super::OperationPlan;
+use crate::core::http::RequestContext;
+use crate::core::ir::IoMetrics;
+
+static GRAPHQL_OPERATION_DURATION: LazyLock<Histogram<f64>> = LazyLock::new(|| {
+    let meter = opentelemetry::global::meter("graphql_operation");
+
+    meter
+        .f64_histogram("graphql.operation.duration")
+        .with_unit("s")
+        .with_description("Duration of the GraphQL operations executed")
+        .build()
+});
+
+static GRAPHQL_OPERATION_ERRORS: LazyLock<Counter<u64>> = LazyLock::new(|| {
+    let meter = opentelemetry::global::meter("graphql_operation");
+
+    meter
+        .u64_counter("graphql.operation.errors")
+        .with_description("Number of GraphQL operations that responded with errors")
+        .build()
+});
+
+/// Recorded when the operation span is created if the client sends an
+/// `operationName`, so that samplers can match it.
+pub const GRAPHQL_OPERATION_NAME: &str = "graphql.operation.name";
+const GRAPHQL_OPERATION_TYPE: &str = "graphql.operation.type";
+/// Hash of the normalized document, see [`document_hash`].
+const GRAPHQL_DOCUMENT_HASH: &str = "graphql.document.hash";
+const GRAPHQL_CLIENT_NAME: &str = "graphql.client.name";
+const GRAPHQL_CLIENT_VERSION: &str = "graphql.client.version";
+
+/// The operation a request executes, as recorded in spans and metrics.
+#[derive(Clone, Debug)]
+pub struct Operation {
+    name: Option<String>,
+    operation_type: String,
+    document_hash: String,
+}
+
+impl<Input> From<&OperationPlan<Input>> for Operation {
+    fn from(plan: &OperationPlan<Input>) -> Self {
+        Self {
+            name: plan.operation_name.clone(),
+            operation_type: plan.operation_type.to_string(),
+            document_hash: plan.document_hash.clone(),
+        }
+    }
+}
+
+impl Operation {
+    /// Names the current span after the operation, following the OTel
+    /// GraphQL conventions, and records its attributes, unless the name was
+    /// recorded with the span already. Metrics are only recorded when
+    /// telemetry is exported, without the document hash and client version,
+    /// and with the operation and client names capped by `metrics`.
+    pub fn record(
+        &self,
+        req_ctx: &RequestContext,
+        duration: Duration,
+        is_ok: bool,
+        is_name_recorded: bool,
+        metrics: Option<&IoMetrics>,
+    ) {
+        let span = tracing::Span::current();
+        let span_name = match &self.name {
+            Some(name) => format!("{} {name}", self.operation_type),
+            None => self.operation_type.clone(),
+        };
+        span.record("otel.name", span_name.as_str());
+        if !is_ok {
+            span.record("otel.status_code", "ERROR");
+        }
+
+        let name = self.name.as_deref();
+        let client_name = req_ctx.client_name.as_deref();
+        let mut attributes = self.attributes(name, client_name);
+        attributes.push(KeyValue::new(
+            GRAPHQL_DOCUMENT_HASH,
+            self.document_hash.clone(),
+        ));
+        if let Some(client_version) = &req_ctx.client_version {
+            attributes.push(KeyValue::new(
+                GRAPHQL_CLIENT_VERSION,
+                client_version.clone(),
+            ));
+        }
+        for attribute in attributes {
+            if is_name_recorded && attribute.key.as_str() == GRAPHQL_OPERATION_NAME {
+                continue;
+            }
+            span.set_attribute(attribute.key, attribute.value);
+        }
+
+        if let Some(metrics) = metrics {
+            let (name, client_name) = metrics.operation_labels(name, client_name);
+            let attributes = self.attributes(name, client_name);
+            if !is_ok {
+                GRAPHQL_OPERATION_ERRORS.add(1, &attributes);
+            }
+            GRAPHQL_OPERATION_DURATION.record(duration.as_secs_f64(), &attributes);
+        }
+    }
+
+    /// The attributes shared by the span and the metrics.
+    fn attributes(&self, name: Option<&str>, client_name: Option<&str>) -> Vec<KeyValue> {
+        let mut attributes = Vec::with_capacity(5);
+        attributes.push(KeyValue::new(
+            GRAPHQL_OPERATION_TYPE,
+            self.operation_type.clone(),
+        ));
+        if let Some(name) = name {
+            attributes.push(KeyValue::new(GRAPHQL_OPERATION_NAME, name.to_string()));
+        }
+        if let Some(client_name) = client_name {
+            attributes.push(KeyValue::new(GRAPHQL_CLIENT_NAME, client_name.to_string()));
+        }
+
+        attributes
+    }
+}
+
+/// Hex encoded SHA-256 of the normalized document, so that formatting and
+/// comments don't change the hash of an operation.
+#[must_use]
+pub fn document_hash(query: &str) -> String {
+    Sha256::digest(normalize(query).as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Drops the comments, commas and whitespace of a document, keeping a single
+/// space only where tokens would merge without it.
+fn normalize(query: &str) -> String {
+    let mut normalized = String::with_capacity(query.len());
+    let mut is_separated = false;
+    let mut rest = query;
+
+    while let Some(c) = rest.chars().next() {
+        let len = if c == '#' {
+            is_separated = true;
+            rest.find(['\n', '\r']).unwrap_or(rest.len())
+        } else if c.is_whitespace() || c == ',' || c == '\u{feff}' {
+            is_separated = true;
+            c.len_utf8()
+        } else {
+            if is_separated
+                && let Some(last) = normalized.chars().last()
+                && ((is_name(last) && is_name(c)) || (last == '"' && c == '"'))
+            {
+                normalized.push(' ');
+            }
+            is_separated = false;
+
+            let len = if c == '"' {
+                string_len(rest)
+            } else {
+                c.len_utf8()
+            };
+            normalized.push_str(&rest[..len]);
+            len
+        };
+
+        rest = &rest[len..];
+    }
+
+    normalized
+}
+
+fn is_name(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+const BLOCK_QUOTE: &str = "\"\"\"";
+
+/// Length of the string or block string `rest` starts with, kept as is.
+fn string_len(rest: &str) -> usize {
+    if let Some(body) = rest.strip_prefix(BLOCK_QUOTE) {
+        let mut offset = 0;
+        while let Some(end) = body[offset..].find(BLOCK_QUOTE) {
+            // `\"""` is an escaped quote, not the end of the block string
+            if body[..offset + end].ends_with('\\') {
+                offset += end + 3;
+            } else {
+                return offset + end + 6;
+            }
+        }
+
+        return rest.len();
+    }
+
+    let mut is_escaped = false;
+    for (i, c) in rest.char_indices().skip(1) {
+        match c {
+            '"' if !is_escaped => return i + 1,
+            '\\' if !is_escaped => is_escaped = true,
+            '\n' | '\r' => return i,
+            _ => is_escaped = false,
+        }
+    }
+
+    rest.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_documents() {
+        let query = r#"
+            # Fetches a user
+            query User($id: Int!) {
+              user(id: $id, greeting: "hello,   world") {
+                id,
+                name
+                ... on User { email }
+              }
+            }
+        "#;
+
+        assert_eq!(
+            normalize(query),
+            r#"query User($id:Int!){user(id:$id greeting:"hello,   world"){id name...on User{email}}}"#
+        );
+    }
+
+    #[test]
+    fn keeps_block_strings() {
+        let query = r#"{ a(b: """ x \""" # y """) }"#;
+
+        assert_eq!(normalize(query), r#"{a(b:""" x \""" # y """)}"#);
+    }
+
+    #[test]
+    fn hash_ignores_formatting() {
+        assert_eq!(
+            document_hash("query { users { id name } }"),
+            document_hash("query {\n  users {\n    id,\n    name\n  }\n} # all users")
+        );
+        assert_ne!(
+            document_hash("query { users { id } }"),
+            document_hash("query { users { name } }")
+        );
+    }
+}
@@ -62,7 +62,7 @@ pub fn update_ir(ir: &mut IR, vec: &mut Vec<Auth>) {
         | IR::Map(_)
         | IR::Entity(_)
         | IR::Service(_) => {}
-        IR::Path(ir, _) | IR::Discriminate(_, ir) => {
+        IR::Path(ir, _) | IR::Mask(_, ir) | IR::Discriminate(_, ir) => {
             update_ir(ir, vec);
         }
         IR::Protect(auth, access_expr, ir_0) => {
@@ -20,7 +20,9 @@ impl<A> CheckCache<A> {
 fn check_cache(ir: &IR) -> Option<NonZeroU64> {
     match ir {
         IR::Cache(cache) => Some(cache.max_age),
-        IR::Path(ir, _) | IR::Protect(_, _, ir) | IR::Discriminate(_, ir) => check_cache(ir),
+        IR::Path(ir, _) | IR::Protect(_, _, ir) | IR::Mask(_, ir) | IR::Discriminate(_, ir) => {
+            check_cache(ir)
+        }
         IR::Pipe(ir, ir1) => match (check_cache(ir), check_cache(ir1)) {
             (Some(age1), Some(age2)) => Some(age1.min(age2)),
             _ => None,
@@ -20,6 +20,8 @@ pub fn is_const(ir: &IR) -> bool {
         IR::Dynamic(dynamic_value) => dynamic_value.is_const(),
         IR::IO(_) | IR::Cache(_) | IR::ContextPath(_) => false,
         IR::Path(ir, _) | IR::Protect(_, _, ir) | IR::Discriminate(_, ir) => is_const(ir),
+        // The masked value depends on the request unless it's always masked
+        IR::Mask(mask, ir) => mask.unless.is_none() && is_const(ir),
         IR::Map(map) => is_const(&map.input),
         IR::Pipe(ir, ir1) => is_const(ir) && is_const(ir1),
         IR::Merge(vec) => vec.iter().all(is_const),
@@ -18,7 +18,7 @@ impl<A> CheckProtected<A> {
 pub fn is_protected(ir: &IR) -> bool {
     match ir {
         IR::Dynamic(_) | IR::IO(_) | IR::Cache(_) | IR::ContextPath(_) | IR::Service(_) => false,
-        IR::Path(ir, _) | IR::Discriminate(_, ir) => is_protected(ir),
+        IR::Path(ir, _) | IR::Mask(_, ir) | IR::Discriminate(_, ir) => is_protected(ir),
         IR::Protect(_, _, _) => true,
         IR::Map(map) => is_protected(&map.input),
         IR::Pipe(ir, ir1) => is_protected(ir) || is_protected(ir1),
@@ -78,6 +78,8 @@ where
         Ok(OperationPlan {
             root_name: self.plan.root_name.clone(),
             operation_type: self.plan.operation_type,
+            operation_name: self.plan.operation_name,
+            document_hash: self.plan.document_hash,
             index,
             is_introspection_query: self.plan.is_introspection_query,
             is_dedupe: self.plan.is_dedupe,
@@ -32,6 +32,7 @@ pub mod print_schema;
 pub mod proto_reader;
 pub mod resource_reader;
 pub mod rest;
+pub mod rpc;
 pub mod runtime;
 pub mod s3;
 pub mod scalar;
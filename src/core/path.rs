@@ -88,6 +88,10 @@ impl<Ctx: ResolverContextLike> EvalContext<'_, Ctx> {
                     let claims = guard.clone()?;
                     Some(ValueString::String(Cow::Owned(claims.to_string())))
                 }
+                "tls" => {
+                    let cert = ctx.request_ctx.peer_certificate.as_ref()?;
+                    Some(ValueString::String(Cow::Owned(cert.to_value().to_string())))
+                }
                 _ => None,
             };
         }
@@ -123,6 +127,10 @@ impl<Ctx: ResolverContextLike> EvalContext<'_, Ctx> {
                         other => Some(ValueString::String(Cow::Owned(other.to_string()))),
                     }
                 }
+                "tls" => match ctx.request_ctx.peer_certificate.as_ref()?.get(tail)? {
+                    serde_json::Value::String(s) => Some(ValueString::String(Cow::Owned(s))),
+                    other => Some(ValueString::String(Cow::Owned(other.to_string()))),
+                },
                 _ => None,
             })
     }
@@ -168,7 +176,7 @@ mod tests {
         use indexmap::IndexMap;
 
         use crate::core::EnvIO;
-        use crate::core::http::RequestContext;
+        use crate::core::http::{PeerCertificate, RequestContext, SubjectAltNames};
         use crate::core::ir::{EvalContext, ResolverContextLike, SelectionField};
         use crate::core::path::{PathGraphql, PathString, PathValue, ValueString};
 
@@ -282,6 +290,14 @@ mod tests {
                     "key": "value"
                 }
             }));
+            req_ctx.peer_certificate = Some(Arc::new(PeerCertificate {
+                subject: BTreeMap::from([("CN".to_owned(), "orders-service".to_owned())]),
+                sans: SubjectAltNames {
+                    dns: vec!["orders.internal".to_owned()],
+                    ..Default::default()
+                },
+                fingerprint: "f9b5".to_owned(),
+            }));
 
             req_ctx
         });
@@ -433,6 +449,25 @@ mod tests {
             // claims as whole JSON
             assert!(EVAL_CTX.raw_value(&["claims"]).is_some());
 
+            // tls
+            assert_eq!(
+                EVAL_CTX.raw_value(&["tls", "subject", "CN"]),
+                Some(ValueString::String(Cow::Owned("orders-service".to_owned())))
+            );
+            assert_eq!(
+                EVAL_CTX.raw_value(&["tls", "sans", "dns", "0"]),
+                Some(ValueString::String(Cow::Owned(
+                    "orders.internal".to_owned()
+                )))
+            );
+            assert_eq!(
+                EVAL_CTX.raw_value(&["tls", "fingerprint"]),
+                Some(ValueString::String(Cow::Owned("f9b5".to_owned())))
+            );
+            assert_eq!(EVAL_CTX.raw_value(&["tls", "subject", "O"]), None);
+            assert_eq!(EVAL_CTX.raw_value(&["tls", "sans", "dns", "1"]), None);
+            assert!(EVAL_CTX.raw_value(&["tls"]).is_some());
+
             // other value types
             assert_eq!(EVAL_CTX.raw_value(&["foo", "key"]), None);
             assert_eq!(EVAL_CTX.raw_value(&["bar", "key"]), None);
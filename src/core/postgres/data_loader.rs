@@ -33,6 +33,7 @@ impl PostgresDataLoader {
         DataLoader::new(self)
             .delay(Duration::from_millis(batch.delay as u64))
             .max_batch_size(batch.max_size.unwrap_or_default())
+            .name("postgres")
     }
 }
 
@@ -1,3 +1,5 @@
 pub use reader::*;
+pub use refresh::{DescriptorDiff, ReflectionRefresh};
 mod fetch;
 mod reader;
+mod refresh;
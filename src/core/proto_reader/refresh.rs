@@ -0,0 +1,403 @@
+use std::collections::{BTreeMap, HashSet};
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use opentelemetry::metrics::Counter;
+use prost_reflect::prost_types::FileDescriptorSet;
+use prost_reflect::{DescriptorPool, EnumDescriptor, FieldDescriptor, Kind, MessageDescriptor};
+
+use super::{ProtoMetadata, ProtoReader};
+use crate::core::config::KeyValue;
+
+static GRPC_REFLECTION_INCOMPATIBLE_CHANGES: LazyLock<Counter<u64>> = LazyLock::new(|| {
+    let meter = opentelemetry::global::meter("grpc_reflection");
+
+    meter
+        .u64_counter("grpc.reflection.incompatible_changes")
+        .with_description("Number of incompatible changes found by re-reflecting gRPC upstreams")
+        .build()
+});
+
+/// How the services of a `@link(type: Grpc)` are reflected again, read from
+/// the link meta.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReflectionRefresh {
+    pub url: String,
+    pub headers: Option<Vec<KeyValue>>,
+    /// Time between two reflections (`refreshInterval`).
+    pub interval: Duration,
+    /// Descriptors the blueprint was built from.
+    pub descriptor_set: FileDescriptorSet,
+}
+
+impl ReflectionRefresh {
+    /// Reads the refresh interval (in seconds) from the meta of a Grpc link.
+    /// Returns `None` when the link isn't refreshed.
+    #[must_use]
+    pub fn from_meta(
+        url: String,
+        headers: Option<Vec<KeyValue>>,
+        meta: Option<&serde_json::Value>,
+        metadata: &[ProtoMetadata],
+    ) -> Option<Self> {
+        let interval = meta
+            .and_then(|m| m.get("refreshInterval"))
+            .and_then(serde_json::Value::as_u64)
+            .filter(|seconds| *seconds > 0)
+            .map(Duration::from_secs)?;
+
+        Some(Self { url, headers, interval, descriptor_set: merge(metadata) })
+    }
+
+    /// Starts reflecting the upstream in the background. Each reflection is
+    /// compared with the descriptors the blueprint was built from, and every
+    /// change is logged once; incompatible ones are also counted by the
+    /// `grpc.reflection.incompatible_changes` metric. The blueprint isn't
+    /// rebuilt, so new fields and methods are served after a restart.
+    pub fn spawn(self, reader: ProtoReader) {
+        tokio::spawn(async move {
+            let startup = match DescriptorPool::from_file_descriptor_set(
+                self.descriptor_set.clone(),
+            ) {
+                Ok(pool) => pool,
+                Err(err) => {
+                    tracing::warn!("Failed to load descriptors of {}: {err}", self.url);
+                    return;
+                }
+            };
+            let mut reported = HashSet::new();
+
+            loop {
+                tokio::time::sleep(self.interval).await;
+
+                let pool = match self.reflect(&reader).await {
+                    Ok(pool) => pool,
+                    Err(err) => {
+                        tracing::warn!("Failed to reflect gRPC services of {}: {err}", self.url);
+                        continue;
+                    }
+                };
+
+                let mut diff = DescriptorDiff::new(&startup, &pool);
+                diff.retain_unreported(&mut reported);
+                diff.log(&self.url);
+            }
+        });
+    }
+
+    async fn reflect(&self, reader: &ProtoReader) -> anyhow::Result<DescriptorPool> {
+        let metadata = reader.fetch(&self.url, self.headers.clone()).await?;
+        Ok(DescriptorPool::from_file_descriptor_set(merge(&metadata))?)
+    }
+}
+
+/// The files of every reflected service, each dependency kept once.
+fn merge(metadata: &[ProtoMetadata]) -> FileDescriptorSet {
+    let files = metadata
+        .iter()
+        .flat_map(|metadata| &metadata.descriptor_set.file)
+        .map(|file| (file.name().to_string(), file.clone()))
+        .collect::<BTreeMap<_, _>>();
+
+    FileDescriptorSet { file: files.into_values().collect() }
+}
+
+/// Changes between two reflections of an upstream.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct DescriptorDiff {
+    /// Changes operations built from the previous descriptors keep working
+    /// with, like added fields, methods and enum values.
+    pub compatible: Vec<String>,
+    /// Changes that break operations built from the previous descriptors,
+    /// like removed methods and fields, or fields whose type changed.
+    pub incompatible: Vec<String>,
+}
+
+impl DescriptorDiff {
+    #[must_use]
+    pub fn new(old: &DescriptorPool, new: &DescriptorPool) -> Self {
+        let mut diff = Self::default();
+
+        for service in old.services() {
+            let Some(new_service) = new.get_service_by_name(service.full_name()) else {
+                diff.incompatible
+                    .push(format!("Removed service {}", service.full_name()));
+                continue;
+            };
+
+            for method in service.methods() {
+                let Some(new_method) = new_service.methods().find(|m| m.name() == method.name())
+                else {
+                    diff.incompatible
+                        .push(format!("Removed method {}", method.full_name()));
+                    continue;
+                };
+
+                if method.input().full_name() != new_method.input().full_name()
+                    || method.output().full_name() != new_method.output().full_name()
+                    || method.is_client_streaming() != new_method.is_client_streaming()
+                    || method.is_server_streaming() != new_method.is_server_streaming()
+                {
+                    diff.incompatible
+                        .push(format!("Changed signature of method {}", method.full_name()));
+                }
+            }
+
+            for method in new_service.methods() {
+                if !service.methods().any(|m| m.name() == method.name()) {
+                    diff.compatible
+                        .push(format!("Added method {}", method.full_name()));
+                }
+            }
+        }
+
+        for service in new.services() {
+            if old.get_service_by_name(service.full_name()).is_none() {
+                diff.compatible
+                    .push(format!("Added service {}", service.full_name()));
+            }
+        }
+
+        for message in old.all_messages() {
+            match new.get_message_by_name(message.full_name()) {
+                Some(new_message) => diff.messages(&message, &new_message),
+                None => diff
+                    .incompatible
+                    .push(format!("Removed message {}", message.full_name())),
+            }
+        }
+
+        for enum_ in old.all_enums() {
+            match new.get_enum_by_name(enum_.full_name()) {
+                Some(new_enum) => diff.enums(&enum_, &new_enum),
+                None => diff
+                    .incompatible
+                    .push(format!("Removed enum {}", enum_.full_name())),
+            }
+        }
+
+        diff
+    }
+
+    /// Compares the fields of a message by their number.
+    fn messages(&mut self, old: &MessageDescriptor, new: &MessageDescriptor) {
+        for field in old.fields() {
+            let Some(new_field) = new.get_field(field.number()) else {
+                self.incompatible
+                    .push(format!("Removed field {}", field.full_name()));
+                continue;
+            };
+
+            if field.name() != new_field.name() {
+                self.incompatible.push(format!(
+                    "Renamed field {} to {}",
+                    field.full_name(),
+                    new_field.name()
+                ));
+            } else if field_type(&field) != field_type(&new_field) {
+                self.incompatible
+                    .push(format!("Changed type of field {}", field.full_name()));
+            }
+        }
+
+        for field in new.fields() {
+            if old.get_field(field.number()).is_none() {
+                self.compatible
+                    .push(format!("Added field {}", field.full_name()));
+            }
+        }
+    }
+
+    /// Compares the values of an enum by their number.
+    fn enums(&mut self, old: &EnumDescriptor, new: &EnumDescriptor) {
+        for value in old.values() {
+            if new.get_value(value.number()).is_none() {
+                self.incompatible.push(format!(
+                    "Removed enum value {}.{}",
+                    old.full_name(),
+                    value.name()
+                ));
+            }
+        }
+
+        for value in new.values() {
+            if old.get_value(value.number()).is_none() {
+                self.compatible.push(format!(
+                    "Added enum value {}.{}",
+                    new.full_name(),
+                    value.name()
+                ));
+            }
+        }
+    }
+
+    /// Drops the changes found by an earlier reflection, remembering the
+    /// others in `reported`.
+    fn retain_unreported(&mut self, reported: &mut HashSet<String>) {
+        self.compatible.retain(|change| reported.insert(change.clone()));
+        self.incompatible
+            .retain(|change| reported.insert(change.clone()));
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.compatible.is_empty() && self.incompatible.is_empty()
+    }
+
+    fn log(&self, url: &str) {
+        for change in &self.compatible {
+            tracing::info!("gRPC upstream {url} changed: {change} (served after a restart)");
+        }
+        for change in &self.incompatible {
+            tracing::warn!("gRPC upstream {url} changed incompatibly: {change}");
+        }
+
+        if !self.incompatible.is_empty() {
+            GRPC_REFLECTION_INCOMPATIBLE_CHANGES.add(
+                u64::try_from(self.incompatible.len()).unwrap_or(u64::MAX),
+                &[opentelemetry::KeyValue::new("url", url.to_string())],
+            );
+        }
+    }
+}
+
+/// The type of a field as it's sent on the wire and mapped to GraphQL.
+fn field_type(field: &FieldDescriptor) -> (String, bool) {
+    let kind = match field.kind() {
+        Kind::Message(message) => message.full_name().to_string(),
+        Kind::Enum(enum_) => enum_.full_name().to_string(),
+        kind => format!("{kind:?}"),
+    };
+
+    (kind, field.is_list())
+}
+
+#[cfg(test)]
+mod tests {
+    #![expect(clippy::unwrap_used, reason = "test code")]
+    use super::*;
+
+    const PROTO: &str = r#"
+        syntax = "proto3";
+        package news;
+
+        enum Status {
+          DRAFT = 0;
+          PUBLISHED = 1;
+        }
+
+        message News {
+          int32 id = 1;
+          string title = 2;
+          Status status = 3;
+        }
+
+        message NewsId {
+          int32 id = 1;
+        }
+
+        service NewsService {
+          rpc GetNews(NewsId) returns (News) {}
+          rpc DeleteNews(NewsId) returns (News) {}
+        }
+    "#;
+
+    fn pool(source: &str) -> DescriptorPool {
+        let file = protox_parse::parse("news.proto", source).unwrap();
+        DescriptorPool::from_file_descriptor_set(FileDescriptorSet { file: vec![file] }).unwrap()
+    }
+
+    #[test]
+    fn unchanged_descriptors() {
+        assert!(DescriptorDiff::new(&pool(PROTO), &pool(PROTO)).is_empty());
+    }
+
+    #[test]
+    fn compatible_changes() {
+        let new = PROTO
+            .replace("Status status = 3;", "Status status = 3;\n  string body = 4;")
+            .replace("PUBLISHED = 1;", "PUBLISHED = 1;\n  ARCHIVED = 2;")
+            .replace(
+                "rpc GetNews(NewsId) returns (News) {}",
+                "rpc GetNews(NewsId) returns (News) {}\n  rpc ListNews(NewsId) returns (News) {}",
+            );
+        let diff = DescriptorDiff::new(&pool(PROTO), &pool(&new));
+
+        assert_eq!(
+            diff.compatible,
+            vec![
+                "Added method news.NewsService.ListNews",
+                "Added field news.News.body",
+                "Added enum value news.Status.ARCHIVED",
+            ]
+        );
+        assert!(diff.incompatible.is_empty());
+    }
+
+    #[test]
+    fn incompatible_changes() {
+        let new = PROTO
+            .replace("string title = 2;", "int64 title = 2;")
+            .replace("Status status = 3;", "")
+            .replace("rpc DeleteNews(NewsId) returns (News) {}", "");
+        let diff = DescriptorDiff::new(&pool(PROTO), &pool(&new));
+
+        assert_eq!(
+            diff.incompatible,
+            vec![
+                "Removed method news.NewsService.DeleteNews",
+                "Changed type of field news.News.title",
+                "Removed field news.News.status",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_changes_once() {
+        let new = PROTO.replace("rpc DeleteNews(NewsId) returns (News) {}", "");
+        let mut reported = HashSet::new();
+
+        let mut diff = DescriptorDiff::new(&pool(PROTO), &pool(&new));
+        diff.retain_unreported(&mut reported);
+        assert_eq!(
+            diff.incompatible,
+            vec!["Removed method news.NewsService.DeleteNews"]
+        );
+
+        let mut diff = DescriptorDiff::new(&pool(PROTO), &pool(&new));
+        diff.retain_unreported(&mut reported);
+        assert!(diff.is_empty());
+    }
+
+    #[test]
+    fn refresh_from_meta() {
+        let metadata = [ProtoMetadata {
+            descriptor_set: FileDescriptorSet {
+                file: vec![protox_parse::parse("news.proto", PROTO).unwrap()],
+            },
+            path: "news.proto".to_string(),
+        }];
+        let url = "http://localhost:50051".to_string();
+
+        let refresh = ReflectionRefresh::from_meta(
+            url.clone(),
+            None,
+            Some(&serde_json::json!({ "refreshInterval": 30 })),
+            &metadata,
+        )
+        .unwrap();
+        assert_eq!(refresh.interval, Duration::from_secs(30));
+        assert_eq!(refresh.descriptor_set, metadata[0].descriptor_set);
+
+        for meta in [
+            None,
+            Some(serde_json::json!({})),
+            Some(serde_json::json!({ "refreshInterval": 0 })),
+            Some(serde_json::json!({ "refreshInterval": "30" })),
+        ] {
+            assert!(
+                ReflectionRefresh::from_meta(url.clone(), None, meta.as_ref(), &metadata).is_none()
+            );
+        }
+    }
+}
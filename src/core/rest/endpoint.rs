@@ -133,9 +133,9 @@ impl Endpoint {
         doc
     }
 
+    /// Drops `@rest`, and `@rpc` of operations also served over gRPC.
     fn drop_rest_directive(directives: &mut Vec<Positioned<Directive>>) {
-        let name = Name::new("rest");
-        directives.retain(|v| v.node.name.node != name);
+        directives.retain(|v| !matches!(v.node.name.node.as_str(), "rest" | "rpc"));
     }
 
     pub fn matches<'a>(&'a self, request: &Request) -> Option<PartialRequest<'a>> {
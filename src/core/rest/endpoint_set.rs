@@ -5,7 +5,7 @@ use gqlforge_valid::Validator;
 use super::endpoint::Endpoint;
 use super::partial_request::PartialRequest;
 use super::{Request, Result};
-use crate::core::blueprint::Blueprint;
+use crate::core::blueprint::{Blueprint, SchemaModifiers};
 use crate::core::http::RequestContext;
 use crate::core::macros::MergeRight;
 use crate::core::rest::operation::OperationQuery;
@@ -89,6 +89,31 @@ impl EndpointSet<Unchecked> {
             endpoints: self.endpoints,
         })
     }
+
+    /// Validates each endpoint on its own against `blueprint` and returns the
+    /// ones that fail, identified by their index in the set, with their
+    /// errors.
+    pub async fn invalid_endpoints(
+        &self,
+        blueprint: &Blueprint,
+        target_runtime: TargetRuntime,
+    ) -> Vec<(usize, Vec<String>)> {
+        let schema = blueprint.to_schema_with(&SchemaModifiers::default().with_no_resolver());
+        let req_ctx = Arc::new(RequestContext::new(target_runtime));
+        let mut invalid = vec![];
+
+        for (index, endpoint) in self.endpoints.iter().enumerate() {
+            let req = endpoint.clone().into_request();
+            let errors = OperationQuery::new(req, req_ctx.clone())
+                .validate(&schema)
+                .await;
+            if !errors.is_empty() {
+                invalid.push((index, errors.iter().map(ToString::to_string).collect()));
+            }
+        }
+
+        invalid
+    }
 }
 
 impl EndpointSet<Checked> {
@@ -20,7 +20,7 @@ impl OperationQuery {
         Self { query }
     }
 
-    async fn validate(self, schema: &Schema) -> Vec<Error> {
+    pub(super) async fn validate(self, schema: &Schema) -> Vec<Error> {
         schema
             .execute(self.query.0)
             .await
@@ -0,0 +1,209 @@
+use async_graphql_value::{ConstValue, Name, Number};
+use indexmap::IndexMap;
+use prost::Message;
+use prost_reflect::{DynamicMessage, Kind, MessageDescriptor, ReflectMessage, Value};
+
+use super::{Error, Result};
+
+/// Decodes a request message into the variables of its operation. Fields
+/// with presence that aren't set are left out, so they read as null.
+///
+/// # Errors
+///
+/// Returns an error if the payload isn't a valid message of `descriptor`.
+pub fn decode(descriptor: &MessageDescriptor, payload: &[u8]) -> Result<ConstValue> {
+    let message = DynamicMessage::decode(descriptor.clone(), payload)?;
+    Ok(from_message(&message))
+}
+
+fn from_message(message: &DynamicMessage) -> ConstValue {
+    let mut object = IndexMap::new();
+    for field in message.descriptor().fields() {
+        if field.supports_presence() && !message.has_field(&field) {
+            continue;
+        }
+
+        let value = message.get_field(&field);
+        object.insert(Name::new(field.name()), from_value(&value, &field.kind()));
+    }
+
+    ConstValue::Object(object)
+}
+
+fn from_value(value: &Value, kind: &Kind) -> ConstValue {
+    match value {
+        Value::Bool(value) => ConstValue::Boolean(*value),
+        Value::I32(value) => ConstValue::Number(Number::from(*value)),
+        Value::I64(value) => ConstValue::Number(Number::from(*value)),
+        Value::U32(value) => ConstValue::Number(Number::from(*value)),
+        Value::U64(value) => ConstValue::Number(Number::from(*value)),
+        Value::F32(value) => {
+            Number::from_f64(f64::from(*value)).map_or(ConstValue::Null, ConstValue::Number)
+        }
+        Value::F64(value) => Number::from_f64(*value).map_or(ConstValue::Null, ConstValue::Number),
+        Value::String(value) => ConstValue::String(value.clone()),
+        Value::Bytes(value) => ConstValue::Binary(value.clone()),
+        Value::EnumNumber(number) => kind
+            .as_enum()
+            .and_then(|descriptor| descriptor.get_value(*number))
+            .map_or(ConstValue::Null, |value| {
+                ConstValue::Enum(Name::new(value.name()))
+            }),
+        Value::Message(message) => from_message(message),
+        Value::List(values) => {
+            ConstValue::List(values.iter().map(|value| from_value(value, kind)).collect())
+        }
+        // Generated messages have no map fields.
+        Value::Map(_) => ConstValue::Null,
+    }
+}
+
+/// Encodes the data of a response as a message of `descriptor`. Null fields
+/// are left unset.
+///
+/// # Errors
+///
+/// Returns an error if a value doesn't fit the type of its field.
+pub fn encode(descriptor: &MessageDescriptor, data: &ConstValue) -> Result<Vec<u8>> {
+    Ok(to_message(descriptor, data)?.encode_to_vec())
+}
+
+fn encode_error(value: &ConstValue, expected: impl ToString) -> Error {
+    Error::Encode { value: value.to_string(), expected: expected.to_string() }
+}
+
+fn to_message(descriptor: &MessageDescriptor, value: &ConstValue) -> Result<DynamicMessage> {
+    let ConstValue::Object(object) = value else {
+        return Err(encode_error(value, descriptor.full_name()));
+    };
+
+    let mut message = DynamicMessage::new(descriptor.clone());
+    for field in descriptor.fields() {
+        let value = match object.get(field.name()) {
+            None | Some(ConstValue::Null) => continue,
+            Some(value) => value,
+        };
+
+        let converted = if field.is_list() {
+            let ConstValue::List(items) = value else {
+                return Err(encode_error(value, "a list"));
+            };
+            Value::List(
+                items
+                    .iter()
+                    .map(|item| to_value(item, &field.kind()))
+                    .collect::<Result<_>>()?,
+            )
+        } else {
+            to_value(value, &field.kind())?
+        };
+
+        message
+            .try_set_field(&field, converted)
+            .map_err(|_| encode_error(value, field.full_name()))?;
+    }
+
+    Ok(message)
+}
+
+fn to_value(value: &ConstValue, kind: &Kind) -> Result<Value> {
+    let converted = match (kind, value) {
+        (Kind::Message(descriptor), value) => Some(Value::Message(to_message(descriptor, value)?)),
+        (Kind::Enum(descriptor), ConstValue::Enum(name)) => descriptor
+            .get_value_by_name(name.as_str())
+            .map(|value| Value::EnumNumber(value.number())),
+        (Kind::Enum(descriptor), ConstValue::String(name)) => descriptor
+            .get_value_by_name(name)
+            .map(|value| Value::EnumNumber(value.number())),
+        (Kind::Bool, ConstValue::Boolean(value)) => Some(Value::Bool(*value)),
+        (Kind::Int32, ConstValue::Number(number)) => number
+            .as_i64()
+            .and_then(|number| i32::try_from(number).ok())
+            .map(Value::I32),
+        (Kind::Int64, ConstValue::Number(number)) => number.as_i64().map(Value::I64),
+        (Kind::Int64, ConstValue::String(number)) => number.parse().ok().map(Value::I64),
+        (Kind::Uint32, ConstValue::Number(number)) => number
+            .as_u64()
+            .and_then(|number| u32::try_from(number).ok())
+            .map(Value::U32),
+        (Kind::Uint64, ConstValue::Number(number)) => number.as_u64().map(Value::U64),
+        (Kind::Uint64, ConstValue::String(number)) => number.parse().ok().map(Value::U64),
+        (Kind::Double, ConstValue::Number(number)) => number.as_f64().map(Value::F64),
+        (Kind::String, ConstValue::String(value)) => Some(Value::String(value.clone())),
+        (Kind::String, ConstValue::Enum(value)) => Some(Value::String(value.to_string())),
+        // Scalars without a protobuf counterpart, like `JSON`, are sent as JSON
+        // text.
+        (Kind::String, value) => Some(Value::String(serde_json::to_string(value)?)),
+        _ => None,
+    };
+
+    converted.ok_or_else(|| encode_error(value, format!("{kind:?}")))
+}
+
+#[cfg(test)]
+mod tests {
+    #![expect(clippy::unwrap_used, reason = "test code")]
+    use prost_reflect::DescriptorPool;
+    use prost_reflect::prost_types::FileDescriptorSet;
+    use serde_json::json;
+
+    use super::*;
+
+    const PROTO: &str = r#"
+        syntax = "proto3";
+        package test;
+
+        enum Status {
+          ACTIVE = 0;
+          INACTIVE = 1;
+        }
+
+        message User {
+          int32 id = 1;
+          optional string name = 2;
+          Status status = 3;
+          repeated int64 scores = 4;
+          string meta = 5;
+        }
+    "#;
+
+    fn user_descriptor() -> MessageDescriptor {
+        let file = protox_parse::parse("test.proto", PROTO).unwrap();
+        let pool =
+            DescriptorPool::from_file_descriptor_set(FileDescriptorSet { file: vec![file] })
+                .unwrap();
+        pool.get_message_by_name("test.User").unwrap()
+    }
+
+    #[test]
+    fn round_trips_messages() {
+        let descriptor = user_descriptor();
+        let data = ConstValue::from_json(json!({
+            "id": 1,
+            "name": null,
+            "status": "INACTIVE",
+            "scores": [1, "2"],
+            "meta": { "admin": true }
+        }))
+        .unwrap();
+
+        let payload = encode(&descriptor, &data).unwrap();
+        let decoded = decode(&descriptor, &payload).unwrap();
+
+        let expected = json!({
+            "id": 1,
+            "status": "INACTIVE",
+            "scores": [1, 2],
+            "meta": "{\"admin\":true}"
+        });
+        assert_eq!(decoded.into_json().unwrap(), expected);
+    }
+
+    #[test]
+    fn rejects_mismatched_values() {
+        let descriptor = user_descriptor();
+        let data = ConstValue::from_json(json!({ "id": "one" })).unwrap();
+
+        assert!(encode(&descriptor, &data).is_err());
+    }
+}
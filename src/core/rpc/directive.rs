@@ -0,0 +1,32 @@
+use async_graphql::parser::types::Directive;
+
+use super::Result;
+
+pub(crate) const RPC: &str = "rpc";
+
+/// A structure that represents the RPC directive, which selects the
+/// operations served as gRPC methods.
+#[derive(Default, Debug, PartialEq)]
+pub(crate) struct Rpc {
+    /// Fully qualified name of the service, like `users.UserService`.
+    pub service: Option<String>,
+    pub method: Option<String>,
+}
+
+impl TryFrom<&Directive> for Rpc {
+    type Error = super::Error;
+
+    fn try_from(directive: &Directive) -> Result<Self> {
+        let mut rpc = Rpc::default();
+
+        for (k, v) in &directive.arguments {
+            match k.node.as_str() {
+                "service" => rpc.service = Some(serde_json::from_str(&v.node.to_string())?),
+                "method" => rpc.method = Some(serde_json::from_str(&v.node.to_string())?),
+                _ => {}
+            }
+        }
+
+        Ok(rpc)
+    }
+}
@@ -0,0 +1,48 @@
+use async_graphql::parser::types::OperationType;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Async Graphql Parser Error: {}", _0)]
+    ParseGraphQL(#[from] async_graphql::parser::Error),
+
+    #[error("Serde Json Error: {}", _0)]
+    SerdeJson(#[from] serde_json::Error),
+
+    #[error("Protobuf Descriptor Error: {}", _0)]
+    Descriptor(#[from] prost_reflect::DescriptorError),
+
+    #[error("Protobuf Decode Error: {}", _0)]
+    Decode(#[from] prost::DecodeError),
+
+    #[error("Plan Error: {}", _0)]
+    Plan(#[from] crate::core::jit::Error),
+
+    #[error("@rpc on an anonymous operation needs a method name")]
+    MissingMethodName,
+
+    #[error("Duplicate gRPC method: {}", _0)]
+    DuplicateMethod(String),
+
+    #[error("Root operation type not defined for {}", _0)]
+    RootTypeNotDefined(OperationType),
+
+    #[error("{} operations can't be served as unary gRPC methods", _0)]
+    UnsupportedOperation(OperationType),
+
+    #[error("Unknown type: {}", _0)]
+    UnknownType(String),
+
+    #[error("Unknown field: {type_name}.{field}")]
+    UnknownField { type_name: String, field: String },
+
+    #[error("Unknown fragment: {}", _0)]
+    UnknownFragment(String),
+
+    #[error("Nested lists can't be mapped to protobuf: {}", _0)]
+    NestedList(String),
+
+    #[error("Can't encode {value} as {expected}")]
+    Encode { value: String, expected: String },
+}
+
+pub type Result<A> = std::result::Result<A, Error>;
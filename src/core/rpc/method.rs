@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+
+use async_graphql::Positioned;
+use async_graphql::parser::types::{FragmentDefinition, OperationDefinition};
+use async_graphql_value::Name;
+use convert_case::{Case, Casing};
+
+use super::directive::{RPC, Rpc};
+use super::{Error, Result};
+
+/// Service of the methods whose `@rpc` doesn't name one.
+pub const DEFAULT_SERVICE: &str = "gqlforge.Gateway";
+
+/// A unary gRPC method created from a GraphQL operation
+#[derive(Debug, Clone)]
+pub struct Method {
+    package: String,
+    service: String,
+    name: String,
+    operation_name: Option<String>,
+    /// Source of the document holding the operation, which is executed by
+    /// its name.
+    query: String,
+    pub operation: OperationDefinition,
+    pub fragments: HashMap<Name, Positioned<FragmentDefinition>>,
+}
+
+/// Creates a Method instance from @rpc directive
+impl Method {
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the operations can't be parsed or an anonymous
+    /// operation doesn't name its method.
+    pub fn try_new(operations: &str) -> Result<Vec<Self>> {
+        let doc = async_graphql::parser::parse_query(operations)?;
+        let mut methods = Vec::new();
+
+        for (operation_name, op) in doc.operations.iter() {
+            let rpc = op.node.directives.iter().find_map(|d| {
+                if d.node.name.node == RPC {
+                    Some(Rpc::try_from(&d.node))
+                } else {
+                    None
+                }
+            });
+
+            let Some(rpc) = rpc else {
+                continue;
+            };
+            let rpc = rpc?;
+
+            let name = match (rpc.method, operation_name) {
+                (Some(method), _) => method,
+                (None, Some(operation_name)) => operation_name.as_str().to_case(Case::Pascal),
+                (None, None) => return Err(Error::MissingMethodName),
+            };
+            let service = rpc.service.unwrap_or_else(|| DEFAULT_SERVICE.to_string());
+            let (package, service) = match service.rsplit_once('.') {
+                Some((package, service)) => (package.to_string(), service.to_string()),
+                None => (String::new(), service),
+            };
+
+            let pos = (op.pos.line, op.pos.column);
+            methods.push((pos, Self {
+                package,
+                service,
+                name,
+                operation_name: operation_name.map(ToString::to_string),
+                query: operations.to_string(),
+                operation: op.node.clone(),
+                fragments: doc.fragments.clone(),
+            }));
+        }
+
+        // Operations are kept in a map, so they're put back in source order.
+        methods.sort_by_key(|(pos, _)| *pos);
+        Ok(methods.into_iter().map(|(_, method)| method).collect())
+    }
+
+    #[must_use]
+    pub fn package(&self) -> &str {
+        &self.package
+    }
+
+    #[must_use]
+    pub fn service(&self) -> &str {
+        &self.service
+    }
+
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[must_use]
+    pub fn operation_name(&self) -> Option<&str> {
+        self.operation_name.as_deref()
+    }
+
+    #[must_use]
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// The HTTP/2 path the method is called on, like
+    /// `/gqlforge.Gateway/GetUser`.
+    #[must_use]
+    pub fn path(&self) -> String {
+        if self.package.is_empty() {
+            format!("/{}/{}", self.service, self.name)
+        } else {
+            format!("/{}.{}/{}", self.package, self.service, self.name)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![expect(clippy::unwrap_used, reason = "test code")]
+    use super::*;
+
+    const TEST_QUERY: &str = r#"
+        query getUser($id: Int!) @rpc {
+            user(id: $id) { id }
+        }
+
+        query listPosts @rpc(service: "blog.v1.PostService", method: "List") {
+            posts { id }
+        }
+
+        query skipped {
+            posts { id }
+        }
+    "#;
+
+    #[test]
+    fn test_methods() {
+        let methods = Method::try_new(TEST_QUERY).unwrap();
+        let paths = methods.iter().map(Method::path).collect::<Vec<_>>();
+
+        assert_eq!(
+            paths,
+            vec!["/gqlforge.Gateway/GetUser", "/blog.v1.PostService/List"]
+        );
+        assert_eq!(methods[1].operation_name(), Some("listPosts"));
+    }
+
+    #[test]
+    fn test_anonymous_operation_without_method() {
+        let result = Method::try_new("query @rpc { posts { id } }");
+        assert!(matches!(result, Err(Error::MissingMethodName)));
+    }
+}
@@ -0,0 +1,212 @@
+use std::hash::{Hash, Hasher};
+
+use async_graphql_value::ConstValue;
+use gqlforge_hasher::GqlforgeHasher;
+use prost_reflect::prost_types::{FileDescriptorProto, FileDescriptorSet};
+use prost_reflect::{DescriptorPool, MessageDescriptor};
+
+use super::method::Method;
+use super::{Error, Result, proto};
+use crate::core::blueprint::{Blueprint, Index};
+use crate::core::jit::{self, OPHash};
+use crate::core::macros::MergeRight;
+use crate::core::rest::{Checked, Unchecked};
+
+/// A method checked against the blueprint, with the messages its calls are
+/// encoded with.
+#[derive(Clone, Debug)]
+pub struct Route {
+    path: String,
+    query: String,
+    operation_name: Option<String>,
+    plan_hash: OPHash,
+    input: MessageDescriptor,
+    output: MessageDescriptor,
+}
+
+impl Route {
+    #[must_use]
+    pub fn input(&self) -> &MessageDescriptor {
+        &self.input
+    }
+
+    #[must_use]
+    pub fn output(&self) -> &MessageDescriptor {
+        &self.output
+    }
+
+    /// Key of the plan of the operation in the operation plan cache.
+    #[must_use]
+    pub fn plan_hash(&self) -> &OPHash {
+        &self.plan_hash
+    }
+
+    /// A request executing the operation of the method with the variables
+    /// decoded from a call.
+    #[must_use]
+    pub fn request(&self, variables: ConstValue) -> jit::Request<ConstValue> {
+        let variables = match variables {
+            ConstValue::Object(variables) => variables,
+            _ => Default::default(),
+        };
+
+        jit::Request {
+            operation_name: self.operation_name.clone(),
+            ..jit::Request::new(&self.query)
+        }
+        .variables(variables.into_iter().map(|(k, v)| (k.to_string(), v)))
+    }
+}
+
+/// Collection of gRPC methods
+#[derive(Default, Clone, Debug, MergeRight)]
+pub struct MethodSet<Status> {
+    methods: Vec<Method>,
+    routes: Vec<Route>,
+    marker: std::marker::PhantomData<Status>,
+}
+
+impl MethodSet<Unchecked> {
+    #[must_use]
+    pub fn get_methods(&self) -> &Vec<Method> {
+        &self.methods
+    }
+
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the operations can't be parsed.
+    pub fn try_new(operations: &str) -> Result<MethodSet<Unchecked>> {
+        Ok(MethodSet { methods: Method::try_new(operations)?, ..Default::default() })
+    }
+
+    /// Generates the `.proto` files declaring the methods, one per package.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an operation selects fields or uses types that
+    /// aren't in the blueprint, or can't be mapped to protobuf.
+    pub fn to_proto(&self, blueprint: &Blueprint) -> Result<Vec<FileDescriptorProto>> {
+        proto::build(&Index::from(blueprint), &self.methods)
+    }
+
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a method can't be served from `blueprint`, or two
+    /// methods share a name.
+    pub fn into_checked(self, blueprint: &Blueprint) -> Result<MethodSet<Checked>> {
+        let pool = DescriptorPool::from_file_descriptor_set(FileDescriptorSet {
+            file: self.to_proto(blueprint)?,
+        })?;
+        let mut routes: Vec<Route> = Vec::with_capacity(self.methods.len());
+
+        for method in &self.methods {
+            let path = method.path();
+            if routes.iter().any(|route| route.path == path) {
+                return Err(Error::DuplicateMethod(path));
+            }
+
+            let prefix = if method.package().is_empty() {
+                String::new()
+            } else {
+                format!("{}.", method.package())
+            };
+            let message = |suffix: &str| {
+                let name = format!("{prefix}{}{suffix}", method.name());
+                pool.get_message_by_name(&name)
+                    .ok_or(Error::UnknownType(name))
+            };
+
+            let mut hasher = GqlforgeHasher::default();
+            method.query().hash(&mut hasher);
+            method.operation_name().hash(&mut hasher);
+
+            let route = Route {
+                path,
+                query: method.query().to_string(),
+                operation_name: method.operation_name().map(ToString::to_string),
+                plan_hash: OPHash::new(hasher.finish()),
+                input: message("Request")?,
+                output: message("Response")?,
+            };
+
+            // Fails early on operations the JIT can't plan.
+            route
+                .request(ConstValue::Null)
+                .create_plan(blueprint)?;
+            routes.push(route);
+        }
+
+        Ok(MethodSet {
+            methods: self.methods,
+            routes,
+            marker: std::marker::PhantomData::<Checked>,
+        })
+    }
+}
+
+impl MethodSet<Checked> {
+    #[must_use]
+    pub fn find(&self, path: &str) -> Option<&Route> {
+        self.routes.iter().find(|route| route.path == path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![expect(clippy::unwrap_used, reason = "test code")]
+    use insta::assert_snapshot;
+
+    use super::*;
+    use crate::core::config::ConfigModule;
+    use crate::include_config;
+
+    const OPERATIONS: &str = r#"
+        query getUser($id: Int!) @rpc {
+            user(id: $id) {
+                id
+                name
+                ... on User { email }
+            }
+        }
+
+        query listPosts @rpc(method: "ListPosts") {
+            posts { id title }
+        }
+    "#;
+
+    fn blueprint() -> Blueprint {
+        let config = include_config!("fixtures/rpc.graphql").unwrap();
+        Blueprint::try_from(&ConfigModule::from(config)).unwrap()
+    }
+
+    #[test]
+    fn test_proto() {
+        let set = MethodSet::try_new(OPERATIONS).unwrap();
+        let files = set.to_proto(&blueprint()).unwrap();
+
+        assert_snapshot!(proto::print(&files[0]));
+    }
+
+    #[test]
+    fn test_into_checked() {
+        let set = MethodSet::try_new(OPERATIONS)
+            .unwrap()
+            .into_checked(&blueprint())
+            .unwrap();
+        let route = set.find("/gqlforge.Gateway/GetUser").unwrap();
+
+        assert_eq!(route.input().full_name(), "gqlforge.GetUserRequest");
+        assert_eq!(route.output().full_name(), "gqlforge.GetUserResponse");
+        assert!(set.find("/gqlforge.Gateway/Unknown").is_none());
+    }
+
+    #[test]
+    fn test_unknown_field() {
+        let set = MethodSet::try_new("query getUser @rpc { user(id: 1) { age } }").unwrap();
+        let result = set.into_checked(&blueprint());
+
+        assert!(matches!(result, Err(Error::UnknownField { .. })));
+    }
+}
@@ -0,0 +1,11 @@
+pub mod codec;
+mod directive;
+pub mod error;
+mod method;
+mod method_set;
+pub mod proto;
+
+pub use method::{DEFAULT_SERVICE, Method};
+pub use method_set::{MethodSet, Route};
+
+pub use error::{Error, Result};
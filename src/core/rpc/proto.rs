@@ -0,0 +1,511 @@
+use std::collections::BTreeMap;
+
+use async_graphql::parser::types::{self as gql, OperationType, Selection, SelectionSet};
+use convert_case::{Case, Casing};
+use indexmap::IndexMap;
+use prost_reflect::prost_types::field_descriptor_proto::{Label, Type as ProtoType};
+use prost_reflect::prost_types::{
+    DescriptorProto, EnumDescriptorProto, EnumValueDescriptorProto, FieldDescriptorProto,
+    FileDescriptorProto, MethodDescriptorProto, OneofDescriptorProto, ServiceDescriptorProto,
+};
+
+use super::method::Method;
+use super::{Error, Result};
+use crate::core::Type;
+use crate::core::blueprint::{
+    EnumTypeDefinition, Index, InputObjectTypeDefinition, QueryField,
+};
+
+/// A GraphQL type flattened to the shape of a protobuf field.
+struct Shape {
+    name: String,
+    repeated: bool,
+    nullable: bool,
+}
+
+impl Shape {
+    fn new(ty: &Type) -> Result<Self> {
+        match ty {
+            Type::Named { name, non_null } => {
+                Ok(Self { name: name.clone(), repeated: false, nullable: !non_null })
+            }
+            Type::List { of_type, non_null } => match of_type.as_ref() {
+                Type::Named { name, .. } => {
+                    Ok(Self { name: name.clone(), repeated: true, nullable: !non_null })
+                }
+                Type::List { .. } => Err(Error::NestedList(gql::Type::from(ty).to_string())),
+            },
+        }
+    }
+}
+
+/// The protobuf type of a field, with the fully qualified name of enums and
+/// messages.
+enum FieldType {
+    Scalar(ProtoType),
+    Enum(String),
+    Message(String),
+}
+
+/// The protobuf type GraphQL scalars are carried as. Scalars without a
+/// protobuf counterpart are sent as strings.
+fn scalar_type(name: &str) -> ProtoType {
+    match name {
+        "Int" | "Int8" | "Int16" | "Int32" => ProtoType::Int32,
+        "Int64" => ProtoType::Int64,
+        "UInt8" | "UInt16" | "UInt32" => ProtoType::Uint32,
+        "UInt64" => ProtoType::Uint64,
+        "Float" => ProtoType::Double,
+        "Boolean" => ProtoType::Bool,
+        _ => ProtoType::String,
+    }
+}
+
+fn field_number(index: usize) -> i32 {
+    i32::try_from(index + 1).unwrap_or(i32::MAX)
+}
+
+fn message(name: &str) -> DescriptorProto {
+    DescriptorProto { name: Some(name.to_string()), ..Default::default() }
+}
+
+fn push_field(message: &mut DescriptorProto, name: &str, index: usize, shape: &Shape, ty: FieldType) {
+    let (r#type, type_name) = match ty {
+        FieldType::Scalar(r#type) => (r#type, None),
+        FieldType::Enum(name) => (ProtoType::Enum, Some(name)),
+        FieldType::Message(name) => (ProtoType::Message, Some(name)),
+    };
+    let label = if shape.repeated {
+        Label::Repeated
+    } else {
+        Label::Optional
+    };
+
+    let mut field = FieldDescriptorProto {
+        name: Some(name.to_string()),
+        json_name: Some(name.to_string()),
+        number: Some(field_number(index)),
+        label: Some(label as i32),
+        r#type: Some(r#type as i32),
+        type_name,
+        ..Default::default()
+    };
+
+    // Nullable scalars and enums track presence, so that null isn't read as
+    // their default value.
+    if shape.nullable && !shape.repeated && r#type != ProtoType::Message {
+        field.proto3_optional = Some(true);
+        field.oneof_index = Some(i32::try_from(message.oneof_decl.len()).unwrap_or(i32::MAX));
+        message.oneof_decl.push(OneofDescriptorProto {
+            name: Some(format!("_{name}")),
+            ..Default::default()
+        });
+    }
+
+    message.field.push(field);
+}
+
+/// A field of a selection set, with the type it's selected on.
+struct SelectedField<'a> {
+    type_name: String,
+    field: &'a gql::Field,
+    /// Whether the field is selected in a fragment on a narrower type, and
+    /// so may be missing from the response.
+    conditional: bool,
+}
+
+/// Collects the fields of a selection set by response key, flattening its
+/// fragments.
+fn collect_fields<'a>(
+    type_name: &str,
+    selection_set: &'a SelectionSet,
+    method: &'a Method,
+    conditional: bool,
+    fields: &mut IndexMap<String, SelectedField<'a>>,
+) -> Result<()> {
+    for selection in &selection_set.items {
+        match &selection.node {
+            Selection::Field(field) => {
+                let field = &field.node;
+                let key = field.alias.as_ref().unwrap_or(&field.name).node.to_string();
+                fields.entry(key).or_insert_with(|| SelectedField {
+                    type_name: type_name.to_string(),
+                    field,
+                    conditional,
+                });
+            }
+            Selection::FragmentSpread(spread) => {
+                let name = &spread.node.fragment_name.node;
+                let fragment = method
+                    .fragments
+                    .get(name)
+                    .ok_or_else(|| Error::UnknownFragment(name.to_string()))?;
+                let on = fragment.node.type_condition.node.on.node.as_str();
+                collect_fields(
+                    on,
+                    &fragment.node.selection_set.node,
+                    method,
+                    conditional || on != type_name,
+                    fields,
+                )?;
+            }
+            Selection::InlineFragment(fragment) => {
+                let fragment = &fragment.node;
+                let on = fragment
+                    .type_condition
+                    .as_ref()
+                    .map_or(type_name, |condition| condition.node.on.node.as_str());
+                collect_fields(
+                    on,
+                    &fragment.selection_set.node,
+                    method,
+                    conditional || on != type_name,
+                    fields,
+                )?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Builds the `.proto` file of the methods of a package.
+struct FileBuilder<'a> {
+    index: &'a Index,
+    package: &'a str,
+    enums: BTreeMap<String, EnumDescriptorProto>,
+    inputs: BTreeMap<String, DescriptorProto>,
+    messages: Vec<DescriptorProto>,
+    services: IndexMap<String, ServiceDescriptorProto>,
+}
+
+impl<'a> FileBuilder<'a> {
+    fn new(index: &'a Index, package: &'a str) -> Self {
+        Self {
+            index,
+            package,
+            enums: BTreeMap::new(),
+            inputs: BTreeMap::new(),
+            messages: Vec::new(),
+            services: IndexMap::new(),
+        }
+    }
+
+    fn qualify(&self, name: &str) -> String {
+        if self.package.is_empty() {
+            format!(".{name}")
+        } else {
+            format!(".{}.{name}", self.package)
+        }
+    }
+
+    fn add_method(&mut self, method: &Method) -> Result<()> {
+        let index = self.index;
+        let ty = method.operation.ty;
+        let root = match ty {
+            OperationType::Query => index.get_query().as_str(),
+            OperationType::Mutation => index.get_mutation().ok_or(Error::RootTypeNotDefined(ty))?,
+            OperationType::Subscription => return Err(Error::UnsupportedOperation(ty)),
+        };
+
+        let request_name = format!("{}Request", method.name());
+        let mut request = message(&request_name);
+        for (i, variable) in method.operation.variable_definitions.iter().enumerate() {
+            let variable = &variable.node;
+            let shape = Shape::new(&Type::from(&variable.var_type.node))?;
+            let ty = self.input_type(&shape.name)?;
+            push_field(&mut request, variable.name.node.as_str(), i, &shape, ty);
+        }
+
+        let response_name = format!("{}Response", method.name());
+        let input_type = self.qualify(&request_name);
+        let output_type = self.qualify(&response_name);
+        let response = self.selection_message(
+            &response_name,
+            &output_type,
+            root,
+            &method.operation.selection_set.node,
+            method,
+        )?;
+
+        self.messages.push(request);
+        self.messages.push(response);
+
+        self.services
+            .entry(method.service().to_string())
+            .or_insert_with(|| ServiceDescriptorProto {
+                name: Some(method.service().to_string()),
+                ..Default::default()
+            })
+            .method
+            .push(MethodDescriptorProto {
+                name: Some(method.name().to_string()),
+                input_type: Some(input_type),
+                output_type: Some(output_type),
+                ..Default::default()
+            });
+
+        Ok(())
+    }
+
+    fn input_type(&mut self, name: &str) -> Result<FieldType> {
+        let index = self.index;
+        if let Some(def) = index.get_enum_definition(name) {
+            self.add_enum(def);
+            Ok(FieldType::Enum(self.qualify(name)))
+        } else if let Some(def) = index.get_input_type_definition(name) {
+            self.add_input(def)?;
+            Ok(FieldType::Message(self.qualify(name)))
+        } else if index.type_is_scalar(name) {
+            Ok(FieldType::Scalar(scalar_type(name)))
+        } else {
+            Err(Error::UnknownType(name.to_string()))
+        }
+    }
+
+    fn add_enum(&mut self, def: &EnumTypeDefinition) {
+        if self.enums.contains_key(&def.name) {
+            return;
+        }
+
+        let value = def
+            .enum_values
+            .iter()
+            .enumerate()
+            .map(|(i, value)| EnumValueDescriptorProto {
+                name: Some(value.name.clone()),
+                number: Some(i32::try_from(i).unwrap_or(i32::MAX)),
+                ..Default::default()
+            })
+            .collect();
+
+        self.enums.insert(
+            def.name.clone(),
+            EnumDescriptorProto { name: Some(def.name.clone()), value, ..Default::default() },
+        );
+    }
+
+    fn add_input(&mut self, def: &InputObjectTypeDefinition) -> Result<()> {
+        if self.inputs.contains_key(&def.name) {
+            return Ok(());
+        }
+
+        // Registered before its fields, so that recursive inputs refer to it.
+        self.inputs.insert(def.name.clone(), message(&def.name));
+
+        let mut input = message(&def.name);
+        for (i, field) in def.fields.iter().enumerate() {
+            let shape = Shape::new(&field.of_type)?;
+            let ty = self.input_type(&shape.name)?;
+            push_field(&mut input, &field.name, i, &shape, ty);
+        }
+
+        self.inputs.insert(def.name.clone(), input);
+        Ok(())
+    }
+
+    /// The message of a selection set on `type_name`. Selections of objects
+    /// become messages nested in it.
+    fn selection_message(
+        &mut self,
+        name: &str,
+        qualified: &str,
+        type_name: &str,
+        selection_set: &SelectionSet,
+        method: &Method,
+    ) -> Result<DescriptorProto> {
+        let index = self.index;
+        let mut fields = IndexMap::new();
+        collect_fields(type_name, selection_set, method, false, &mut fields)?;
+
+        let mut message = message(name);
+        for (i, (key, selected)) in fields.iter().enumerate() {
+            let field_name = selected.field.name.node.as_str();
+            if field_name == "__typename" {
+                let shape = Shape {
+                    name: "String".to_string(),
+                    repeated: false,
+                    nullable: selected.conditional,
+                };
+                push_field(&mut message, key, i, &shape, FieldType::Scalar(ProtoType::String));
+                continue;
+            }
+
+            let Some(QueryField::Field(def)) = index.get_field(&selected.type_name, field_name)
+            else {
+                return Err(Error::UnknownField {
+                    type_name: selected.type_name.clone(),
+                    field: field_name.to_string(),
+                });
+            };
+
+            let mut shape = Shape::new(&def.0.of_type)?;
+            shape.nullable |= selected.conditional;
+
+            let ty = if index.get_enum_definition(&shape.name).is_some() {
+                self.input_type(&shape.name)?
+            } else if index.type_is_scalar(&shape.name) {
+                FieldType::Scalar(scalar_type(&shape.name))
+            } else {
+                let nested_name = key.to_case(Case::Pascal);
+                let nested_qualified = format!("{qualified}.{nested_name}");
+                let nested = self.selection_message(
+                    &nested_name,
+                    &nested_qualified,
+                    &shape.name,
+                    &selected.field.selection_set.node,
+                    method,
+                )?;
+                message.nested_type.push(nested);
+                FieldType::Message(nested_qualified)
+            };
+
+            push_field(&mut message, key, i, &shape, ty);
+        }
+
+        Ok(message)
+    }
+
+    fn finish(self) -> FileDescriptorProto {
+        let name = if self.package.is_empty() {
+            "rpc.proto".to_string()
+        } else {
+            format!("{}.proto", self.package)
+        };
+
+        FileDescriptorProto {
+            name: Some(name),
+            package: (!self.package.is_empty()).then(|| self.package.to_string()),
+            message_type: self.inputs.into_values().chain(self.messages).collect(),
+            enum_type: self.enums.into_values().collect(),
+            service: self.services.into_values().collect(),
+            syntax: Some("proto3".to_string()),
+            ..Default::default()
+        }
+    }
+}
+
+/// Generates the `.proto` files declaring `methods`, one per package.
+///
+/// # Errors
+///
+/// Returns an error if an operation selects fields or uses types that aren't
+/// in `index`, or can't be mapped to protobuf.
+pub fn build(index: &Index, methods: &[Method]) -> Result<Vec<FileDescriptorProto>> {
+    let mut packages: IndexMap<&str, Vec<&Method>> = IndexMap::new();
+    for method in methods {
+        packages.entry(method.package()).or_default().push(method);
+    }
+
+    packages
+        .into_iter()
+        .map(|(package, methods)| {
+            let mut builder = FileBuilder::new(index, package);
+            for method in methods {
+                builder.add_method(method)?;
+            }
+            Ok(builder.finish())
+        })
+        .collect()
+}
+
+/// The name of a type relative to the innermost of `scopes` enclosing it.
+fn relative<'a>(name: &'a str, scopes: &[String]) -> &'a str {
+    scopes
+        .iter()
+        .rev()
+        .find_map(|scope| name.strip_prefix(scope.as_str()))
+        .unwrap_or(name)
+}
+
+fn print_field(out: &mut String, field: &FieldDescriptorProto, scopes: &[String], indent: &str) {
+    let label = if field.label() == Label::Repeated {
+        "repeated "
+    } else if field.proto3_optional() {
+        "optional "
+    } else {
+        ""
+    };
+    let ty = match field.r#type() {
+        ProtoType::Double => "double",
+        ProtoType::Int32 => "int32",
+        ProtoType::Int64 => "int64",
+        ProtoType::Uint32 => "uint32",
+        ProtoType::Uint64 => "uint64",
+        ProtoType::Bool => "bool",
+        ProtoType::String => "string",
+        _ => relative(field.type_name(), scopes),
+    };
+
+    out.push_str(&format!(
+        "{indent}{label}{ty} {} = {};\n",
+        field.name(),
+        field.number()
+    ));
+}
+
+fn print_message(out: &mut String, message: &DescriptorProto, scopes: &[String], indent: &str) {
+    if message.field.is_empty() && message.nested_type.is_empty() {
+        out.push_str(&format!("{indent}message {} {{}}\n", message.name()));
+        return;
+    }
+
+    out.push_str(&format!("{indent}message {} {{\n", message.name()));
+    let inner = format!("{indent}  ");
+    let mut scopes = scopes.to_vec();
+    let scope = scopes
+        .last()
+        .map(|scope| format!("{scope}{}.", message.name()));
+    scopes.extend(scope);
+    for nested in &message.nested_type {
+        print_message(out, nested, &scopes, &inner);
+        out.push('\n');
+    }
+    for field in &message.field {
+        print_field(out, field, &scopes, &inner);
+    }
+    out.push_str(&format!("{indent}}}\n"));
+}
+
+/// Prints a generated file as the source of a `.proto` file.
+#[must_use]
+pub fn print(file: &FileDescriptorProto) -> String {
+    let scopes = vec![
+        file.package
+            .as_ref()
+            .map_or_else(|| ".".to_string(), |package| format!(".{package}.")),
+    ];
+
+    let mut out = String::from("syntax = \"proto3\";\n");
+    if let Some(package) = &file.package {
+        out.push_str(&format!("\npackage {package};\n"));
+    }
+
+    for enum_ in &file.enum_type {
+        out.push_str(&format!("\nenum {} {{\n", enum_.name()));
+        for value in &enum_.value {
+            out.push_str(&format!("  {} = {};\n", value.name(), value.number()));
+        }
+        out.push_str("}\n");
+    }
+
+    for message in &file.message_type {
+        out.push('\n');
+        print_message(&mut out, message, &scopes, "");
+    }
+
+    for service in &file.service {
+        out.push_str(&format!("\nservice {} {{\n", service.name()));
+        for method in &service.method {
+            out.push_str(&format!(
+                "  rpc {}({}) returns ({});\n",
+                method.name(),
+                relative(method.input_type(), &scopes),
+                relative(method.output_type(), &scopes)
+            ));
+        }
+        out.push_str("}\n");
+    }
+
+    out
+}
@@ -76,7 +76,9 @@ impl HttpIO for Http {
                 let headers_match = req
                     .headers()
                     .iter()
-                    .filter(|(key, _)| *key != "content-type" && *key != "te")
+                    .filter(|(key, _)| {
+                        *key != "content-type" && *key != "te" && *key != "grpc-accept-encoding"
+                    })
                     .all(|(key, value)| {
                         let header_name = key.to_string();
 
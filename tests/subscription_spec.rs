@@ -238,6 +238,31 @@ type Subscription {{
   sensorData: SensorData @http(url: "http://127.0.0.1:{upstream_port}/sse/sensors")
 }}
 
+type SensorData {{
+  temperature: Float!
+  humidity: Float!
+}}"#
+        )
+    }
+
+    /// Like [`generate_schema`], with a `sensorLog` subscription that is
+    /// hidden by the contract on the GraphQL route.
+    fn generate_contract_schema(server_port: u16, upstream_port: u16) -> String {
+        format!(
+            r#"schema @server(port: {server_port}, contracts: [{{route: "/graphql", excludeTags: ["internal"]}}, {{route: "/internal/graphql"}}]) {{
+  query: Query
+  subscription: Subscription
+}}
+
+type Query {{
+  dummy: String @expr(body: "ok")
+}}
+
+type Subscription {{
+  sensorData: SensorData @http(url: "http://127.0.0.1:{upstream_port}/sse/sensors")
+  sensorLog: SensorData @http(url: "http://127.0.0.1:{upstream_port}/sse/sensors") @tag(name: "internal")
+}}
+
 type SensorData {{
   temperature: Float!
   humidity: Float!
@@ -390,4 +415,45 @@ type SensorData {{
         let sse_events = parse_sse_events(&body);
         assert!(sse_events.is_empty());
     }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_http_subscription_sse_contract() {
+        let events = vec![json!({"temperature": 21.0, "humidity": 45.0})];
+
+        let (upstream_port, _upstream_handle) = start_mock_sse_server(events).await;
+        let schema = generate_contract_schema(8813, upstream_port);
+        start_gqlforge_server(&schema).await;
+
+        let client = Client::new();
+        let query = json!({
+            "query": "subscription { sensorLog { temperature humidity } }"
+        });
+        let subscribe = |route: &str| {
+            client
+                .post(format!("http://127.0.0.1:8813{route}"))
+                .json(&query)
+                .send()
+        };
+
+        // The contract on the GraphQL route hides the subscription
+        let body = subscribe("/graphql").await.unwrap().text().await.unwrap();
+        let sse_events = parse_sse_events(&body);
+        assert_eq!(sse_events.len(), 1);
+        assert!(
+            sse_events[0]["errors"][0]["message"]
+                .as_str()
+                .unwrap()
+                .contains("sensorLog")
+        );
+
+        let response = subscribe("/internal/graphql").await.unwrap();
+        let body = tokio::time::timeout(Duration::from_secs(10), response.text())
+            .await
+            .expect("Timeout reading SSE response")
+            .unwrap();
+        assert_eq!(
+            parse_sse_events(&body),
+            vec![json!({"data": {"sensorLog": {"temperature": 21.0, "humidity": 45.0}}})]
+        );
+    }
 }